@@ -1,7 +1,20 @@
 //! PipeWire integration module
 //!
 //! Provides functionality to control PipeWire sample rate based on
-//! the currently playing song in MPD.
+//! the currently playing song in MPD, and to move MPD's stream between sinks.
 
 #[cfg(target_os = "linux")]
 pub mod pipewire;
+
+/// A PipeWire audio sink that MPD's stream can be moved to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioSink {
+    /// Global object id of the sink node
+    pub id: u32,
+    /// Object serial, used as the stream target
+    pub serial: String,
+    /// Node name (e.g. "bluez_output.XX_XX_XX_XX_XX_XX.1")
+    pub name: String,
+    /// Human readable name shown in the sink overlay
+    pub description: String,
+}
@@ -1,13 +1,20 @@
 //! PipeWire sample rate control module
 //!
 //! This module provides functionality to force PipeWire's sample rate
-//! to match the currently playing song in MPD for bit-perfect playback.
+//! to match the currently playing song in MPD for bit-perfect playback,
+//! and to route MPD's output stream to a different sink.
 
+use crate::app::audio::AudioSink;
 use crate::app::logging::log_pipewire_operation;
 use log::{debug, warn};
 use pipewire::{
-    context::ContextBox, main_loop::MainLoopBox, metadata::Metadata, properties::PropertiesBox,
-    registry::GlobalObject, types::ObjectType,
+    context::ContextBox,
+    core::Core,
+    main_loop::{MainLoop, MainLoopBox},
+    metadata::Metadata,
+    properties::PropertiesBox,
+    registry::GlobalObject,
+    types::ObjectType,
 };
 use std::cell::Cell;
 use std::cell::RefCell;
@@ -34,6 +41,19 @@ const SYNC_TIMEOUT: Duration = Duration::from_millis(50);
 /// Iteration step for the main loop
 const LOOP_ITERATION_STEP: Duration = Duration::from_millis(10);
 
+/// Timeout for enumerating all nodes (waits for the registry round trip)
+const ENUMERATION_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The metadata name used by the session manager for default devices and stream targets
+const DEFAULT_METADATA_NAME: &str = "default";
+
+/// Metadata keys the session manager reads to route a stream to a specific node
+const TARGET_OBJECT_KEY: &str = "target.object";
+const TARGET_NODE_KEY: &str = "target.node";
+
+/// Application name MPD reports for its PipeWire and PulseAudio outputs
+const MPD_APP_NAME: &str = "Music Player Daemon";
+
 /// Cache for supported sample rates - populated once on startup, valid for entire program lifetime
 static SUPPORTED_RATES_CACHE: OnceLock<Vec<u32>> = OnceLock::new();
 
@@ -389,6 +409,214 @@ fn parse_allowed_rates(value: &str) -> Vec<u32> {
     rates
 }
 
+/// Wait until the server has processed everything sent so far (and announced all globals).
+///
+/// Returns false if the round trip did not complete within `timeout`.
+fn roundtrip(mainloop: &MainLoop, core: &Core, timeout: Duration) -> Result<bool, String> {
+    let done = Rc::new(Cell::new(false));
+    let done_clone = done.clone();
+
+    let _core_listener = core
+        .add_listener_local()
+        .done(move |_id, _seq| {
+            done_clone.set(true);
+        })
+        .register();
+
+    core.sync(0).map_err(|e| format!("Failed to sync: {e}"))?;
+
+    let start = std::time::Instant::now();
+    while !done.get() && start.elapsed() < timeout {
+        mainloop.loop_().iterate(LOOP_ITERATION_STEP);
+    }
+
+    Ok(done.get())
+}
+
+/// Lists the audio sinks MPD's stream can be moved to, sorted by description.
+pub fn list_sinks() -> Result<Vec<AudioSink>, String> {
+    let result = list_sinks_inner();
+    match &result {
+        Ok(sinks) => {
+            log_pipewire_operation("list_sinks", true, Some(&format!("{} sinks", sinks.len())))
+        }
+        Err(e) => log_pipewire_operation("list_sinks", false, Some(e)),
+    }
+    result
+}
+
+fn list_sinks_inner() -> Result<Vec<AudioSink>, String> {
+    pipewire::init();
+
+    let mainloop =
+        MainLoopBox::new(None).map_err(|e| format!("Failed to create PipeWire MainLoop: {e}"))?;
+    let context = ContextBox::new(mainloop.loop_(), None)
+        .map_err(|e| format!("Failed to create PipeWire Context: {e}"))?;
+    let core = context
+        .connect(None)
+        .map_err(|e| format!("Failed to connect to PipeWire Core: {e}"))?;
+    let registry = core
+        .get_registry()
+        .map_err(|e| format!("Failed to get PipeWire registry: {e}"))?;
+
+    let sinks: Rc<RefCell<Vec<AudioSink>>> = Rc::new(RefCell::new(Vec::new()));
+    let sinks_clone = sinks.clone();
+
+    // Collect every audio sink node announced by the registry
+    let _registry_listener = registry
+        .add_listener_local()
+        .global(move |global| {
+            if global.type_ == ObjectType::Node
+                && let Some(props) = global.props.as_ref()
+                && props.get("media.class") == Some("Audio/Sink")
+            {
+                let name = props.get("node.name").unwrap_or_default().to_string();
+                let description = props
+                    .get("node.description")
+                    .or_else(|| props.get("node.nick"))
+                    .unwrap_or(name.as_str())
+                    .to_string();
+                sinks_clone.borrow_mut().push(AudioSink {
+                    id: global.id,
+                    serial: props
+                        .get("object.serial")
+                        .map(str::to_string)
+                        .unwrap_or_else(|| global.id.to_string()),
+                    name,
+                    description,
+                });
+            }
+        })
+        .register();
+
+    if !roundtrip(&mainloop, &core, ENUMERATION_TIMEOUT)? {
+        warn!("Timeout while enumerating PipeWire sinks, list may be incomplete");
+    }
+
+    let mut sinks = sinks.take();
+    sinks.sort_by_key(|sink| sink.description.to_lowercase());
+    debug!("Found {} PipeWire sinks", sinks.len());
+    Ok(sinks)
+}
+
+/// Moves MPD's output stream(s) to `sink`, or back to the default sink when `None`.
+///
+/// The target is stored in the session manager's "default" metadata, so it
+/// lasts as long as the stream node exists (MPD re-creates it after a restart).
+///
+/// # Returns
+/// * `Ok(n)` with the number of MPD streams that were moved
+/// * `Err(String)` if PipeWire could not be reached or no MPD stream was found
+pub fn move_mpd_to_sink(sink: Option<&AudioSink>) -> Result<usize, String> {
+    let result = move_mpd_to_sink_inner(sink);
+
+    let details = match (&result, sink) {
+        (Ok(_), Some(sink)) => sink.name.clone(),
+        (Ok(_), None) => "default".to_string(),
+        (Err(e), _) => e.clone(),
+    };
+    log_pipewire_operation("move_mpd_stream", result.is_ok(), Some(&details));
+
+    result
+}
+
+fn move_mpd_to_sink_inner(sink: Option<&AudioSink>) -> Result<usize, String> {
+    pipewire::init();
+
+    let mainloop =
+        MainLoopBox::new(None).map_err(|e| format!("Failed to create PipeWire MainLoop: {e}"))?;
+    let context = ContextBox::new(mainloop.loop_(), None)
+        .map_err(|e| format!("Failed to create PipeWire Context: {e}"))?;
+    let core = context
+        .connect(None)
+        .map_err(|e| format!("Failed to connect to PipeWire Core: {e}"))?;
+    let registry = core
+        .get_registry()
+        .map_err(|e| format!("Failed to get PipeWire registry: {e}"))?;
+
+    let found_metadata: Rc<RefCell<Option<GlobalObject<PropertiesBox>>>> =
+        Rc::new(RefCell::new(None));
+    let found_metadata_clone = found_metadata.clone();
+    let mpd_streams: Rc<RefCell<Vec<u32>>> = Rc::new(RefCell::new(Vec::new()));
+    let mpd_streams_clone = mpd_streams.clone();
+
+    // Look for the "default" metadata and MPD's playback stream nodes
+    let _registry_listener = registry
+        .add_listener_local()
+        .global(move |global| {
+            let Some(props) = global.props.as_ref() else {
+                return;
+            };
+            match global.type_ {
+                ObjectType::Metadata
+                    if props.get("metadata.name") == Some(DEFAULT_METADATA_NAME) =>
+                {
+                    *found_metadata_clone.borrow_mut() = Some(global.to_owned());
+                }
+                ObjectType::Node
+                    if props.get("media.class") == Some("Stream/Output/Audio")
+                        && (props.get("application.name") == Some(MPD_APP_NAME)
+                            || props
+                                .get("node.name")
+                                .is_some_and(|name| name.starts_with("mpd."))) =>
+                {
+                    mpd_streams_clone.borrow_mut().push(global.id);
+                }
+                _ => {}
+            }
+        })
+        .register();
+
+    if !roundtrip(&mainloop, &core, ENUMERATION_TIMEOUT)? {
+        warn!("Timeout while enumerating PipeWire nodes");
+    }
+
+    let streams = mpd_streams.take();
+    if streams.is_empty() {
+        return Err("No MPD stream found (is MPD playing through PipeWire?)".to_string());
+    }
+
+    let metadata_ref = found_metadata.borrow();
+    let global = metadata_ref
+        .as_ref()
+        .ok_or_else(|| "PipeWire default metadata not found".to_string())?;
+    let metadata: Metadata = registry
+        .bind(global)
+        .map_err(|e| format!("Failed to bind metadata: {e}"))?;
+
+    // Clearing the target (None) hands the stream back to the default sink
+    let node_id = sink.map(|sink| sink.id.to_string());
+    for &stream in &streams {
+        metadata.set_property(
+            stream,
+            TARGET_OBJECT_KEY,
+            sink.map(|_| "Spa:Id"),
+            sink.map(|sink| sink.serial.as_str()),
+        );
+        metadata.set_property(
+            stream,
+            TARGET_NODE_KEY,
+            sink.map(|_| "Spa:Id"),
+            node_id.as_deref(),
+        );
+        debug!(
+            "Set target of MPD stream {} to {:?}",
+            stream,
+            sink.map(|s| &s.name)
+        );
+    }
+
+    // Drop the borrow before running the loop
+    drop(metadata_ref);
+
+    // Flush the property changes to the server
+    if !roundtrip(&mainloop, &core, SYNC_TIMEOUT)? {
+        warn!("PipeWire sync timed out, stream target may not be applied");
+    }
+
+    Ok(streams.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -440,3 +668,19 @@ pub async fn reset_sample_rate_async() -> Result<(), String> {
         .await
         .map_err(|e| format!("Task join error: {e}"))?
 }
+
+/// Async wrapper for list_sinks that runs the blocking PipeWire call
+/// on a separate thread to avoid blocking the tokio runtime.
+pub async fn list_sinks_async() -> Result<Vec<AudioSink>, String> {
+    tokio::task::spawn_blocking(list_sinks)
+        .await
+        .map_err(|e| format!("Task join error: {e}"))?
+}
+
+/// Async wrapper for move_mpd_to_sink that runs the blocking PipeWire call
+/// on a separate thread to avoid blocking the tokio runtime.
+pub async fn move_mpd_to_sink_async(sink: Option<AudioSink>) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || move_mpd_to_sink(sink.as_ref()))
+        .await
+        .map_err(|e| format!("Task join error: {e}"))?
+}
@@ -1,6 +1,6 @@
 use crate::app::{MenuMode, PanelFocus};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, Instant};
 
 use crate::app::mpd_handler::MPDAction;
@@ -29,11 +29,27 @@ pub struct KeyBinds {
     queue_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
     artists_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
     albums_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
+    files_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
     sequential_bindings: Vec<SequentialKeyBinding>,
     current_state: KeyState,
     default_timeout: Duration,
+    /// Vim-style count typed before a navigation key
+    pending_count: Option<PendingCount>,
 }
 
+/// Count typed so far and the time it expires
+#[derive(Debug, Clone, Copy)]
+struct PendingCount {
+    count: usize,
+    timeout: Instant,
+    /// Bound digit the count started with. Whether it is a count or its own binding is
+    /// only known from the next key.
+    held: Option<(KeyModifiers, KeyCode)>,
+}
+
+/// Largest count prefix, keeps a mistyped count from stalling the UI
+const MAX_COUNT: usize = 999;
+
 impl KeyBinds {
     /// New constructor that supports sequential bindings
     pub fn new_with_sequential(
@@ -41,6 +57,7 @@ impl KeyBinds {
         queue_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
         artists_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
         albums_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
+        files_map: HashMap<(KeyModifiers, KeyCode), MPDAction>,
         sequential_bindings: Vec<SequentialKeyBinding>,
     ) -> Self {
         Self {
@@ -48,10 +65,99 @@ impl KeyBinds {
             queue_map,
             artists_map,
             albums_map,
+            files_map,
             sequential_bindings,
             current_state: KeyState::Idle,
             default_timeout: Duration::from_millis(1000),
+            pending_count: None,
+        }
+    }
+
+    /// Like `handle_key`, but digits typed first are collected into a count that is
+    /// returned along with the action. The count survives a key sequence ("5gg").
+    ///
+    /// A digit bound to an action is held until the next key: another digit or an action
+    /// that takes a count ("10k") makes it a count, anything else fires its binding
+    /// first, as does the count timing out (see [`KeyBinds::update`]).
+    pub fn handle_counted_key(
+        &mut self,
+        key: KeyEvent,
+        mode: &MenuMode,
+        panel_focus: &PanelFocus,
+    ) -> Vec<(MPDAction, Option<usize>)> {
+        let key_tuple = (key.modifiers, key.code);
+        let now = Instant::now();
+        let mut actions = Vec::new();
+        let mut pending = self.pending_count.take();
+        if pending.is_some_and(|pending| pending.timeout < now) {
+            actions.extend(self.held_action(pending.take(), mode, panel_focus));
         }
+
+        if let (KeyModifiers::NONE, KeyCode::Char(c)) = key_tuple
+            && let Some(digit) = c.to_digit(10)
+            && matches!(self.current_state, KeyState::Idle)
+            && (pending.is_some() || digit != 0)
+        {
+            let count = pending.map_or(0, |pending| pending.count) * 10 + digit as usize;
+            self.pending_count = Some(PendingCount {
+                count: count.min(MAX_COUNT),
+                timeout: now + self.default_timeout,
+                held: (pending.is_none() && self.is_bound(key_tuple, mode)).then_some(key_tuple),
+            });
+            return actions;
+        }
+
+        // Any other key ends the count, whether or not it is bound,
+        // unless it starts a sequence the count applies to
+        match self.handle_key(key, mode, panel_focus) {
+            Some(action) if action.uses_count() || pending.is_none_or(|p| p.held.is_none()) => {
+                actions.push((action, pending.map(|pending| pending.count)));
+            }
+            // The held digit was no count after all
+            Some(action) => {
+                actions.extend(self.held_action(pending, mode, panel_focus));
+                actions.push((action, None));
+            }
+            None if matches!(self.current_state, KeyState::Awaiting { .. }) => {
+                self.pending_count = pending.map(|pending| PendingCount {
+                    timeout: now + self.default_timeout,
+                    ..pending
+                });
+            }
+            None => actions.extend(self.held_action(pending, mode, panel_focus)),
+        }
+        actions
+    }
+
+    /// Binding of the digit `pending` holds, if any
+    fn held_action(
+        &mut self,
+        pending: Option<PendingCount>,
+        mode: &MenuMode,
+        panel_focus: &PanelFocus,
+    ) -> Option<(MPDAction, Option<usize>)> {
+        let (modifiers, code) = pending?.held?;
+        self.handle_key(KeyEvent::new(code, modifiers), mode, panel_focus)
+            .map(|action| (action, None))
+    }
+
+    /// Whether a key has a single-key binding in the given mode
+    fn is_bound(&self, key_tuple: (KeyModifiers, KeyCode), mode: &MenuMode) -> bool {
+        self.global_map.contains_key(&key_tuple)
+            || match mode {
+                MenuMode::Queue => self.queue_map.contains_key(&key_tuple),
+                MenuMode::Artists => self.artists_map.contains_key(&key_tuple),
+                MenuMode::Albums => {
+                    self.albums_map.contains_key(&key_tuple)
+                        || self.artists_map.contains_key(&key_tuple)
+                }
+                MenuMode::Files => self.files_map.contains_key(&key_tuple),
+            }
+    }
+
+    /// Count typed so far, if any
+    pub fn pending_count(&self) -> Option<usize> {
+        self.pending_count.map(|pending| pending.count)
     }
 
     /// Handle key events and return corresponding MPD commands with sequential support
@@ -138,6 +244,11 @@ impl KeyBinds {
                     }
                 }
             }
+            MenuMode::Files => {
+                if let Some(action) = self.files_map.get(&key_tuple) {
+                    return Some(action.clone());
+                }
+            }
         }
 
         // Check if this key could start a sequential binding
@@ -221,19 +332,334 @@ impl KeyBinds {
         }
     }
 
-    /// Check if currently awaiting input for a sequence
+    /// Every binding grouped by mode, as (action description, keys) rows for the
+    /// help overlay. Built from the same maps `handle_key` uses, so remaps show up.
+    pub fn help_sections(&self) -> Vec<(&'static str, Vec<(String, String)>)> {
+        let sequences = self.sequential_bindings.iter().map(|binding| {
+            let keys: Vec<String> = binding.sequence.iter().copied().map(key_display).collect();
+            (binding.action.clone(), keys.join(" "))
+        });
+        let global: Vec<_> = self
+            .global_map
+            .iter()
+            .map(|(key, action)| (action.clone(), key_display(*key)))
+            .chain(sequences)
+            .collect();
+
+        [
+            ("Global", global),
+            ("Queue", help_rows(&self.queue_map)),
+            ("Artists", help_rows(&self.artists_map)),
+            ("Albums", help_rows(&self.albums_map)),
+            ("Files", help_rows(&self.files_map)),
+        ]
+        .into_iter()
+        .map(|(title, bindings)| (title, group_by_action(bindings)))
+        .collect()
+    }
+
+    /// Hints for the bottom line in `mode` with `panel_focus` (or in the now playing view),
+    /// as (key, label) pairs. Hints whose action has no key are left out.
+    pub fn key_hints(
+        &self,
+        mode: &MenuMode,
+        panel_focus: &PanelFocus,
+        now_playing: bool,
+    ) -> Vec<(String, &'static str)> {
+        hint_actions(mode, panel_focus, now_playing)
+            .iter()
+            .filter_map(|(action, label)| Some((self.hint_key(action, mode)?, *label)))
+            .collect()
+    }
+
+    /// Simplest key reaching `action` in `mode`: plain keys before modified ones and
+    /// single keys before sequences
+    fn hint_key(&self, action: &MPDAction, mode: &MenuMode) -> Option<String> {
+        let mode_map = match mode {
+            MenuMode::Queue => &self.queue_map,
+            MenuMode::Artists => &self.artists_map,
+            MenuMode::Albums => &self.albums_map,
+            MenuMode::Files => &self.files_map,
+        };
+        // Global bindings win over the mode's own
+        let single = self
+            .global_map
+            .iter()
+            .chain(
+                mode_map
+                    .iter()
+                    .filter(|(key, _)| !self.global_map.contains_key(key)),
+            )
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| (key_rank(*key), key_display(*key)));
+        let sequences = self
+            .sequential_bindings
+            .iter()
+            .filter(|binding| binding.action == *action)
+            .map(|binding| {
+                let keys: Vec<String> = binding.sequence.iter().copied().map(key_display).collect();
+                (3, keys.join(" "))
+            });
+        single.chain(sequences).min().map(|(_, key)| key)
+    }
+
+    /// Check if currently awaiting input for a sequence or after a count
     pub fn is_awaiting_input(&self) -> bool {
-        matches!(self.current_state, KeyState::Awaiting { .. })
+        matches!(self.current_state, KeyState::Awaiting { .. }) || self.pending_count.is_some()
     }
 
-    /// Update method to handle timeouts (call this regularly)
-    pub fn update(&mut self) -> Option<MPDAction> {
+    /// Update method to handle timeouts (call this regularly). Returns the binding of a
+    /// held digit whose count timed out.
+    pub fn update(&mut self, mode: &MenuMode, panel_focus: &PanelFocus) -> Option<MPDAction> {
         if let KeyState::Awaiting { timeout, .. } = &self.current_state
             && *timeout < Instant::now()
         {
             self.current_state = KeyState::Idle;
             // No action on timeout, just reset
         }
+        if self
+            .pending_count
+            .is_some_and(|pending| pending.timeout < Instant::now())
+        {
+            let pending = self.pending_count.take();
+            return self
+                .held_action(pending, mode, panel_focus)
+                .map(|(action, _)| action);
+        }
         None
     }
 }
+
+/// Human readable name of a key, e.g. "Ctrl+k" or "↑"
+pub fn key_display((modifiers, key_code): (KeyModifiers, KeyCode)) -> String {
+    let key_str = match key_code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => format!("{:?}", key_code),
+    };
+
+    // Add modifier prefixes
+    let mut result = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        result.push_str("Ctrl+");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        result.push_str("Alt+");
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        result.push_str("Shift+");
+    }
+    result.push_str(&key_str);
+    result
+}
+
+/// Actions worth a hint in each view, with their labels. Actions are the ones bound in
+/// the mode's map, `handle_key` turns some of them into others depending on the panel.
+fn hint_actions(
+    mode: &MenuMode,
+    panel_focus: &PanelFocus,
+    now_playing: bool,
+) -> &'static [(MPDAction, &'static str)] {
+    if now_playing {
+        return &[
+            (MPDAction::TogglePlayPause, "play/pause"),
+            (MPDAction::Next, "next"),
+            (MPDAction::Previous, "previous"),
+            (MPDAction::ToggleNowPlaying, "back"),
+            (MPDAction::ShowHelp, "help"),
+        ];
+    }
+    match (mode, panel_focus) {
+        (MenuMode::Queue, _) => &[
+            (MPDAction::PlaySelected, "play"),
+            (MPDAction::RemoveFromQueue, "remove"),
+            (MPDAction::ClearQueue, "clear"),
+            (MPDAction::FilterQueue, "filter"),
+            (MPDAction::ShowActions, "actions"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+        (MenuMode::Artists, PanelFocus::Albums) => &[
+            (MPDAction::SwitchPanelRight, "expand"),
+            (MPDAction::AddSongToQueue, "add"),
+            (MPDAction::AddAndPlay, "add and play"),
+            (MPDAction::ReplaceQueueWithAlbum, "play album"),
+            (MPDAction::ShowActions, "actions"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+        (MenuMode::Artists, _) => &[
+            (MPDAction::SwitchPanelRight, "albums"),
+            (MPDAction::AddSongToQueue, "add"),
+            (MPDAction::SearchDatabase, "search"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+        (MenuMode::Albums, PanelFocus::AlbumTracks) => &[
+            (MPDAction::AddAndPlay, "play"),
+            (MPDAction::AddSongToQueue, "add"),
+            (MPDAction::SwitchPanelLeft, "albums"),
+            (MPDAction::ShowActions, "actions"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+        (MenuMode::Albums, _) => &[
+            (MPDAction::PlaySelected, "tracks"),
+            (MPDAction::AddSongToQueue, "add"),
+            (MPDAction::AddAndPlay, "add and play"),
+            (MPDAction::ReplaceQueueWithAlbum, "play album"),
+            (MPDAction::ShowActions, "actions"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+        (MenuMode::Files, _) => &[
+            (MPDAction::SwitchPanelRight, "open"),
+            (MPDAction::SwitchPanelLeft, "up"),
+            (MPDAction::AddSongToQueue, "add"),
+            (MPDAction::AddAndPlay, "play"),
+            (MPDAction::ShowHelp, "help"),
+        ],
+    }
+}
+
+/// How simple a key is to press, lower is simpler
+fn key_rank((modifiers, key_code): (KeyModifiers, KeyCode)) -> u8 {
+    match (modifiers, key_code) {
+        (KeyModifiers::NONE, KeyCode::Char(_) | KeyCode::Enter) => 0,
+        (KeyModifiers::NONE, _) => 1,
+        _ => 2,
+    }
+}
+
+fn help_rows(map: &HashMap<(KeyModifiers, KeyCode), MPDAction>) -> Vec<(MPDAction, String)> {
+    map.iter()
+        .map(|(key, action)| (action.clone(), key_display(*key)))
+        .collect()
+}
+
+/// Merge the keys of each action into one row, sorted by action name
+fn group_by_action(bindings: Vec<(MPDAction, String)>) -> Vec<(String, String)> {
+    let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (action, key) in bindings {
+        grouped
+            .entry(describe_action(&action))
+            .or_default()
+            .push(key);
+    }
+    grouped
+        .into_iter()
+        .map(|(action, mut keys)| {
+            keys.sort();
+            (action, keys.join(", "))
+        })
+        .collect()
+}
+
+/// Turn an action name like "TogglePlayPause" into "Toggle play pause"
+fn describe_action(action: &MPDAction) -> String {
+    let name = action.to_string();
+    let mut description = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            description.push(' ');
+            description.push(c.to_ascii_lowercase());
+        } else {
+            description.push(c);
+        }
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Config;
+
+    fn default_binds() -> KeyBinds {
+        let (global_map, queue_map, artists_map, albums_map, files_map, sequential_bindings) =
+            Config::default().binds.build_enhanced_key_maps();
+        KeyBinds::new_with_sequential(
+            global_map,
+            queue_map,
+            artists_map,
+            albums_map,
+            files_map,
+            sequential_bindings,
+        )
+    }
+
+    fn press(binds: &mut KeyBinds, keys: &str) -> Vec<(MPDAction, Option<usize>)> {
+        keys.chars()
+            .flat_map(|c| {
+                binds.handle_counted_key(
+                    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+                    &MenuMode::Queue,
+                    &PanelFocus::Artists,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_count_can_start_with_a_bound_digit() {
+        let mut binds = default_binds();
+        // 1 switches views by default, but starts the count here
+        assert_eq!(
+            press(&mut binds, "10k"),
+            vec![(MPDAction::QueueUp, Some(10))]
+        );
+        assert_eq!(
+            press(&mut binds, "2j"),
+            vec![(MPDAction::QueueDown, Some(2))]
+        );
+
+        // Followed by a key that takes no count, the digit keeps its binding
+        assert_eq!(
+            press(&mut binds, "2p"),
+            vec![
+                (MPDAction::SwitchToArtists, None),
+                (MPDAction::TogglePlayPause, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_held_digit_fires_on_timeout() {
+        let mut binds = default_binds();
+        binds.default_timeout = Duration::ZERO;
+        assert!(press(&mut binds, "3").is_empty());
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(
+            binds.update(&MenuMode::Queue, &PanelFocus::Artists),
+            Some(MPDAction::SwitchToAlbums)
+        );
+        assert_eq!(binds.pending_count(), None);
+    }
+
+    #[test]
+    fn test_key_hints_use_simplest_key() {
+        let hints = default_binds().key_hints(&MenuMode::Queue, &PanelFocus::Artists, false);
+        let key = |label: &str| {
+            hints
+                .iter()
+                .find(|(_, hint)| *hint == label)
+                .map(|(key, _)| key.as_str())
+        };
+
+        // Enter beats l and the arrow, a single key beats the "d d" sequence
+        assert_eq!(key("play"), Some("Enter"));
+        assert_eq!(key("remove"), Some("x"));
+        assert_eq!(key("clear"), Some("d d"));
+        assert_eq!(key("filter"), Some("Ctrl+f"));
+    }
+}
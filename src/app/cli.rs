@@ -1,4 +1,5 @@
 use clap::Parser;
+use log::LevelFilter;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone)]
@@ -22,6 +23,33 @@ pub struct Args {
     /// Generate default config file at the specified path (or ./config.toml if not specified)
     #[arg(long, value_name = "PATH", num_args = 0..=1, require_equals = false, default_missing_value = ".")]
     pub generate_config: Option<PathBuf>,
+
+    /// Log more: -v info, -vv debug, -vvv trace (overrides the config's level)
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only log errors (overrides the config's level)
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Write the log to this file, turning logging on if the config has it off
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+}
+
+impl Args {
+    /// Log level picked with -v or -q, None keeps the configured one
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        if self.quiet {
+            return Some(LevelFilter::Error);
+        }
+        match self.verbose {
+            0 => None,
+            1 => Some(LevelFilter::Info),
+            2 => Some(LevelFilter::Debug),
+            _ => Some(LevelFilter::Trace),
+        }
+    }
 }
 
 /// Parse "on" or "off" string to boolean
@@ -32,3 +60,27 @@ fn parse_on_off(s: &str) -> Result<bool, String> {
         _ => Err(format!("Invalid value '{}': expected 'on' or 'off'", s)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_level(args: &[&str]) -> Option<LevelFilter> {
+        Args::try_parse_from(std::iter::once("zarumet").chain(args.iter().copied()))
+            .unwrap()
+            .log_level()
+    }
+
+    #[test]
+    fn test_log_level_flags() {
+        assert_eq!(log_level(&[]), None);
+        assert_eq!(log_level(&["-v"]), Some(LevelFilter::Info));
+        assert_eq!(log_level(&["-vv"]), Some(LevelFilter::Debug));
+        assert_eq!(
+            log_level(&["-v", "-v", "-v", "-v"]),
+            Some(LevelFilter::Trace)
+        );
+        assert_eq!(log_level(&["-q"]), Some(LevelFilter::Error));
+        assert!(Args::try_parse_from(["zarumet", "-v", "-q"]).is_err());
+    }
+}
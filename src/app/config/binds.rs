@@ -44,6 +44,8 @@ pub struct BindsConfig {
     pub switch_to_artists: Vec<String>,
     #[serde(default = "BindsConfig::default_switch_to_albums")]
     pub switch_to_albums: Vec<String>,
+    #[serde(default = "BindsConfig::default_switch_to_files")]
+    pub switch_to_files: Vec<String>,
     #[serde(default = "BindsConfig::default_seek_forward")]
     pub seek_forward: Vec<String>,
     #[serde(default = "BindsConfig::default_seek_backward")]
@@ -78,6 +80,66 @@ pub struct BindsConfig {
     pub go_to_bottom: Vec<String>,
     #[serde(default = "BindsConfig::default_toggle_bit_perfect")]
     pub toggle_bit_perfect: Vec<String>,
+    #[serde(default = "BindsConfig::default_stop_after_current")]
+    pub stop_after_current: Vec<String>,
+    #[serde(default = "BindsConfig::default_open_sleep_timer")]
+    pub open_sleep_timer: Vec<String>,
+    #[serde(default = "BindsConfig::default_deduplicate_queue")]
+    pub deduplicate_queue: Vec<String>,
+    #[serde(default = "BindsConfig::default_crop_queue")]
+    pub crop_queue: Vec<String>,
+    #[serde(default = "BindsConfig::default_shuffle_queue")]
+    pub shuffle_queue: Vec<String>,
+    #[serde(default = "BindsConfig::default_enter_volume")]
+    pub enter_volume: Vec<String>,
+    #[serde(default = "BindsConfig::default_add_and_play")]
+    pub add_and_play: Vec<String>,
+    #[serde(default = "BindsConfig::default_open_output_sinks")]
+    pub open_output_sinks: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_track_info")]
+    pub show_track_info: Vec<String>,
+    #[serde(default = "BindsConfig::default_search_database")]
+    pub search_database: Vec<String>,
+    #[serde(default = "BindsConfig::default_add_to_queue_alternate")]
+    pub add_to_queue_alternate: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_mpd_settings")]
+    pub show_mpd_settings: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_help")]
+    pub show_help: Vec<String>,
+    #[serde(default = "BindsConfig::default_page_up")]
+    pub page_up: Vec<String>,
+    #[serde(default = "BindsConfig::default_page_down")]
+    pub page_down: Vec<String>,
+    #[serde(default = "BindsConfig::default_toggle_grouping")]
+    pub toggle_grouping: Vec<String>,
+    #[serde(default = "BindsConfig::default_toggle_now_playing")]
+    pub toggle_now_playing: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_partitions")]
+    pub show_partitions: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_actions")]
+    pub show_actions: Vec<String>,
+    #[serde(default = "BindsConfig::default_go_to_artist")]
+    pub go_to_artist: Vec<String>,
+    #[serde(default = "BindsConfig::default_go_to_album")]
+    pub go_to_album: Vec<String>,
+    #[serde(default = "BindsConfig::default_remove_album_from_queue")]
+    pub remove_album_from_queue: Vec<String>,
+    #[serde(default = "BindsConfig::default_filter_queue")]
+    pub filter_queue: Vec<String>,
+    #[serde(default = "BindsConfig::default_back")]
+    pub back: Vec<String>,
+    #[serde(default = "BindsConfig::default_cycle_repeat")]
+    pub cycle_repeat: Vec<String>,
+    #[serde(default = "BindsConfig::default_show_library_stats")]
+    pub show_library_stats: Vec<String>,
+    #[serde(default = "BindsConfig::default_copy_song_info")]
+    pub copy_song_info: Vec<String>,
+    #[serde(default = "BindsConfig::default_go_to_current")]
+    pub go_to_current: Vec<String>,
+    #[serde(default = "BindsConfig::default_parent_directory")]
+    pub parent_directory: Vec<String>,
+    #[serde(default = "BindsConfig::default_replace_queue_with_album")]
+    pub replace_queue_with_album: Vec<String>,
 }
 
 impl BindsConfig {
@@ -137,7 +199,6 @@ impl BindsConfig {
 
     fn default_quit_enhanced() -> Vec<String> {
         vec![
-            "esc".to_string(),
             "q".to_string(),
             "ctrl-c".to_string(),
             "shift-z shift-z".to_string(),
@@ -155,6 +216,9 @@ impl BindsConfig {
     fn default_switch_to_albums() -> Vec<String> {
         vec!["3".to_string()]
     }
+    fn default_switch_to_files() -> Vec<String> {
+        vec!["4".to_string()]
+    }
     fn default_seek_forward() -> Vec<String> {
         vec!["shift-l".to_string(), "shift-right".to_string()]
     }
@@ -201,7 +265,7 @@ impl BindsConfig {
         vec!["l".to_string(), "right".to_string()]
     }
     fn default_add_to_queue() -> Vec<String> {
-        vec!["a".to_string(), "enter".to_string()]
+        vec!["a".to_string()]
     }
     fn default_scroll_up_big() -> Vec<String> {
         vec!["ctrl-u".to_string()]
@@ -218,6 +282,96 @@ impl BindsConfig {
     fn default_toggle_bit_perfect() -> Vec<String> {
         vec!["b".to_string()]
     }
+    fn default_stop_after_current() -> Vec<String> {
+        vec!["shift-s".to_string()]
+    }
+    fn default_open_sleep_timer() -> Vec<String> {
+        vec!["t".to_string()]
+    }
+    fn default_deduplicate_queue() -> Vec<String> {
+        vec!["shift-d".to_string()]
+    }
+    fn default_crop_queue() -> Vec<String> {
+        vec!["shift-c".to_string()]
+    }
+    fn default_shuffle_queue() -> Vec<String> {
+        vec!["shift-r".to_string()]
+    }
+    fn default_enter_volume() -> Vec<String> {
+        vec!["v".to_string()]
+    }
+    fn default_add_and_play() -> Vec<String> {
+        vec!["enter".to_string()]
+    }
+    fn default_open_output_sinks() -> Vec<String> {
+        vec!["o".to_string()]
+    }
+    fn default_show_track_info() -> Vec<String> {
+        vec!["i".to_string()]
+    }
+    fn default_search_database() -> Vec<String> {
+        vec!["/".to_string()]
+    }
+    fn default_add_to_queue_alternate() -> Vec<String> {
+        vec!["shift-a".to_string()]
+    }
+    fn default_show_mpd_settings() -> Vec<String> {
+        vec!["shift-m".to_string()]
+    }
+    fn default_show_help() -> Vec<String> {
+        vec!["?".to_string()]
+    }
+    fn default_page_up() -> Vec<String> {
+        vec!["pageup".to_string()]
+    }
+    fn default_page_down() -> Vec<String> {
+        vec!["pagedown".to_string()]
+    }
+    fn default_toggle_grouping() -> Vec<String> {
+        vec!["ctrl-g".to_string()]
+    }
+    fn default_toggle_now_playing() -> Vec<String> {
+        vec!["f".to_string()]
+    }
+    fn default_show_partitions() -> Vec<String> {
+        vec!["shift-p".to_string()]
+    }
+    fn default_show_actions() -> Vec<String> {
+        vec![".".to_string()]
+    }
+    fn default_go_to_artist() -> Vec<String> {
+        vec!["g a".to_string()]
+    }
+    fn default_go_to_album() -> Vec<String> {
+        vec!["g l".to_string()]
+    }
+    fn default_remove_album_from_queue() -> Vec<String> {
+        vec!["d a".to_string()]
+    }
+    fn default_filter_queue() -> Vec<String> {
+        vec!["ctrl-f".to_string()]
+    }
+    fn default_back() -> Vec<String> {
+        vec!["esc".to_string()]
+    }
+    fn default_cycle_repeat() -> Vec<String> {
+        vec!["ctrl-r".to_string()]
+    }
+    fn default_show_library_stats() -> Vec<String> {
+        vec!["shift-i".to_string()]
+    }
+    fn default_copy_song_info() -> Vec<String> {
+        vec!["y".to_string()]
+    }
+    fn default_go_to_current() -> Vec<String> {
+        vec!["g c".to_string()]
+    }
+    fn default_parent_directory() -> Vec<String> {
+        vec!["backspace".to_string()]
+    }
+    fn default_replace_queue_with_album() -> Vec<String> {
+        vec!["shift-enter".to_string(), "shift-e".to_string()]
+    }
 
     pub fn parse_keybinding(
         &self,
@@ -329,6 +483,10 @@ impl BindsConfig {
             (crossterm::event::KeyModifiers, crossterm::event::KeyCode),
             crate::app::mpd_handler::MPDAction,
         >,
+        HashMap<
+            (crossterm::event::KeyModifiers, crossterm::event::KeyCode),
+            crate::app::mpd_handler::MPDAction,
+        >,
         Vec<SequentialKeyBinding>,
     ) {
         self.build_enhanced_key_maps_internal()
@@ -355,12 +513,17 @@ impl BindsConfig {
             (crossterm::event::KeyModifiers, crossterm::event::KeyCode),
             crate::app::mpd_handler::MPDAction,
         >,
+        HashMap<
+            (crossterm::event::KeyModifiers, crossterm::event::KeyCode),
+            crate::app::mpd_handler::MPDAction,
+        >,
         Vec<SequentialKeyBinding>,
     ) {
         let mut global_map = HashMap::new();
         let mut queue_map = HashMap::new();
         let mut artists_map = HashMap::new();
         let mut albums_map = HashMap::new();
+        let mut files_map = HashMap::new();
         let mut sequential_bindings = Vec::new();
 
         // Global bindings (always available)
@@ -375,11 +538,15 @@ impl BindsConfig {
         // Albums mode specific bindings
         self.add_enhanced_albums_bindings(&mut albums_map, &mut sequential_bindings);
 
+        // Files mode specific bindings
+        self.add_enhanced_files_bindings(&mut files_map, &mut sequential_bindings);
+
         (
             global_map,
             queue_map,
             artists_map,
             albums_map,
+            files_map,
             sequential_bindings,
         )
     }
@@ -513,6 +680,12 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.switch_to_files,
+            crate::app::mpd_handler::MPDAction::SwitchToFiles,
+            single_map,
+            sequential_bindings,
+        );
         self.add_enhanced_binding_for_action(
             &self.seek_forward,
             crate::app::mpd_handler::MPDAction::SeekForward,
@@ -531,6 +704,102 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.stop_after_current,
+            crate::app::mpd_handler::MPDAction::StopAfterCurrent,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.open_sleep_timer,
+            crate::app::mpd_handler::MPDAction::OpenSleepTimer,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.enter_volume,
+            crate::app::mpd_handler::MPDAction::EnterVolume,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.open_output_sinks,
+            crate::app::mpd_handler::MPDAction::OpenOutputSinks,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_track_info,
+            crate::app::mpd_handler::MPDAction::ShowTrackInfo,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.search_database,
+            crate::app::mpd_handler::MPDAction::SearchDatabase,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_mpd_settings,
+            crate::app::mpd_handler::MPDAction::ShowMpdSettings,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_help,
+            crate::app::mpd_handler::MPDAction::ShowHelp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.toggle_grouping,
+            crate::app::mpd_handler::MPDAction::ToggleGrouping,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.toggle_now_playing,
+            crate::app::mpd_handler::MPDAction::ToggleNowPlaying,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_partitions,
+            crate::app::mpd_handler::MPDAction::ShowPartitions,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_actions,
+            crate::app::mpd_handler::MPDAction::ShowActions,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.back,
+            crate::app::mpd_handler::MPDAction::Back,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.cycle_repeat,
+            crate::app::mpd_handler::MPDAction::CycleRepeat,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.show_library_stats,
+            crate::app::mpd_handler::MPDAction::ShowLibraryStats,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.copy_song_info,
+            crate::app::mpd_handler::MPDAction::CopySongInfo,
+            single_map,
+            sequential_bindings,
+        );
     }
 
     /// Helper method to add bindings that may be sequential
@@ -629,6 +898,66 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.deduplicate_queue,
+            crate::app::mpd_handler::MPDAction::DeduplicateQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.crop_queue,
+            crate::app::mpd_handler::MPDAction::CropQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.shuffle_queue,
+            crate::app::mpd_handler::MPDAction::ShuffleQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_up,
+            crate::app::mpd_handler::MPDAction::PageUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_down,
+            crate::app::mpd_handler::MPDAction::PageDown,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_artist,
+            crate::app::mpd_handler::MPDAction::GoToArtist,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_album,
+            crate::app::mpd_handler::MPDAction::GoToAlbum,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.remove_album_from_queue,
+            crate::app::mpd_handler::MPDAction::RemoveAlbumFromQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.filter_queue,
+            crate::app::mpd_handler::MPDAction::FilterQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_current,
+            crate::app::mpd_handler::MPDAction::GoToCurrent,
+            single_map,
+            sequential_bindings,
+        );
     }
 
     fn add_enhanced_artists_bindings(
@@ -703,6 +1032,36 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.add_and_play,
+            crate::app::mpd_handler::MPDAction::AddAndPlay,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_to_queue_alternate,
+            crate::app::mpd_handler::MPDAction::AddToQueueAlternate,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.replace_queue_with_album,
+            crate::app::mpd_handler::MPDAction::ReplaceQueueWithAlbum,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_up,
+            crate::app::mpd_handler::MPDAction::PageUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_down,
+            crate::app::mpd_handler::MPDAction::PageDown,
+            single_map,
+            sequential_bindings,
+        );
     }
 
     fn add_enhanced_albums_bindings(
@@ -785,6 +1144,131 @@ impl BindsConfig {
             single_map,
             sequential_bindings,
         );
+        self.add_enhanced_binding_for_action(
+            &self.add_and_play,
+            crate::app::mpd_handler::MPDAction::AddAndPlay,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_to_queue_alternate,
+            crate::app::mpd_handler::MPDAction::AddToQueueAlternate,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.replace_queue_with_album,
+            crate::app::mpd_handler::MPDAction::ReplaceQueueWithAlbum,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_up,
+            crate::app::mpd_handler::MPDAction::PageUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_down,
+            crate::app::mpd_handler::MPDAction::PageDown,
+            single_map,
+            sequential_bindings,
+        );
+    }
+
+    fn add_enhanced_files_bindings(
+        &self,
+        single_map: &mut HashMap<
+            (crossterm::event::KeyModifiers, crossterm::event::KeyCode),
+            crate::app::mpd_handler::MPDAction,
+        >,
+        sequential_bindings: &mut Vec<SequentialKeyBinding>,
+    ) {
+        // Files mode: the panel keys go into and out of directories
+        self.add_enhanced_binding_for_action(
+            &self.switch_panel_left,
+            crate::app::mpd_handler::MPDAction::SwitchPanelLeft,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.parent_directory,
+            crate::app::mpd_handler::MPDAction::SwitchPanelLeft,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.switch_panel_right,
+            crate::app::mpd_handler::MPDAction::SwitchPanelRight,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.scroll_up,
+            crate::app::mpd_handler::MPDAction::NavigateUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.scroll_down,
+            crate::app::mpd_handler::MPDAction::NavigateDown,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_to_queue,
+            crate::app::mpd_handler::MPDAction::AddSongToQueue,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_and_play,
+            crate::app::mpd_handler::MPDAction::AddAndPlay,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.add_to_queue_alternate,
+            crate::app::mpd_handler::MPDAction::AddToQueueAlternate,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.scroll_up_big,
+            crate::app::mpd_handler::MPDAction::ScrollUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.scroll_down_big,
+            crate::app::mpd_handler::MPDAction::ScrollDown,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_top,
+            crate::app::mpd_handler::MPDAction::GoToTop,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.go_to_bottom,
+            crate::app::mpd_handler::MPDAction::GoToBottom,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_up,
+            crate::app::mpd_handler::MPDAction::PageUp,
+            single_map,
+            sequential_bindings,
+        );
+        self.add_enhanced_binding_for_action(
+            &self.page_down,
+            crate::app::mpd_handler::MPDAction::PageDown,
+            single_map,
+            sequential_bindings,
+        );
     }
 }
 
@@ -811,6 +1295,7 @@ impl Default for BindsConfig {
             switch_to_queue_menu: Self::default_switch_to_queue_menu(),
             switch_to_artists: Self::default_switch_to_artists(),
             switch_to_albums: Self::default_switch_to_albums(),
+            switch_to_files: Self::default_switch_to_files(),
             seek_forward: Self::default_seek_forward(),
             seek_backward: Self::default_seek_backward(),
             play_selected: Self::default_play_selected(),
@@ -828,6 +1313,36 @@ impl Default for BindsConfig {
             go_to_top: Self::default_go_to_top(),
             go_to_bottom: Self::default_go_to_bottom(),
             toggle_bit_perfect: Self::default_toggle_bit_perfect(),
+            stop_after_current: Self::default_stop_after_current(),
+            open_sleep_timer: Self::default_open_sleep_timer(),
+            deduplicate_queue: Self::default_deduplicate_queue(),
+            crop_queue: Self::default_crop_queue(),
+            shuffle_queue: Self::default_shuffle_queue(),
+            enter_volume: Self::default_enter_volume(),
+            add_and_play: Self::default_add_and_play(),
+            open_output_sinks: Self::default_open_output_sinks(),
+            show_track_info: Self::default_show_track_info(),
+            search_database: Self::default_search_database(),
+            add_to_queue_alternate: Self::default_add_to_queue_alternate(),
+            show_mpd_settings: Self::default_show_mpd_settings(),
+            show_help: Self::default_show_help(),
+            page_up: Self::default_page_up(),
+            page_down: Self::default_page_down(),
+            toggle_grouping: Self::default_toggle_grouping(),
+            toggle_now_playing: Self::default_toggle_now_playing(),
+            show_partitions: Self::default_show_partitions(),
+            show_actions: Self::default_show_actions(),
+            go_to_artist: Self::default_go_to_artist(),
+            go_to_album: Self::default_go_to_album(),
+            remove_album_from_queue: Self::default_remove_album_from_queue(),
+            filter_queue: Self::default_filter_queue(),
+            back: Self::default_back(),
+            cycle_repeat: Self::default_cycle_repeat(),
+            show_library_stats: Self::default_show_library_stats(),
+            copy_song_info: Self::default_copy_song_info(),
+            go_to_current: Self::default_go_to_current(),
+            parent_directory: Self::default_parent_directory(),
+            replace_queue_with_album: Self::default_replace_queue_with_album(),
         }
     }
 }
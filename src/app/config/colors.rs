@@ -16,6 +16,12 @@ pub struct ColorsConfig {
     pub progress_filled: String,
     #[serde(default = "ColorsConfig::default_progress_empty")]
     pub progress_empty: String,
+    /// Filled part of the progress bar while paused (`progress_filled` is used while playing)
+    #[serde(default = "ColorsConfig::default_progress_paused")]
+    pub progress_paused: String,
+    /// Filled part of the progress bar while stopped
+    #[serde(default = "ColorsConfig::default_progress_stopped")]
+    pub progress_stopped: String,
     #[serde(default = "ColorsConfig::default_paused")]
     pub paused: String,
     #[serde(default = "ColorsConfig::default_playing")]
@@ -52,6 +58,8 @@ pub struct ColorsConfig {
     pub mode: String,
     #[serde(default = "ColorsConfig::default_track_duration")]
     pub track_duration: String,
+    #[serde(default = "ColorsConfig::default_cover_border")]
+    pub cover_border: String,
 }
 
 impl ColorsConfig {
@@ -115,6 +123,21 @@ impl ColorsConfig {
             .unwrap_or(ratatui::style::Color::Green)
     }
 
+    /// Color of the filled progress bar for the given play state
+    pub fn progress_state_color(
+        &self,
+        play_state: Option<mpd_client::responses::PlayState>,
+    ) -> ratatui::style::Color {
+        let hex = match play_state {
+            Some(mpd_client::responses::PlayState::Playing) => &self.progress_filled,
+            Some(mpd_client::responses::PlayState::Paused) => &self.progress_paused,
+            Some(mpd_client::responses::PlayState::Stopped) | None => &self.progress_stopped,
+        };
+        Self::parse_hex(hex)
+            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
+            .unwrap_or_else(|| self.progress_filled_color())
+    }
+
     pub fn progress_empty_color(&self) -> ratatui::style::Color {
         Self::parse_hex(&self.progress_empty)
             .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
@@ -216,6 +239,12 @@ impl ColorsConfig {
             .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
             .unwrap_or(ratatui::style::Color::Red)
     }
+
+    pub fn cover_border_color(&self) -> ratatui::style::Color {
+        Self::parse_hex(&self.cover_border)
+            .map(|(r, g, b)| ratatui::style::Color::Rgb(r, g, b))
+            .unwrap_or(ratatui::style::Color::White)
+    }
 }
 
 impl ColorsConfig {
@@ -247,6 +276,14 @@ impl ColorsConfig {
         "#1b1d0e".to_string()
     }
 
+    fn default_progress_paused() -> String {
+        "#b18a4a".to_string()
+    }
+
+    fn default_progress_stopped() -> String {
+        "#8193af".to_string()
+    }
+
     fn default_paused() -> String {
         "#e16a7c".to_string()
     }
@@ -311,6 +348,10 @@ impl ColorsConfig {
         "#e16a7c".to_string()
     }
 
+    fn default_cover_border() -> String {
+        "#fae280".to_string()
+    }
+
     fn default_volume_empty() -> String {
         "#1b1d0e".to_string()
     }
@@ -333,6 +374,8 @@ impl Default for ColorsConfig {
             stopped: Self::default_stopped(),
             progress_filled: Self::default_progress_filled(),
             progress_empty: Self::default_progress_empty(),
+            progress_paused: Self::default_progress_paused(),
+            progress_stopped: Self::default_progress_stopped(),
             time_elapsed: Self::default_time_elapsed(),
             time_separator: Self::default_time_separator(),
             time_duration: Self::default_time_duration(),
@@ -348,6 +391,7 @@ impl Default for ColorsConfig {
             volume_empty: Self::default_volume_empty(),
             mode: Self::default_mode(),
             track_duration: Self::default_track_duration(),
+            cover_border: Self::default_cover_border(),
         }
     }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::mpd_handler::MPDAction;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ConfirmConfig {
+    /// Ask before clearing the whole queue
+    #[serde(default = "ConfirmConfig::default_clear_queue")]
+    pub clear_queue: bool,
+    /// Ask before removing everything except the current song
+    #[serde(default = "ConfirmConfig::default_crop_queue")]
+    pub crop_queue: bool,
+    /// Ask before adding a folder with more songs than this (0 never asks)
+    #[serde(default = "ConfirmConfig::default_add_folder_threshold")]
+    pub add_folder_threshold: usize,
+    /// Ask before replacing a queue holding songs from other albums
+    #[serde(default = "ConfirmConfig::default_replace_queue")]
+    pub replace_queue: bool,
+}
+
+impl ConfirmConfig {
+    fn default_clear_queue() -> bool {
+        true
+    }
+
+    fn default_crop_queue() -> bool {
+        true
+    }
+
+    fn default_add_folder_threshold() -> usize {
+        500
+    }
+
+    fn default_replace_queue() -> bool {
+        true
+    }
+
+    /// Check if `action` has to be confirmed before it runs
+    pub fn requires(&self, action: &MPDAction) -> bool {
+        match action {
+            MPDAction::ClearQueue => self.clear_queue,
+            MPDAction::CropQueue => self.crop_queue,
+            MPDAction::ReplaceQueueWithAlbum => self.replace_queue,
+            _ => false,
+        }
+    }
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            clear_queue: Self::default_clear_queue(),
+            crop_queue: Self::default_crop_queue(),
+            add_folder_threshold: Self::default_add_folder_threshold(),
+            replace_queue: Self::default_replace_queue(),
+        }
+    }
+}
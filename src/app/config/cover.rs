@@ -0,0 +1,264 @@
+use crate::app::config::colors::ColorsConfig;
+use crate::app::config::format::unknown_value_warning;
+use ratatui::widgets::BorderType;
+use serde::{Deserialize, Serialize};
+
+/// A place MPD can read cover art from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverSource {
+    /// Picture embedded in the audio file (MPD command: readpicture)
+    Embedded,
+    /// Image file next to the audio file, e.g. cover.jpg (MPD command: albumart)
+    Folder,
+}
+
+impl std::fmt::Display for CoverSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoverSource::Embedded => write!(f, "embedded"),
+            CoverSource::Folder => write!(f, "folder"),
+        }
+    }
+}
+
+/// Where the cover is placed inside the available image area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverPosition {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// How a cover whose aspect ratio differs from its area is scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverScaling {
+    /// Show the whole cover, leaving the rest of the area empty (letterboxing)
+    Fit,
+    /// Cover the whole area, cropping the overflowing sides
+    Fill,
+}
+
+/// What is drawn in the cover area of songs without cover art
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverPlaceholder {
+    /// The built-in image: a music note on a gray gradient
+    Default,
+    /// The album's initials on a gradient colored after the album name
+    Initials,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CoverConfig {
+    /// Fetch and display cover art (disable for bandwidth-constrained sessions)
+    #[serde(default = "CoverConfig::default_enabled")]
+    pub enabled: bool,
+    /// Order in which cover art sources are tried ("embedded", "folder")
+    #[serde(default = "CoverConfig::default_sources")]
+    pub sources: Vec<String>,
+    /// Maximum cover width in terminal cells (0 = use all available space)
+    #[serde(default = "CoverConfig::default_max_size")]
+    pub max_width: u16,
+    /// Maximum cover height in terminal cells (0 = use all available space)
+    #[serde(default = "CoverConfig::default_max_size")]
+    pub max_height: u16,
+    /// Placement inside the image area (center, top, bottom, left, right)
+    #[serde(default = "CoverConfig::default_position")]
+    pub position: String,
+    /// Share one cached cover between all tracks of an album instead of caching per file
+    #[serde(default = "CoverConfig::default_cache_by_album")]
+    pub cache_by_album: bool,
+    /// Frame drawn around the cover (none, plain, rounded, double, thick)
+    #[serde(default = "CoverConfig::default_border")]
+    pub border: String,
+    /// Scaling of covers that don't match the area's shape ("fit" or "fill")
+    #[serde(default = "CoverConfig::default_scaling")]
+    pub scaling: String,
+    /// Hex color of the letterbox around a fitted cover (empty = terminal background)
+    #[serde(default = "CoverConfig::default_background")]
+    pub background: String,
+    /// Covers larger than this many pixels on their longer side are scaled down before
+    /// caching (0 = keep the original size; the cache still stays within its byte budget)
+    #[serde(default = "CoverConfig::default_max_dimension")]
+    pub max_dimension: u32,
+    /// Shown for songs without cover art ("none", "default" or "initials")
+    #[serde(default = "CoverConfig::default_placeholder")]
+    pub placeholder: String,
+    /// Covers prefetched on either side of the current song in the queue (0 = none)
+    #[serde(default = "CoverConfig::default_prefetch_count")]
+    pub prefetch_count: usize,
+    /// Cover art fetches sent to MPD at the same time, the rest wait their turn
+    #[serde(default = "CoverConfig::default_max_concurrent_fetches")]
+    pub max_concurrent_fetches: usize,
+}
+
+impl CoverConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn default_sources() -> Vec<String> {
+        vec!["embedded".to_string(), "folder".to_string()]
+    }
+
+    fn default_cache_by_album() -> bool {
+        false
+    }
+
+    fn default_max_size() -> u16 {
+        0
+    }
+
+    fn default_position() -> String {
+        "center".to_string()
+    }
+
+    fn default_border() -> String {
+        "none".to_string()
+    }
+
+    fn default_scaling() -> String {
+        "fit".to_string()
+    }
+
+    fn default_background() -> String {
+        String::new()
+    }
+
+    fn default_max_dimension() -> u32 {
+        1000
+    }
+
+    fn default_placeholder() -> String {
+        "none".to_string()
+    }
+
+    fn default_prefetch_count() -> usize {
+        2
+    }
+
+    fn default_max_concurrent_fetches() -> usize {
+        4
+    }
+
+    /// Parse the configured scaling, falling back to fit
+    pub fn cover_scaling(&self) -> CoverScaling {
+        if self.scaling.eq_ignore_ascii_case("fill") {
+            CoverScaling::Fill
+        } else {
+            CoverScaling::Fit
+        }
+    }
+
+    /// Parse the configured placeholder, None to leave the area empty (also the fallback)
+    pub fn cover_placeholder(&self) -> Option<CoverPlaceholder> {
+        match self.placeholder.to_lowercase().as_str() {
+            "default" => Some(CoverPlaceholder::Default),
+            "initials" => Some(CoverPlaceholder::Initials),
+            _ => None,
+        }
+    }
+
+    /// Parse the configured letterbox color, None to leave the terminal background
+    pub fn background_rgb(&self) -> Option<(u8, u8, u8)> {
+        ColorsConfig::parse_hex(&self.background)
+    }
+
+    /// Parse the configured frame, None for no frame (also the fallback)
+    pub fn cover_border(&self) -> Option<BorderType> {
+        match self.border.to_lowercase().as_str() {
+            "plain" => Some(BorderType::Plain),
+            "rounded" => Some(BorderType::Rounded),
+            "double" => Some(BorderType::Double),
+            "thick" => Some(BorderType::Thick),
+            _ => None,
+        }
+    }
+
+    /// Parse the configured placement, falling back to center
+    pub fn cover_position(&self) -> CoverPosition {
+        match self.position.to_lowercase().as_str() {
+            "top" => CoverPosition::Top,
+            "bottom" => CoverPosition::Bottom,
+            "left" => CoverPosition::Left,
+            "right" => CoverPosition::Right,
+            _ => CoverPosition::Center,
+        }
+    }
+
+    /// Parse the configured sources in order, skipping unknown entries
+    pub fn cover_sources(&self) -> Vec<CoverSource> {
+        self.sources
+            .iter()
+            .filter_map(|source| match source.to_lowercase().as_str() {
+                "embedded" => Some(CoverSource::Embedded),
+                "folder" => Some(CoverSource::Folder),
+                other => {
+                    log::warn!("Unknown cover art source '{}', ignoring", other);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Warnings for settings that fall back to their default
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .sources
+            .iter()
+            .filter_map(|source| {
+                unknown_value_warning("cover", "sources", source, &["embedded", "folder"])
+            })
+            .collect();
+        warnings.extend(
+            [
+                (
+                    "position",
+                    &self.position,
+                    &["center", "top", "bottom", "left", "right"][..],
+                ),
+                (
+                    "border",
+                    &self.border,
+                    &["none", "plain", "rounded", "double", "thick"],
+                ),
+                ("scaling", &self.scaling, &["fit", "fill"]),
+                (
+                    "placeholder",
+                    &self.placeholder,
+                    &["none", "default", "initials"],
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(key, value, known)| unknown_value_warning("cover", key, value, known)),
+        );
+        if !self.background.is_empty() && self.background_rgb().is_none() {
+            warnings.push(format!(
+                "Invalid value in [cover] background: {} (expected a hex color like #1e1e2e)",
+                self.background
+            ));
+        }
+        warnings
+    }
+}
+
+impl Default for CoverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            sources: Self::default_sources(),
+            max_width: Self::default_max_size(),
+            max_height: Self::default_max_size(),
+            position: Self::default_position(),
+            cache_by_album: Self::default_cache_by_album(),
+            border: Self::default_border(),
+            scaling: Self::default_scaling(),
+            background: Self::default_background(),
+            max_dimension: Self::default_max_dimension(),
+            placeholder: Self::default_placeholder(),
+            prefetch_count: Self::default_prefetch_count(),
+            max_concurrent_fetches: Self::default_max_concurrent_fetches(),
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use crate::app::config::binds::BindsConfig;
 use crate::app::config::colors::ColorsConfig;
+use crate::app::config::confirm::ConfirmConfig;
+use crate::app::config::cover::CoverConfig;
 use crate::app::config::logging::LoggingConfig;
 use crate::app::config::mpd::MpdConfig;
 use crate::app::config::pipewire::PipewireConfig;
+use crate::app::config::sleep_timer::SleepTimerConfig;
+use crate::app::config::ui::UiConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -18,6 +22,14 @@ pub struct Config {
     pub pipewire: PipewireConfig,
     #[serde(default)]
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub cover: CoverConfig,
+    #[serde(default)]
+    pub sleep_timer: SleepTimerConfig,
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
 }
 
 /// Calculate Levenshtein distance between two strings
@@ -100,6 +112,32 @@ fn format_unknown_warning(section: &str, key: &str, suggestion: Option<&str>) ->
     }
 }
 
+/// Warning for a setting that is none of its `known` values (ignoring case), None when
+/// it is one of them
+pub(super) fn unknown_value_warning(
+    section: &str,
+    key: &str,
+    value: &str,
+    known: &[&str],
+) -> Option<String> {
+    if known.iter().any(|known| known.eq_ignore_ascii_case(value)) {
+        return None;
+    }
+    Some(match find_similar(value, known) {
+        Some(s) => format!(
+            "Invalid value in [{}] {}: {} (did you mean: {}?)",
+            section, key, value, s
+        ),
+        None => format!(
+            "Invalid value in [{}] {}: {} (expected one of: {})",
+            section,
+            key,
+            value,
+            known.join(", ")
+        ),
+    })
+}
+
 impl Config {
     /// Returns the default config file path based on the platform:
     /// - Linux: ~/.config/zarumet/config.toml (XDG_CONFIG_HOME)
@@ -139,9 +177,9 @@ impl Config {
         let contents = std::fs::read_to_string(&config_path)?;
 
         // Check for unknown config options before parsing
-        let warnings = Self::check_unknown_fields(&contents);
+        let mut warnings = Self::check_unknown_fields(&contents);
 
-        let config: Config = toml::from_str(&contents).unwrap_or_else(|e| {
+        let mut config: Config = toml::from_str(&contents).unwrap_or_else(|e| {
             // This warning will be lost since logger isn't initialized yet,
             // but at least we log in debug mode
             if cfg!(debug_assertions) {
@@ -149,18 +187,53 @@ impl Config {
             }
             Config::default()
         });
+        config.ui.parse_queue_columns();
+        warnings.extend(config.value_warnings());
         Ok((config, warnings))
     }
 
+    /// Warnings for settings with a value that is ignored in favor of the default
+    fn value_warnings(&self) -> Vec<String> {
+        let mut warnings = self.mpd.warnings();
+        warnings.extend(self.cover.warnings());
+        warnings.extend(self.sleep_timer.warnings());
+        warnings.extend(self.ui.warnings());
+        warnings
+    }
+
     /// Check for unknown fields in the config file and return warnings
     fn check_unknown_fields(contents: &str) -> Vec<String> {
         let mut warnings = Vec::new();
 
         // Known top-level sections
-        const KNOWN_SECTIONS: &[&str] = &["mpd", "colors", "binds", "pipewire", "logging"];
+        const KNOWN_SECTIONS: &[&str] = &[
+            "mpd",
+            "colors",
+            "binds",
+            "pipewire",
+            "logging",
+            "cover",
+            "sleep_timer",
+            "confirm",
+            "ui",
+        ];
 
         // Known fields per section
-        const KNOWN_MPD_FIELDS: &[&str] = &["address", "volume_increment", "volume_increment_fine"];
+        const KNOWN_MPD_FIELDS: &[&str] = &[
+            "address",
+            "volume_increment",
+            "volume_increment_fine",
+            "load_strategy",
+            "binary_limit_kb",
+            "add_position",
+            "group_by",
+            "previous_restart_secs",
+            "connect_timeout_secs",
+            "restore_queue",
+            "on_queue_end",
+            "featured_artist_patterns",
+            "auto_refresh_library",
+        ];
 
         const KNOWN_COLORS_FIELDS: &[&str] = &[
             "border",
@@ -170,6 +243,8 @@ impl Config {
             "border_title",
             "progress_filled",
             "progress_empty",
+            "progress_paused",
+            "progress_stopped",
             "paused",
             "playing",
             "stopped",
@@ -188,6 +263,7 @@ impl Config {
             "volume_empty",
             "mode",
             "track_duration",
+            "cover_border",
         ];
 
         const KNOWN_BINDS_FIELDS: &[&str] = &[
@@ -211,6 +287,7 @@ impl Config {
             "switch_to_queue_menu",
             "switch_to_artists",
             "switch_to_albums",
+            "switch_to_files",
             "seek_forward",
             "seek_backward",
             "scroll_up",
@@ -228,6 +305,36 @@ impl Config {
             "go_to_top",
             "go_to_bottom",
             "toggle_bit_perfect",
+            "stop_after_current",
+            "open_sleep_timer",
+            "deduplicate_queue",
+            "crop_queue",
+            "shuffle_queue",
+            "enter_volume",
+            "add_and_play",
+            "open_output_sinks",
+            "show_track_info",
+            "search_database",
+            "add_to_queue_alternate",
+            "show_mpd_settings",
+            "show_help",
+            "page_up",
+            "page_down",
+            "toggle_grouping",
+            "toggle_now_playing",
+            "show_partitions",
+            "show_actions",
+            "go_to_artist",
+            "go_to_album",
+            "remove_album_from_queue",
+            "filter_queue",
+            "back",
+            "cycle_repeat",
+            "show_library_stats",
+            "copy_song_info",
+            "go_to_current",
+            "parent_directory",
+            "replace_queue_with_album",
         ];
 
         const KNOWN_PIPEWIRE_FIELDS: &[&str] = &["bit_perfect_enabled"];
@@ -243,6 +350,50 @@ impl Config {
             "custom_log_path",
         ];
 
+        const KNOWN_COVER_FIELDS: &[&str] = &[
+            "enabled",
+            "sources",
+            "max_width",
+            "max_height",
+            "position",
+            "cache_by_album",
+            "border",
+            "scaling",
+            "background",
+            "max_dimension",
+            "placeholder",
+            "prefetch_count",
+            "max_concurrent_fetches",
+        ];
+
+        const KNOWN_SLEEP_TIMER_FIELDS: &[&str] =
+            &["presets", "action", "quit_on_expire", "fade_out_secs"];
+
+        const KNOWN_CONFIRM_FIELDS: &[&str] = &[
+            "clear_queue",
+            "crop_queue",
+            "add_folder_threshold",
+            "replace_queue",
+        ];
+
+        const KNOWN_UI_FIELDS: &[&str] = &[
+            "start_mode",
+            "artist_separator",
+            "progress_style",
+            "scrollbars",
+            "queue_columns",
+            "time_format",
+            "time_padding",
+            "partial_redraw",
+            "title_fallback",
+            "clock",
+            "queue_album_separators",
+            "album_format_badges",
+            "copy_format",
+            "enter_adds_folder",
+            "key_hints",
+        ];
+
         // Parse as generic TOML table
         let table: Result<toml::Table, _> = toml::from_str(contents);
         let table = match table {
@@ -310,6 +461,46 @@ impl Config {
             }
         }
 
+        if let Some(toml::Value::Table(cover)) = table.get("cover") {
+            for key in cover.keys() {
+                if !KNOWN_COVER_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_COVER_FIELDS);
+                    let msg = format_unknown_warning("[cover]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(sleep_timer)) = table.get("sleep_timer") {
+            for key in sleep_timer.keys() {
+                if !KNOWN_SLEEP_TIMER_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_SLEEP_TIMER_FIELDS);
+                    let msg = format_unknown_warning("[sleep_timer]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(confirm)) = table.get("confirm") {
+            for key in confirm.keys() {
+                if !KNOWN_CONFIRM_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_CONFIRM_FIELDS);
+                    let msg = format_unknown_warning("[confirm]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
+        if let Some(toml::Value::Table(ui)) = table.get("ui") {
+            for key in ui.keys() {
+                if !KNOWN_UI_FIELDS.contains(&key.as_str()) {
+                    let suggestion = find_similar(key, KNOWN_UI_FIELDS);
+                    let msg = format_unknown_warning("[ui]", key, suggestion.as_deref());
+                    warnings.push(msg);
+                }
+            }
+        }
+
         warnings
     }
 
@@ -339,3 +530,33 @@ impl Config {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_has_no_value_warnings() {
+        assert!(Config::default().value_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_value_warnings() {
+        let mut config = Config::default();
+        config.mpd.on_queue_end = "Random_Album".to_string();
+        config.cover.scaling = "FILL".to_string();
+        assert!(config.value_warnings().is_empty());
+
+        config.mpd.on_queue_end = "repaet".to_string();
+        config.cover.scaling = "stretch".to_string();
+        config.cover.background = "#12345".to_string();
+        assert_eq!(
+            config.value_warnings(),
+            [
+                "Invalid value in [mpd] on_queue_end: repaet (did you mean: repeat?)",
+                "Invalid value in [cover] scaling: stretch (expected one of: fit, fill)",
+                "Invalid value in [cover] background: #12345 (expected a hex color like #1e1e2e)",
+            ]
+        );
+    }
+}
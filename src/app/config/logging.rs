@@ -5,7 +5,7 @@ pub struct LoggingConfig {
     /// Enable logging to file
     #[serde(default = "LoggingConfig::default_enabled")]
     pub enabled: bool,
-    /// Log level (error, warn, info, debug, trace)
+    /// Log level (error, warn, info, debug, trace), `-v`/`-q` override it
     #[serde(default = "LoggingConfig::default_level")]
     pub level: String,
     /// Enable logging to console
@@ -23,7 +23,7 @@ pub struct LoggingConfig {
     /// Number of log files to keep when rotating
     #[serde(default = "LoggingConfig::default_keep_log_files")]
     pub keep_log_files: u32,
-    /// Custom log file path (optional)
+    /// Custom log file path (optional, `--log-file` overrides it)
     #[serde(default)]
     pub custom_log_path: Option<String>,
 }
@@ -34,7 +34,7 @@ impl LoggingConfig {
     }
 
     fn default_level() -> String {
-        "info".to_string()
+        "warn".to_string()
     }
 
     fn default_log_to_console() -> bool {
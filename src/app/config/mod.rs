@@ -1,9 +1,13 @@
 pub mod binds;
 pub mod colors;
+pub mod confirm;
+pub mod cover;
 pub mod format;
 pub mod logging;
 pub mod mpd;
 pub mod pipewire;
+pub mod sleep_timer;
+pub mod ui;
 
 pub use format::Config;
 pub use logging::LoggingConfig;
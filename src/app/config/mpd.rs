@@ -1,5 +1,65 @@
+use mpd_client::tag::Tag;
 use serde::{Deserialize, Serialize};
 
+use crate::app::config::format::unknown_value_warning;
+
+/// Tag the artist list of the library is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    AlbumArtist,
+    Artist,
+}
+
+impl GroupBy {
+    /// MPD tag listed and filtered on
+    pub fn tag(self) -> Tag {
+        match self {
+            GroupBy::AlbumArtist => Tag::AlbumArtist,
+            GroupBy::Artist => Tag::Artist,
+        }
+    }
+
+    /// The other grouping, for the runtime toggle
+    pub fn toggled(self) -> Self {
+        match self {
+            GroupBy::AlbumArtist => GroupBy::Artist,
+            GroupBy::Artist => GroupBy::AlbumArtist,
+        }
+    }
+}
+
+/// Where added songs are inserted into the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddPosition {
+    /// Append to the end of the queue
+    End,
+    /// Right after the current song
+    Next,
+    /// After the remaining queued tracks of the current song's album
+    AfterCurrentAlbum,
+}
+
+impl AddPosition {
+    /// Position used by the alternate add key: the end, or next when adding to the end
+    pub fn alternate(self) -> Self {
+        match self {
+            AddPosition::End => AddPosition::Next,
+            AddPosition::Next | AddPosition::AfterCurrentAlbum => AddPosition::End,
+        }
+    }
+}
+
+/// What happens when the last track of the queue finishes with repeat off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueEnd {
+    /// Let playback stop
+    Stop,
+    /// Play the queue again from the top
+    Repeat,
+    /// Append a random album from the library and play it
+    RandomAlbum,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MpdConfig {
     #[serde(default = "MpdConfig::default_address")]
@@ -8,6 +68,41 @@ pub struct MpdConfig {
     pub volume_increment: u32,
     #[serde(default = "MpdConfig::default_volume_increment_fine")]
     pub volume_increment_fine: u32,
+    /// How the Albums view loads the whole library:
+    /// "listall" fetches every song in one bulk query (fastest on a local server),
+    /// "by_artist" issues one query per artist (smaller responses, often faster
+    /// over the network and safer for very large libraries)
+    #[serde(default = "MpdConfig::default_load_strategy")]
+    pub load_strategy: String,
+    /// MPD binary limit in KiB, i.e. the chunk size used when fetching cover art.
+    /// Covers larger than this still load in full, just over more round trips.
+    #[serde(default = "MpdConfig::default_binary_limit_kb")]
+    pub binary_limit_kb: usize,
+    /// Where added songs go: "end", "next" or "after_current_album"
+    #[serde(default = "MpdConfig::default_add_position")]
+    pub add_position: String,
+    /// Build the library from "album_artist" (default) or "artist" tags
+    #[serde(default = "MpdConfig::default_group_by")]
+    pub group_by: String,
+    /// Past this many seconds into a track, "previous" restarts it instead (0 = never)
+    #[serde(default = "MpdConfig::default_previous_restart_secs")]
+    pub previous_restart_secs: u64,
+    /// Give up connecting at startup after this many seconds (0 = wait forever)
+    #[serde(default = "MpdConfig::default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Offer to restore the last seen queue when MPD comes back with an empty one
+    #[serde(default = "MpdConfig::default_restore_queue")]
+    pub restore_queue: bool,
+    /// When the queue runs out: "stop", "repeat" or "random_album"
+    #[serde(default = "MpdConfig::default_on_queue_end")]
+    pub on_queue_end: String,
+    /// Artist name parts that start a featured-artist credit (" feat. "), cut off
+    /// for grouping the library. Empty to group every credit separately.
+    #[serde(default)]
+    pub featured_artist_patterns: Vec<String>,
+    /// Reload the library when MPD's database changes; otherwise just point it out
+    #[serde(default = "MpdConfig::default_auto_refresh_library")]
+    pub auto_refresh_library: bool,
 }
 
 impl MpdConfig {
@@ -20,6 +115,96 @@ impl MpdConfig {
     fn default_volume_increment_fine() -> u32 {
         1
     }
+    fn default_load_strategy() -> String {
+        "listall".to_string()
+    }
+    fn default_binary_limit_kb() -> usize {
+        5 * 1024
+    }
+    fn default_add_position() -> String {
+        "end".to_string()
+    }
+    fn default_group_by() -> String {
+        "album_artist".to_string()
+    }
+    fn default_previous_restart_secs() -> u64 {
+        3
+    }
+    fn default_connect_timeout_secs() -> u64 {
+        5
+    }
+    fn default_restore_queue() -> bool {
+        true
+    }
+    fn default_on_queue_end() -> String {
+        "stop".to_string()
+    }
+    fn default_auto_refresh_library() -> bool {
+        true
+    }
+
+    /// How long to try connecting at startup, None to wait as long as it takes
+    pub fn connect_timeout(&self) -> Option<std::time::Duration> {
+        (self.connect_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(self.connect_timeout_secs))
+    }
+
+    /// Check if the library should be loaded one artist at a time
+    pub fn load_by_artist(&self) -> bool {
+        self.load_strategy.eq_ignore_ascii_case("by_artist")
+    }
+
+    /// Parse the configured grouping tag, falling back to album artists
+    pub fn group_by(&self) -> GroupBy {
+        if self.group_by.eq_ignore_ascii_case("artist") {
+            GroupBy::Artist
+        } else {
+            GroupBy::AlbumArtist
+        }
+    }
+
+    /// Parse the configured end of queue behavior, falling back to stopping
+    pub fn on_queue_end(&self) -> QueueEnd {
+        match self.on_queue_end.to_lowercase().as_str() {
+            "repeat" => QueueEnd::Repeat,
+            "random_album" => QueueEnd::RandomAlbum,
+            _ => QueueEnd::Stop,
+        }
+    }
+
+    /// Warnings for settings that fall back to their default
+    pub fn warnings(&self) -> Vec<String> {
+        [
+            (
+                "load_strategy",
+                &self.load_strategy,
+                &["listall", "by_artist"][..],
+            ),
+            (
+                "add_position",
+                &self.add_position,
+                &["end", "next", "after_current_album"],
+            ),
+            ("group_by", &self.group_by, &["album_artist", "artist"]),
+            (
+                "on_queue_end",
+                &self.on_queue_end,
+                &["stop", "repeat", "random_album"],
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, value, known)| unknown_value_warning("mpd", key, value, known))
+        .collect()
+    }
+
+    /// Parse the configured add position, falling back to the end of the queue
+    pub fn add_position(&self) -> AddPosition {
+        match self.add_position.to_lowercase().as_str() {
+            "next" => AddPosition::Next,
+            "after_current_album" => AddPosition::AfterCurrentAlbum,
+            _ => AddPosition::End,
+        }
+    }
 }
 
 impl Default for MpdConfig {
@@ -28,6 +213,16 @@ impl Default for MpdConfig {
             address: Self::default_address(),
             volume_increment: Self::default_volume_increment(),
             volume_increment_fine: Self::default_volume_increment_fine(),
+            load_strategy: Self::default_load_strategy(),
+            binary_limit_kb: Self::default_binary_limit_kb(),
+            add_position: Self::default_add_position(),
+            group_by: Self::default_group_by(),
+            previous_restart_secs: Self::default_previous_restart_secs(),
+            connect_timeout_secs: Self::default_connect_timeout_secs(),
+            restore_queue: Self::default_restore_queue(),
+            on_queue_end: Self::default_on_queue_end(),
+            featured_artist_patterns: Vec::new(),
+            auto_refresh_library: Self::default_auto_refresh_library(),
         }
     }
 }
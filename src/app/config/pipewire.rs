@@ -60,6 +60,26 @@ pub fn resolve_bit_perfect_rate(song_rate: u32, supported_rates: &[u32]) -> u32
     }
 }
 
+/// Format a sample rate in kHz, e.g. 44100 -> "44.1kHz", 96000 -> "96kHz"
+fn format_khz(rate: u32) -> String {
+    if rate.is_multiple_of(1000) {
+        format!("{}kHz", rate / 1000)
+    } else {
+        format!("{:.1}kHz", rate as f64 / 1000.0)
+    }
+}
+
+/// Describe a fallback rate, or None if the song plays at its native rate
+pub fn bit_perfect_rate_warning(song_rate: u32, target_rate: u32) -> Option<String> {
+    (song_rate != target_rate).then(|| {
+        format!(
+            "{} not supported, using {}",
+            format_khz(song_rate),
+            format_khz(target_rate)
+        )
+    })
+}
+
 impl Default for PipewireConfig {
     fn default() -> Self {
         Self {
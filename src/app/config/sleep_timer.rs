@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::config::format::unknown_value_warning;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SleepTimerConfig {
+    /// Durations (in minutes) offered in the sleep timer overlay
+    #[serde(default = "SleepTimerConfig::default_presets")]
+    pub presets: Vec<u64>,
+    /// What to do when the timer runs out ("pause" or "stop")
+    #[serde(default = "SleepTimerConfig::default_action")]
+    pub action: String,
+    /// Quit zarumet after playback has been stopped
+    #[serde(default = "SleepTimerConfig::default_quit_on_expire")]
+    pub quit_on_expire: bool,
+    /// Gradually lower the volume over this many seconds before stopping (0 = no fade)
+    #[serde(default = "SleepTimerConfig::default_fade_out_secs")]
+    pub fade_out_secs: u64,
+}
+
+impl SleepTimerConfig {
+    fn default_presets() -> Vec<u64> {
+        vec![15, 30, 45, 60, 90, 120]
+    }
+
+    fn default_action() -> String {
+        "pause".to_string()
+    }
+
+    fn default_quit_on_expire() -> bool {
+        false
+    }
+
+    fn default_fade_out_secs() -> u64 {
+        0
+    }
+
+    /// Check if the timer should stop playback instead of pausing it
+    pub fn stops_playback(&self) -> bool {
+        self.action.eq_ignore_ascii_case("stop")
+    }
+
+    /// Warnings for settings that fall back to their default
+    pub fn warnings(&self) -> Vec<String> {
+        unknown_value_warning("sleep_timer", "action", &self.action, &["pause", "stop"])
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Default for SleepTimerConfig {
+    fn default() -> Self {
+        Self {
+            presets: Self::default_presets(),
+            action: Self::default_action(),
+            quit_on_expire: Self::default_quit_on_expire(),
+            fade_out_secs: Self::default_fade_out_secs(),
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::MenuMode;
+use crate::app::config::format::unknown_value_warning;
+
+/// How the progress bar is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStyle {
+    /// Solid block bar (█)
+    Block,
+    /// Braille dots, two steps per cell for smoother movement
+    Braille,
+    /// Thin line (━), the original look
+    Line,
+    /// Eighth-block steps (▏▎▍▌▋▊▉█) on a shaded track (░)
+    FractionBlock,
+}
+
+/// When durations get an hours field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HourDisplay {
+    /// Minutes keep counting past an hour ("61:01")
+    Never,
+    /// Only durations of an hour or more ("1:01:01")
+    Auto,
+    /// Every duration ("0:03:05")
+    Always,
+}
+
+/// How durations are written (see `utils::format_duration`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FmtOpts {
+    pub hours: HourDisplay,
+    /// Zero-pad the leading field to two digits ("03:05")
+    pub pad: bool,
+}
+
+/// What a queue column shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueColumnKind {
+    /// Position in the queue ("12. ")
+    Position,
+    /// Track number on the album
+    Track,
+    Title,
+    Artist,
+    Album,
+    Duration,
+}
+
+impl QueueColumnKind {
+    /// Free-text columns, separated by a border and sharing the leftover width
+    pub fn is_text(self) -> bool {
+        matches!(self, Self::Title | Self::Artist | Self::Album)
+    }
+}
+
+/// How the clock on the bottom border shows the time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockFormat {
+    /// "14:05"
+    H24,
+    /// " 2:05 PM"
+    H12,
+}
+
+/// What is shown as the title of songs without a Title tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleFallback {
+    /// A title made from the file name ("03 - Some_Song.flac" shows "Some Song")
+    Filename,
+    /// "Unknown Title"
+    Unknown,
+}
+
+/// How wide a queue column is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Natural width for numbers and durations, an equal share of the rest for text
+    Auto,
+    /// Fixed number of terminal cells
+    Cells(usize),
+    /// Percentage of the width left after separators
+    Percent(usize),
+}
+
+/// One entry of `queue_columns`, e.g. "title:40%"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueColumn {
+    pub kind: QueueColumnKind,
+    pub width: ColumnWidth,
+}
+
+impl QueueColumn {
+    /// Parse "name", "name:cells" or "name:percent%"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (name, width) = match spec.split_once(':') {
+            Some((name, width)) => (name, Some(width.trim())),
+            None => (spec, None),
+        };
+        let kind = match name.trim().to_lowercase().as_str() {
+            "position" => QueueColumnKind::Position,
+            "track" => QueueColumnKind::Track,
+            "title" => QueueColumnKind::Title,
+            "artist" => QueueColumnKind::Artist,
+            "album" => QueueColumnKind::Album,
+            "duration" => QueueColumnKind::Duration,
+            _ => return None,
+        };
+        let width = match width {
+            None => ColumnWidth::Auto,
+            Some(width) => match width.strip_suffix('%') {
+                Some(percent) => ColumnWidth::Percent(percent.parse().ok().filter(|p| *p <= 100)?),
+                None => ColumnWidth::Cells(width.parse().ok()?),
+            },
+        };
+        Some(Self { kind, width })
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UiConfig {
+    /// View shown on startup ("queue", "tracks", "albums" or "files")
+    #[serde(default = "UiConfig::default_start_mode")]
+    pub start_mode: String,
+    /// Separator between artist names on tracks with several artists
+    #[serde(default = "UiConfig::default_artist_separator")]
+    pub artist_separator: String,
+    /// Progress bar look ("block", "braille", "line", "fraction-block")
+    #[serde(default = "UiConfig::default_progress_style")]
+    pub progress_style: String,
+    /// Show scrollbars on list panels that don't fit on screen
+    #[serde(default = "UiConfig::default_scrollbars")]
+    pub scrollbars: bool,
+    /// Queue columns in display order, each "name" or "name:width" (cells or "40%")
+    #[serde(default = "UiConfig::default_queue_columns")]
+    pub queue_columns: Vec<String>,
+    /// `queue_columns` parsed once when the config is loaded
+    #[serde(skip)]
+    parsed_queue_columns: Vec<QueueColumn>,
+    /// When durations show hours ("minutes", "auto" or "hours")
+    #[serde(default = "UiConfig::default_time_format")]
+    pub time_format: String,
+    /// Zero-pad the leading field of durations ("03:05" instead of "3:05")
+    #[serde(default = "UiConfig::default_time_padding")]
+    pub time_padding: bool,
+    /// On clock ticks redraw only the progress bar instead of the whole screen
+    #[serde(default = "UiConfig::default_partial_redraw")]
+    pub partial_redraw: bool,
+    /// Title of songs without a Title tag ("filename" or "unknown")
+    #[serde(default = "UiConfig::default_title_fallback")]
+    pub title_fallback: String,
+    /// Clock on the bottom border: "off", "24h" or "12h"
+    #[serde(default = "UiConfig::default_clock")]
+    pub clock: String,
+    /// Draw a line under the last queue row of each run of tracks from one album
+    #[serde(default = "UiConfig::default_queue_album_separators")]
+    pub queue_album_separators: bool,
+    /// Show each album's format (e.g. "FLAC 16/44") next to its runtime in album lists
+    #[serde(default = "UiConfig::default_album_format_badges")]
+    pub album_format_badges: bool,
+    /// Text copied by the copy_song_info bind, with placeholders such as {artist} and {file}
+    #[serde(default = "UiConfig::default_copy_format")]
+    pub copy_format: String,
+    /// Enter on a folder in the Files view adds it instead of opening it
+    #[serde(default = "UiConfig::default_enter_adds_folder")]
+    pub enter_adds_folder: bool,
+    /// Show the most useful keys of the current view on the bottom line
+    #[serde(default = "UiConfig::default_key_hints")]
+    pub key_hints: bool,
+}
+
+impl UiConfig {
+    fn default_start_mode() -> String {
+        "queue".to_string()
+    }
+
+    fn default_artist_separator() -> String {
+        ", ".to_string()
+    }
+
+    fn default_progress_style() -> String {
+        "line".to_string()
+    }
+
+    fn default_scrollbars() -> bool {
+        true
+    }
+
+    fn default_queue_columns() -> Vec<String> {
+        ["position", "title", "artist", "album", "duration"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    fn default_time_format() -> String {
+        "auto".to_string()
+    }
+
+    fn default_time_padding() -> bool {
+        false
+    }
+
+    fn default_partial_redraw() -> bool {
+        true
+    }
+
+    fn default_title_fallback() -> String {
+        "unknown".to_string()
+    }
+
+    fn default_clock() -> String {
+        "off".to_string()
+    }
+
+    fn default_queue_album_separators() -> bool {
+        false
+    }
+
+    fn default_album_format_badges() -> bool {
+        false
+    }
+
+    fn default_copy_format() -> String {
+        "{artist} - {title}".to_string()
+    }
+
+    fn default_enter_adds_folder() -> bool {
+        false
+    }
+
+    fn default_key_hints() -> bool {
+        true
+    }
+
+    /// Parse the configured duration format, falling back to hours when needed
+    pub fn time_format(&self) -> FmtOpts {
+        let hours = match self.time_format.to_lowercase().as_str() {
+            "minutes" => HourDisplay::Never,
+            "hours" => HourDisplay::Always,
+            _ => HourDisplay::Auto,
+        };
+        FmtOpts {
+            hours,
+            pad: self.time_padding,
+        }
+    }
+
+    /// The configured queue columns, as parsed by [`UiConfig::parse_queue_columns`]
+    pub fn queue_columns(&self) -> &[QueueColumn] {
+        &self.parsed_queue_columns
+    }
+
+    /// Parse `queue_columns` after it was deserialized
+    pub(super) fn parse_queue_columns(&mut self) {
+        self.parsed_queue_columns = self.resolve_queue_columns();
+    }
+
+    /// Parse the configured queue columns, skipping entries that don't parse.
+    /// Falls back to the default columns when nothing usable is left.
+    fn resolve_queue_columns(&self) -> Vec<QueueColumn> {
+        let columns: Vec<QueueColumn> = self
+            .queue_columns
+            .iter()
+            .filter_map(|spec| QueueColumn::parse(spec))
+            .collect();
+
+        if columns.is_empty() {
+            Self::default_queue_columns()
+                .iter()
+                .filter_map(|spec| QueueColumn::parse(spec))
+                .collect()
+        } else {
+            columns
+        }
+    }
+
+    /// Parse the configured progress bar style, falling back to the line
+    pub fn progress_style(&self) -> ProgressStyle {
+        match self.progress_style.to_lowercase().as_str() {
+            "block" => ProgressStyle::Block,
+            "braille" => ProgressStyle::Braille,
+            "fraction-block" => ProgressStyle::FractionBlock,
+            _ => ProgressStyle::Line,
+        }
+    }
+
+    /// Parse the configured title fallback, falling back to "Unknown Title"
+    pub fn title_fallback(&self) -> TitleFallback {
+        if self.title_fallback.eq_ignore_ascii_case("filename") {
+            TitleFallback::Filename
+        } else {
+            TitleFallback::Unknown
+        }
+    }
+
+    /// Parse the configured clock, None when it is off (also the fallback)
+    pub fn clock_format(&self) -> Option<ClockFormat> {
+        match self.clock.to_lowercase().as_str() {
+            "24h" => Some(ClockFormat::H24),
+            "12h" => Some(ClockFormat::H12),
+            _ => None,
+        }
+    }
+
+    /// Warnings for `queue_columns` entries that are skipped
+    pub fn queue_column_warnings(&self) -> Vec<String> {
+        self.queue_columns
+            .iter()
+            .filter(|spec| QueueColumn::parse(spec).is_none())
+            .map(|spec| format!("Invalid value in [ui] queue_columns: {}", spec))
+            .collect()
+    }
+
+    /// Warnings for settings that fall back to their default
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = self.queue_column_warnings();
+        warnings.extend(
+            [
+                (
+                    "start_mode",
+                    &self.start_mode,
+                    &["queue", "tracks", "artists", "albums", "files"][..],
+                ),
+                (
+                    "progress_style",
+                    &self.progress_style,
+                    &["block", "braille", "line", "fraction-block"],
+                ),
+                (
+                    "time_format",
+                    &self.time_format,
+                    &["minutes", "auto", "hours"],
+                ),
+                (
+                    "title_fallback",
+                    &self.title_fallback,
+                    &["filename", "unknown"],
+                ),
+                ("clock", &self.clock, &["off", "24h", "12h"]),
+            ]
+            .into_iter()
+            .filter_map(|(key, value, known)| unknown_value_warning("ui", key, value, known)),
+        );
+        warnings
+    }
+
+    /// Parse the configured startup view, falling back to the queue
+    pub fn start_menu_mode(&self) -> MenuMode {
+        match self.start_mode.to_lowercase().as_str() {
+            "queue" => MenuMode::Queue,
+            // The artist/album/track tree is the Artists view
+            "tracks" | "artists" => MenuMode::Artists,
+            "albums" => MenuMode::Albums,
+            "files" => MenuMode::Files,
+            other => {
+                log::warn!("Unknown start mode '{}', starting in the queue", other);
+                MenuMode::Queue
+            }
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        let mut config = Self {
+            start_mode: Self::default_start_mode(),
+            artist_separator: Self::default_artist_separator(),
+            progress_style: Self::default_progress_style(),
+            scrollbars: Self::default_scrollbars(),
+            queue_columns: Self::default_queue_columns(),
+            parsed_queue_columns: Vec::new(),
+            time_format: Self::default_time_format(),
+            time_padding: Self::default_time_padding(),
+            partial_redraw: Self::default_partial_redraw(),
+            title_fallback: Self::default_title_fallback(),
+            clock: Self::default_clock(),
+            queue_album_separators: Self::default_queue_album_separators(),
+            album_format_badges: Self::default_album_format_badges(),
+            copy_format: Self::default_copy_format(),
+            enter_adds_folder: Self::default_enter_adds_folder(),
+            key_hints: Self::default_key_hints(),
+        };
+        config.parse_queue_columns();
+        config
+    }
+}
@@ -3,10 +3,14 @@ use crate::app::Config;
 use crate::app::DirtyFlags;
 use crate::app::KeyBinds;
 use crate::app::cli::Args;
+use crate::app::main_loop::QueueSnapshot;
+use crate::app::song::TagSettings;
+use crate::app::ui::QueuedSet;
 use crate::app::{MenuMode, PanelFocus};
 use ratatui::widgets::ListState;
 use std::cell::Cell;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Trait for App construction
 #[allow(dead_code)]
@@ -67,6 +71,50 @@ pub fn save_bit_perfect_state(enabled: bool) -> std::io::Result<()> {
     std::fs::write(&state_path, contents)
 }
 
+/// Get the path of the queue snapshot, next to the state file
+fn get_queue_snapshot_path() -> Option<PathBuf> {
+    get_state_path().map(|path| path.with_file_name("queue.txt"))
+}
+
+/// Load the file paths of the last saved queue, one per line
+pub fn load_queue_snapshot() -> Vec<PathBuf> {
+    get_queue_snapshot_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Save the queue's file paths, removing the snapshot when the queue is empty
+pub fn save_queue_snapshot(files: &[PathBuf]) -> std::io::Result<()> {
+    let Some(snapshot_path) = get_queue_snapshot_path() else {
+        return Ok(());
+    };
+
+    if files.is_empty() {
+        return match std::fs::remove_file(&snapshot_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+
+    if let Some(parent) = snapshot_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut contents = String::new();
+    for file in files {
+        contents.push_str(&file.to_string_lossy());
+        contents.push('\n');
+    }
+    std::fs::write(&snapshot_path, contents)
+}
+
 impl AppConstructor for App {
     /// Construct a new instance of [`App`].
     fn new(args: Args) -> color_eyre::Result<Self> {
@@ -88,14 +136,22 @@ impl App {
         let queue_list_state = ListState::default();
         // Don't select anything initially - will be set when queue is populated
 
+        // Start in the configured view, with the panel focus that view expects
+        let menu_mode = config.ui.start_menu_mode();
+        let panel_focus = match menu_mode {
+            MenuMode::Albums => PanelFocus::AlbumList,
+            _ => PanelFocus::Artists,
+        };
+
         // Build enhanced key maps from config
-        let (global_map, queue_map, artists_map, albums_map, sequential_bindings) =
+        let (global_map, queue_map, artists_map, albums_map, files_map, sequential_bindings) =
             config.binds.build_enhanced_key_maps();
         let key_binds = KeyBinds::new_with_sequential(
             global_map,
             queue_map,
             artists_map,
             albums_map,
+            files_map,
             sequential_bindings,
         );
 
@@ -109,16 +165,19 @@ impl App {
             running: false,
             current_song: None,
             queue: Vec::new(),
+            queued: QueuedSet::default(),
             selected_queue_index: None, // Will be set when queue is populated
+            queue_ids: Vec::new(),
             queue_list_state,
             artist_list_state: ListState::default(),
             album_list_state: ListState::default(),
             album_display_list_state: ListState::default(),
             all_albums_list_state: ListState::default(),
             album_tracks_list_state: ListState::default(),
+            tag_settings: Arc::new(TagSettings::new(&config)),
             config,
-            menu_mode: MenuMode::Queue,       // Start with queue menu
-            panel_focus: PanelFocus::Artists, // Start with artists panel focused
+            menu_mode,
+            panel_focus,
             artists_panel_focus: PanelFocus::Artists, // Default for Artists mode
             albums_panel_focus: PanelFocus::AlbumList, // Default for Albums mode
             library: None,
@@ -135,11 +194,27 @@ impl App {
             last_song_id: None,
             dirty: DirtyFlags::new(),
             library_reload_pending: false,
+            library_stale: false,
             pending_artist_index: None,
             status_message: None,
             update_in_progress: false,
             last_animation_frame: Cell::new(0),
             user_initiated_reload: false,
+            sleep_timer: None,
+            queue_snapshot: QueueSnapshot::default(),
+            playing_last_track: false,
+            sink_moved: false,
+            pending_album_replacement: None,
+            overlays: Vec::new(),
+            muted_volume: None,
+            stop_after_current: None,
+            queue_filter: None,
+            file_browser: Default::default(),
+            now_playing_view: false,
+            toast: None,
+            album_preload: None,
+            album_counts: None,
+            resize_pending_since: None,
         })
     }
 }
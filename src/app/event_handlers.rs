@@ -5,25 +5,43 @@ use super::App;
 use crate::app::constructor::save_bit_perfect_state;
 use crate::app::mpd_handler::MPDAction;
 use crate::app::navigation::Navigation;
+use crate::app::{MenuMode, MessageType, StatusMessage};
 use crate::logging::log_user_interaction;
 
 /// Trait for event handling
 pub trait EventHandlers {
-    async fn handle_crossterm_events(&mut self, client: &Client) -> color_eyre::Result<()>;
+    async fn handle_crossterm_event(
+        &mut self,
+        event: Event,
+        client: &Client,
+    ) -> color_eyre::Result<()>;
     async fn on_key_event(&mut self, key: KeyEvent, client: &Client) -> color_eyre::Result<()>;
+    async fn on_key_action(
+        &mut self,
+        action: MPDAction,
+        count: Option<usize>,
+        client: &Client,
+    ) -> color_eyre::Result<()>;
     fn quit(&mut self);
 }
 
 impl EventHandlers for App {
-    /// Reads the crossterm events and updates the state of [`App`].
-    async fn handle_crossterm_events(&mut self, client: &Client) -> color_eyre::Result<()> {
-        // Try direct event reading to bypass any terminal interference
-        match crossterm::event::read()? {
+    /// Handles a terminal event and updates the state of [`App`].
+    async fn handle_crossterm_event(
+        &mut self,
+        event: Event,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        match event {
             Event::Key(key) if key.kind == KeyEventKind::Press => {
                 self.on_key_event(key, client).await?;
             }
             Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
+            Event::Resize(_, _) => {
+                // Applied by the main loop once the size settles, so dragging the
+                // window border does not redraw on every intermediate size
+                self.resize_pending_since = Some(std::time::Instant::now());
+            }
             _ => {}
         }
         Ok(())
@@ -37,117 +55,184 @@ impl EventHandlers for App {
             return Ok(());
         }
 
+        // The topmost overlay on the stack traps all keys until it is closed
+        if self.handle_overlay_key(key, client).await {
+            return Ok(());
+        }
+
+        // Typing a queue filter traps all keys until it is confirmed or dropped
+        if self.menu_mode == MenuMode::Queue
+            && self
+                .queue_filter
+                .as_ref()
+                .is_some_and(|filter| filter.editing)
+        {
+            self.handle_queue_filter_key(key);
+            return Ok(());
+        }
+
         // Track whether we were awaiting input before handling the key
         let was_awaiting = self.key_binds.is_awaiting_input();
 
-        if let Some(action) = self
+        let actions = self
             .key_binds
-            .handle_key(key, &self.menu_mode, &self.panel_focus)
-        {
-            // Log user interaction with menu context
-            let context = format!("menu:{:?}, panel:{:?}", self.menu_mode, self.panel_focus);
-            log_user_interaction(&action.to_string(), Some(&context));
+            .handle_counted_key(key, &self.menu_mode, &self.panel_focus);
+        for (action, count) in actions {
+            self.on_key_action(action, count, client).await?;
+        }
 
-            // Check if this action modifies MPD state (requires immediate status refresh)
-            let needs_update = matches!(
-                action,
-                MPDAction::TogglePlayPause
-                    | MPDAction::Next
-                    | MPDAction::Previous
-                    | MPDAction::Random
-                    | MPDAction::Repeat
-                    | MPDAction::Single
-                    | MPDAction::Consume
-                    | MPDAction::VolumeUp
-                    | MPDAction::VolumeUpFine
-                    | MPDAction::VolumeDown
-                    | MPDAction::VolumeDownFine
-                    | MPDAction::ToggleMute
-                    | MPDAction::SeekForward
-                    | MPDAction::SeekBackward
-                    | MPDAction::ClearQueue
-                    | MPDAction::RemoveFromQueue
-                    | MPDAction::MoveUpInQueue
-                    | MPDAction::MoveDownInQueue
-                    | MPDAction::PlaySelected
-                    | MPDAction::AddSongToQueue
-                    | MPDAction::ToggleAlbumExpansion
-            );
+        // Mark key sequence dirty if awaiting state changed (either started or ended a sequence)
+        if was_awaiting || self.key_binds.is_awaiting_input() {
+            self.dirty.mark_key_sequence();
+        }
+        Ok(())
+    }
 
-            match action {
-                MPDAction::Quit => self.quit(),
-                MPDAction::ToggleBitPerfect => {
-                    // Only allow toggling if bit-perfect is available (enabled in config)
-                    if self.config.pipewire.is_available() {
-                        self.bit_perfect_enabled = !self.bit_perfect_enabled;
-                        self.dirty.mark_status();
-                        // Reset PipeWire state tracking so handle_pipewire_state_change
-                        // will properly detect state changes after toggle
-                        self.last_play_state = None;
-                        self.last_sample_rate = None;
+    /// Runs the action a key (with its count prefix) resolved to.
+    async fn on_key_action(
+        &mut self,
+        action: MPDAction,
+        count: Option<usize>,
+        client: &Client,
+    ) -> color_eyre::Result<()> {
+        // Log user interaction with menu context
+        let context = format!("menu:{:?}, panel:{:?}", self.menu_mode, self.panel_focus);
+        log_user_interaction(&action.to_string(), Some(&context));
 
-                        #[cfg(target_os = "linux")]
-                        if self.bit_perfect_enabled {
-                            // Enabling - set sample rate if currently playing
-                            if let Some(ref status) = self.mpd_status
-                                && status.state == mpd_client::responses::PlayState::Playing
-                                && let Some(ref song) = self.current_song
-                                && let Some(song_rate) = song.sample_rate()
-                                && let Some(supported_rates) =
-                                    crate::app::audio::pipewire::get_supported_rates()
-                            {
-                                let target_rate =
-                                    crate::app::config::pipewire::resolve_bit_perfect_rate(
-                                        song_rate,
-                                        &supported_rates,
-                                    );
-                                // Fire-and-forget async call
-                                tokio::spawn(async move {
-                                    let _ = crate::app::audio::pipewire::set_sample_rate_async(
-                                        target_rate,
-                                    )
-                                    .await;
-                                });
-                            }
+        // Check if this action modifies MPD state (requires immediate status refresh)
+        let needs_update = matches!(
+            action,
+            MPDAction::TogglePlayPause
+                | MPDAction::Next
+                | MPDAction::Previous
+                | MPDAction::Random
+                | MPDAction::Repeat
+                | MPDAction::Single
+                | MPDAction::Consume
+                | MPDAction::VolumeUp
+                | MPDAction::VolumeUpFine
+                | MPDAction::VolumeDown
+                | MPDAction::VolumeDownFine
+                | MPDAction::ToggleMute
+                | MPDAction::SeekForward
+                | MPDAction::SeekBackward
+                | MPDAction::ClearQueue
+                | MPDAction::RemoveFromQueue
+                | MPDAction::MoveUpInQueue
+                | MPDAction::MoveDownInQueue
+                | MPDAction::PlaySelected
+                | MPDAction::AddSongToQueue
+                | MPDAction::ToggleAlbumExpansion
+                | MPDAction::StopAfterCurrent
+                | MPDAction::DeduplicateQueue
+                | MPDAction::CropQueue
+                | MPDAction::ShuffleQueue
+                | MPDAction::AddAndPlay
+                | MPDAction::ReplaceQueueWithAlbum
+                | MPDAction::CycleRepeat
+        );
+
+        match action {
+            MPDAction::Quit => self.quit(),
+            MPDAction::Back => {
+                if !self.go_back(count.is_some(), client).await {
+                    self.quit();
+                }
+            }
+            MPDAction::ToggleBitPerfect => {
+                // Only allow toggling if bit-perfect is available (enabled in config)
+                if !self.config.pipewire.is_available() {
+                    self.set_status_message(StatusMessage {
+                        text: "Bit-perfect mode unavailable".to_string(),
+                        created_at: std::time::Instant::now(),
+                        message_type: MessageType::Error,
+                    });
+                } else {
+                    self.bit_perfect_enabled = !self.bit_perfect_enabled;
+                    self.dirty.mark_status();
+                    // Persist right away so the choice survives a crash
+                    let _ = save_bit_perfect_state(self.bit_perfect_enabled);
+                    self.set_status_message(StatusMessage {
+                        text: if self.bit_perfect_enabled {
+                            "Bit-perfect on".to_string()
                         } else {
-                            // Disabling - reset PipeWire sample rate to automatic
-                            // We need to wait for reset before pause/unpause to force renegotiation
-                            let is_playing = self.mpd_status.as_ref().is_some_and(|s| {
-                                s.state == mpd_client::responses::PlayState::Playing
+                            "Bit-perfect off".to_string()
+                        },
+                        created_at: std::time::Instant::now(),
+                        message_type: MessageType::Success,
+                    });
+                    // Reset PipeWire state tracking so handle_pipewire_state_change
+                    // will properly detect state changes after toggle
+                    self.last_play_state = None;
+                    self.last_sample_rate = None;
+
+                    #[cfg(target_os = "linux")]
+                    if self.bit_perfect_enabled {
+                        // Enabling - set sample rate if currently playing
+                        if let Some(ref status) = self.mpd_status
+                            && status.state == mpd_client::responses::PlayState::Playing
+                            && let Some(ref song) = self.current_song
+                            && let Some(song_rate) = song.sample_rate()
+                            && let Some(supported_rates) =
+                                crate::app::audio::pipewire::get_supported_rates()
+                        {
+                            let target_rate =
+                                crate::app::config::pipewire::resolve_bit_perfect_rate(
+                                    song_rate,
+                                    &supported_rates,
+                                );
+                            // Fire-and-forget async call
+                            crate::app::main_loop::BACKGROUND.spawn_to_completion(async move {
+                                let _ =
+                                    crate::app::audio::pipewire::set_sample_rate_async(target_rate)
+                                        .await;
                             });
-                            if crate::app::audio::pipewire::reset_sample_rate_async()
-                                .await
-                                .is_ok()
-                                && is_playing
-                            {
-                                // Do a quick pause/unpause to force PipeWire to renegotiate
-                                let _ = client.command(mpd_client::commands::SetPause(true)).await;
-                                let _ = client.command(mpd_client::commands::Play::current()).await;
-                            }
+                        }
+                    } else {
+                        // Disabling - reset PipeWire sample rate to automatic
+                        // We need to wait for reset before pause/unpause to force renegotiation
+                        let is_playing = self
+                            .mpd_status
+                            .as_ref()
+                            .is_some_and(|s| s.state == mpd_client::responses::PlayState::Playing);
+                        if crate::app::audio::pipewire::reset_sample_rate_async()
+                            .await
+                            .is_ok()
+                            && is_playing
+                        {
+                            // Do a quick pause/unpause to force PipeWire to renegotiate
+                            let _ = client.command(mpd_client::commands::SetPause(true)).await;
+                            let _ = client.command(mpd_client::commands::Play::current()).await;
                         }
                     }
                 }
-                MPDAction::Next | MPDAction::Previous => {
-                    // Only allow Next/Previous if queue is not empty
-                    if !self.queue.is_empty() {
-                        self.handle_navigation_action(action, client).await?;
-                    }
-                }
-                _ => {
-                    // Handle other actions through navigation trait
+            }
+            MPDAction::Next | MPDAction::Previous => {
+                // Only allow Next/Previous if queue is not empty
+                if !self.queue.is_empty() {
                     self.handle_navigation_action(action, client).await?;
                 }
             }
-
-            // Force immediate MPD status update for actions that modify state
-            if needs_update {
-                self.force_update = true;
+            _ if action.accepts_count() => {
+                // A count prefix ("5j") repeats the movement
+                for _ in 0..count.unwrap_or(1) {
+                    self.handle_navigation_action(action.clone(), client)
+                        .await?;
+                }
+            }
+            MPDAction::GoToTop | MPDAction::GoToBottom => {
+                // With a count ("5G") this jumps to that row instead
+                self.handle_go_to_edge(action, count, client).await;
+            }
+            _ => {
+                // Handle other actions through navigation trait
+                self.handle_navigation_action(action, client).await?;
             }
         }
 
-        // Mark key sequence dirty if awaiting state changed (either started or ended a sequence)
-        if was_awaiting || self.key_binds.is_awaiting_input() {
-            self.dirty.mark_key_sequence();
+        // Force immediate MPD status update for actions that modify state
+        if needs_update {
+            self.force_update = true;
         }
         Ok(())
     }
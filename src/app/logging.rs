@@ -20,12 +20,12 @@ pub fn init_logger(config: &LoggingConfig) -> Result<(), FlexiLoggerError> {
 
     let mut logger = Logger::try_with_str(config.level.to_lowercase())?;
 
+    let file_spec = match &config.custom_log_path {
+        Some(path) => FileSpec::try_from(path)?,
+        None => FileSpec::default().directory(get_log_directory()),
+    };
     logger = logger
-        .log_to_file(
-            FileSpec::default()
-                .directory(get_log_directory())
-                .suppress_timestamp(),
-        )
+        .log_to_file(file_spec.suppress_timestamp())
         .format_for_files(custom_log_format)
         .use_utc();
 
@@ -49,7 +49,7 @@ pub fn init_logger(config: &LoggingConfig) -> Result<(), FlexiLoggerError> {
 
     logger.start()?;
     log::info!("Logger initialized with level: {:?}", log_level);
-    log::info!("Log file location: {}", get_log_file_path().display());
+    log::info!("Log file location: {}", get_log_file_path(config).display());
 
     Ok(())
 }
@@ -82,9 +82,12 @@ pub fn get_log_directory() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("./logs"));
 }
 
-/// Get the full path to the main log file
-pub fn get_log_file_path() -> PathBuf {
-    get_log_directory().join("zarumet.log")
+/// Get the full path to the main log file, the configured one if set
+pub fn get_log_file_path(config: &LoggingConfig) -> PathBuf {
+    match &config.custom_log_path {
+        Some(path) => PathBuf::from(path),
+        None => get_log_directory().join("zarumet.log"),
+    }
 }
 
 /// Custom log format for file output
@@ -104,23 +107,26 @@ fn custom_log_format(
     )
 }
 
-/// Ensure log directory exists
-pub fn ensure_log_directory() -> color_eyre::Result<()> {
-    let log_dir = get_log_directory();
+/// Ensure the directory the log file goes in exists
+pub fn ensure_log_directory(config: &LoggingConfig) -> color_eyre::Result<()> {
+    let log_file = get_log_file_path(config);
+    let Some(log_dir) = log_file.parent().filter(|dir| !dir.as_os_str().is_empty()) else {
+        return Ok(());
+    };
     if !log_dir.exists() {
-        std::fs::create_dir_all(&log_dir)?;
+        std::fs::create_dir_all(log_dir)?;
         log::info!("Created log directory: {}", log_dir.display());
     }
     Ok(())
 }
 
 /// Log application startup information
-pub fn log_startup_info() {
+pub fn log_startup_info(config: &LoggingConfig) {
     log::info!("=== Zarumet Starting ===");
     log::info!("Version: {}", env!("CARGO_PKG_VERSION"));
     log::info!("OS: {}", std::env::consts::OS);
     log::info!("Architecture: {}", std::env::consts::ARCH);
-    log::info!("Log file: {}", get_log_file_path().display());
+    log::info!("Log file: {}", get_log_file_path(config).display());
 }
 
 /// Log application shutdown information
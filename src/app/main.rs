@@ -1,6 +1,17 @@
+use mpd_client::client::CommandError;
+use std::sync::Arc;
+
+use crate::app::main_loop::{AlbumCountLoader, QueueSnapshot};
+use crate::app::navigation::file_browser::FileBrowser;
+use crate::app::navigation::overlay::Overlay;
+use crate::app::navigation::queue_filter::QueueFilter;
 use crate::app::{
     Cell, Config, DirtyFlags, KeyBinds, LazyLibrary, ListState, MenuMode, PanelFocus, PlayState,
     SongInfo,
+    main_loop::SleepTimer,
+    mpd_handler::command_error_message,
+    song::{Album, TagSettings},
+    ui::QueuedSet,
 };
 
 #[derive(Debug, Clone)]
@@ -10,6 +21,16 @@ pub struct StatusMessage {
     pub message_type: MessageType,
 }
 
+/// Short-lived notification about a failed action, shown in the bottom left
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub created_at: std::time::Instant,
+}
+
+/// How long a toast stays on screen
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(4);
+
 #[derive(Debug, Clone)]
 pub enum MessageType {
     InProgress,
@@ -26,8 +47,12 @@ pub struct App {
     pub current_song: Option<SongInfo>,
     /// MPD queue information
     pub queue: Vec<SongInfo>,
+    /// Files and album artists in the queue, rebuilt on every queue update
+    pub queued: QueuedSet,
     /// Currently selected queue item index
     pub selected_queue_index: Option<usize>,
+    /// MPD song ids of the queue entries, in queue order
+    pub queue_ids: Vec<mpd_client::commands::SongId>,
     /// List state for the queue widget
     pub queue_list_state: ListState,
     /// List states for Artists navigation
@@ -39,6 +64,8 @@ pub struct App {
     pub album_tracks_list_state: ListState,  // For navigating tracks within an album in Albums mode
     /// Configuration loaded from TOML file
     pub config: Config,
+    /// How song tags are read and artists grouped, from the config
+    pub tag_settings: Arc<TagSettings>,
     /// Current menu mode
     pub menu_mode: MenuMode,
     /// Current panel focus in Artists mode
@@ -75,6 +102,8 @@ pub struct App {
     pub dirty: DirtyFlags,
     /// Flag to indicate library reload is needed
     pub library_reload_pending: bool,
+    /// MPD's database changed since the library was loaded and it wasn't reloaded
+    pub library_stale: bool,
     /// Previous artist index to restore after library reload
     pub pending_artist_index: Option<String>,
     /// Library reload status message
@@ -85,6 +114,38 @@ pub struct App {
     pub last_animation_frame: Cell<u64>,
     /// Track if current reload was user-initiated (for status messages)
     pub user_initiated_reload: bool,
+    /// Running sleep timer, if any
+    pub sleep_timer: Option<SleepTimer>,
+    /// Copy of the queue kept on disk in case MPD loses it
+    pub queue_snapshot: QueueSnapshot,
+    /// Whether the last track of the queue was playing at the last status update,
+    /// to tell the queue running out apart from a user stopping playback
+    pub playing_last_track: bool,
+    /// Whether MPD's stream was moved away from the default sink (undone on quit)
+    pub sink_moved: bool,
+    /// Album to replace the queue with once the replacement is confirmed
+    pub pending_album_replacement: Option<Arc<Album>>,
+    /// Open modal overlays, the last one on top (empty = none open)
+    pub overlays: Vec<Box<dyn Overlay>>,
+    /// Volume before muting, restored on unmute (None = not muted by us)
+    pub muted_volume: Option<u8>,
+    /// Repeat and single options stop-after-current replaced, restored once it is off
+    /// again (None = not armed by us)
+    pub stop_after_current: Option<(bool, mpd_client::commands::SingleMode)>,
+    /// Query narrowing the queue view (None = whole queue shown)
+    pub queue_filter: Option<QueueFilter>,
+    /// Directory listed in the Files view
+    pub file_browser: FileBrowser,
+    /// Whether the fullscreen now playing view replaces the current view
+    pub now_playing_view: bool,
+    /// Last failed action, shown until it expires (None = nothing to show)
+    pub toast: Option<Toast>,
+    /// Background task filling a copy of the library with every album (None = not running)
+    pub album_preload: Option<tokio::task::JoinHandle<color_eyre::Result<LazyLibrary>>>,
+    /// Background task counting the albums of artists not loaded yet (None = not running)
+    pub album_counts: Option<AlbumCountLoader>,
+    /// Time of the last terminal resize event that has not been applied yet
+    pub resize_pending_since: Option<std::time::Instant>,
 }
 
 impl App {
@@ -107,6 +168,32 @@ impl App {
         }
     }
 
+    /// Log a failed MPD command and show it to the user as a toast
+    pub fn report_mpd_error(&mut self, context: &str, error: &CommandError) {
+        log::error!("{}: {}", context, error);
+        self.show_toast(format!("{}: {}", context, command_error_message(error)));
+    }
+
+    /// Show `text` as a toast, replacing any toast still on screen
+    pub fn show_toast(&mut self, text: String) {
+        self.toast = Some(Toast {
+            text,
+            created_at: std::time::Instant::now(),
+        });
+        self.dirty.mark_status_message();
+    }
+
+    pub fn check_toast_expiry(&mut self) {
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| toast.created_at.elapsed() >= TOAST_DURATION)
+        {
+            self.toast = None;
+            self.dirty.mark_status_message();
+        }
+    }
+
     pub fn check_animation_updates(&mut self) {
         if let Some(msg) = &self.status_message
             && matches!(msg.message_type, MessageType::InProgress)
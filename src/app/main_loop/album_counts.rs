@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use mpd_client::filter::{Filter, Operator};
+use mpd_client::tag::Tag;
+use mpd_client::{Client, commands};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::App;
+use crate::app::config::mpd::GroupBy;
+
+/// How often a paused count task checks whether it may continue
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Album count of the artist at an index of the library the task was started for
+type AlbumCount = (usize, String, usize);
+
+/// Index, name and tag values of an artist whose albums are to be counted
+type ArtistToCount = (usize, String, Vec<String>);
+
+/// Background task counting the albums of the artists that aren't loaded yet
+#[derive(Debug)]
+pub struct AlbumCountLoader {
+    handle: JoinHandle<()>,
+    counts: mpsc::UnboundedReceiver<AlbumCount>,
+    /// Set while a full album preload runs, which fills in every count anyway
+    paused: Arc<AtomicBool>,
+}
+
+impl App {
+    /// Start counting the albums of every artist whose albums aren't loaded, replacing
+    /// a count task for a previous library
+    pub fn start_album_counts(&mut self, client: &Client) {
+        self.cancel_album_counts();
+        let Some(ref library) = self.library else {
+            return;
+        };
+
+        let artists: Vec<ArtistToCount> = library
+            .artists
+            .iter()
+            .enumerate()
+            .filter(|(_, artist)| artist.album_count().is_none())
+            .map(|(index, artist)| (index, artist.name.clone(), artist.tag_values.clone()))
+            .collect();
+        if artists.is_empty() {
+            return;
+        }
+
+        log::debug!("Counting albums of {} artists", artists.len());
+        let (tx, counts) = mpsc::unbounded_channel();
+        let paused = Arc::new(AtomicBool::new(false));
+        let handle = tokio::spawn(count_albums(
+            client.clone(),
+            library.group_by,
+            artists,
+            tx,
+            paused.clone(),
+        ));
+        self.album_counts = Some(AlbumCountLoader {
+            handle,
+            counts,
+            paused,
+        });
+    }
+
+    /// Apply the album counts that arrived since the last call
+    pub fn check_album_counts(&mut self) {
+        let Some(ref mut loader) = self.album_counts else {
+            return;
+        };
+        loader
+            .paused
+            .store(self.album_preload.is_some(), Ordering::Relaxed);
+
+        let mut changed = false;
+        while let Ok((index, name, count)) = loader.counts.try_recv() {
+            if let Some(ref mut library) = self.library
+                && let Some(artist) = library.artists.get_mut(index)
+                && artist.name == name
+            {
+                artist.known_album_count = Some(count);
+                changed = true;
+            }
+        }
+        if changed {
+            self.dirty.mark_library();
+        }
+
+        if loader.handle.is_finished() && loader.counts.is_empty() {
+            self.album_counts = None;
+        }
+    }
+
+    /// Abort a running album count task
+    pub fn cancel_album_counts(&mut self) {
+        if let Some(loader) = self.album_counts.take() {
+            loader.handle.abort();
+        }
+    }
+}
+
+/// Count the albums of `artists` one tag query at a time, so a load the user is
+/// waiting for never queues behind more than one of them
+async fn count_albums(
+    client: Client,
+    group_by: GroupBy,
+    artists: Vec<ArtistToCount>,
+    tx: mpsc::UnboundedSender<AlbumCount>,
+    paused: Arc<AtomicBool>,
+) {
+    'artists: for (index, name, tag_values) in artists {
+        // Albums shared by several credits of the artist count once
+        let mut albums = HashSet::new();
+        for value in tag_values {
+            while paused.load(Ordering::Relaxed) {
+                tokio::time::sleep(PAUSE_POLL_INTERVAL).await;
+            }
+
+            // MPD command: list Album "(AlbumArtist == 'name')" (Artist when grouping by artist)
+            let filter = Filter::new(group_by.tag(), Operator::Equal, value);
+            match client
+                .command(commands::List::new(Tag::Album).filter(filter))
+                .await
+            {
+                Ok(list) => albums.extend(list),
+                Err(e) => {
+                    log::warn!("Failed to count albums of '{}': {}", name, e);
+                    continue 'artists;
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+        if tx.send((index, name, albums.len())).is_err() {
+            // The app stopped listening
+            return;
+        }
+    }
+}
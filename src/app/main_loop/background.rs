@@ -0,0 +1,103 @@
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Fire-and-forget tasks of the whole app, wound down together on exit
+pub static BACKGROUND: LazyLock<BackgroundTasks> = LazyLock::new(BackgroundTasks::new);
+
+/// Tracks spawned background tasks so the app can wait for them (or stop them)
+/// before the runtime goes away and drops them wherever they happen to be
+#[derive(Debug)]
+pub struct BackgroundTasks {
+    /// Set to true once shutdown starts, cancelling the cancellable tasks
+    cancel: watch::Sender<bool>,
+    handles: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl BackgroundTasks {
+    fn new() -> Self {
+        Self {
+            cancel: watch::channel(false).0,
+            handles: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Spawn a task that is cancelled at its next await point when shutdown starts,
+    /// for work that is only useful while the app runs (e.g. loading cover art)
+    pub fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let mut cancel = self.cancel.subscribe();
+        self.track(tokio::spawn(async move {
+            tokio::select! {
+                _ = cancel.wait_for(|&cancelled| cancelled) => {}
+                _ = task => {}
+            }
+        }));
+    }
+
+    /// Spawn a task that shutdown waits for, for work that must not be cut off
+    /// halfway (e.g. changing the PipeWire sample rate)
+    pub fn spawn_to_completion(&self, task: impl Future<Output = ()> + Send + 'static) {
+        self.track(tokio::spawn(task));
+    }
+
+    fn track(&self, handle: JoinHandle<()>) {
+        let mut handles = self.handles.lock().unwrap_or_else(|e| e.into_inner());
+        handles.retain(|handle| !handle.is_finished());
+        handles.push(handle);
+    }
+
+    /// Cancel the cancellable tasks and wait up to `timeout` for every task to end.
+    /// Whatever is still running after that is aborted.
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.cancel.send_replace(true);
+        let handles = std::mem::take(&mut *self.handles.lock().unwrap_or_else(|e| e.into_inner()));
+        if handles.is_empty() {
+            return;
+        }
+
+        log::debug!("Waiting for {} background tasks", handles.len());
+        let aborts: Vec<_> = handles.iter().map(JoinHandle::abort_handle).collect();
+        if tokio::time::timeout(timeout, futures::future::join_all(handles))
+            .await
+            .is_err()
+        {
+            let running = aborts.iter().filter(|abort| !abort.is_finished()).count();
+            log::warn!(
+                "Aborting {} background tasks still running on exit",
+                running
+            );
+            for abort in aborts {
+                abort.abort();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_and_waits() {
+        let tasks = BackgroundTasks::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        // Never ends on its own, so only cancellation gets shutdown past it
+        tasks.spawn(std::future::pending());
+        let done = finished.clone();
+        tasks.spawn_to_completion(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            done.store(true, Ordering::Relaxed);
+        });
+
+        let start = std::time::Instant::now();
+        tasks.shutdown(Duration::from_secs(5)).await;
+        assert!(finished.load(Ordering::Relaxed));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -1,3 +1,6 @@
+use std::io::ErrorKind;
+use std::time::Duration;
+
 use mpd_client::Client;
 use tokio::net::TcpStream;
 
@@ -27,3 +30,97 @@ pub async fn connect_to_mpd(
         Ok(Client::connect(connection).await?)
     }
 }
+
+/// Like [`connect_to_mpd`], but give up after `timeout` (None waits as long as it takes)
+pub async fn connect_with_timeout(
+    address: &str,
+    timeout: Option<Duration>,
+) -> color_eyre::Result<(Client, mpd_client::client::ConnectionEvents)> {
+    let Some(timeout) = timeout else {
+        return connect_to_mpd(address).await;
+    };
+    match tokio::time::timeout(timeout, connect_to_mpd(address)).await {
+        Ok(result) => result,
+        Err(_) => Err(std::io::Error::new(
+            ErrorKind::TimedOut,
+            format!("no answer within {}s", timeout.as_secs()),
+        )
+        .into()),
+    }
+}
+
+/// Explanation printed when the startup connection fails: the address that was tried,
+/// the error, and what usually causes it
+pub fn connection_failure_message(address: &str, error: &color_eyre::Report) -> String {
+    let mut message = format!(
+        "zarumet: could not connect to MPD at {}: {}\n\nLikely causes:\n",
+        address, error
+    );
+    for cause in likely_causes(address, error) {
+        message.push_str("  - ");
+        message.push_str(cause);
+        message.push('\n');
+    }
+    message.push_str(
+        "\nSet the address with `address` in the [mpd] section of the config, \
+         or with --address.",
+    );
+    message
+}
+
+/// Usual reasons for a failed connection, guessed from the kind of error
+fn likely_causes(address: &str, error: &color_eyre::Report) -> &'static [&'static str] {
+    let is_unix_socket = address.contains('/');
+    match error.downcast_ref::<std::io::Error>().map(|e| e.kind()) {
+        Some(ErrorKind::ConnectionRefused) => &[
+            "MPD is not running",
+            "MPD listens on a different port (see `port` in mpd.conf)",
+        ],
+        Some(ErrorKind::NotFound) if is_unix_socket => &[
+            "MPD is not running, so the socket doesn't exist",
+            "the socket path differs from `bind_to_address` in mpd.conf",
+        ],
+        Some(ErrorKind::PermissionDenied) => {
+            &["your user may not access the socket (check its owner and permissions)"]
+        }
+        Some(ErrorKind::TimedOut) => &[
+            "the host is unreachable or down",
+            "a firewall drops connections to the port",
+            "raise `connect_timeout_secs` for a slow network",
+        ],
+        Some(_) => &[
+            "the host name can't be resolved",
+            "the address isn't in host:port form",
+        ],
+        // The connection was made, but the greeting wasn't MPD's
+        None => &["something other than MPD listens on that address"],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_message_names_address_and_cause() {
+        let error: color_eyre::Report =
+            std::io::Error::new(ErrorKind::ConnectionRefused, "Connection refused").into();
+        let message = connection_failure_message("localhost:6600", &error);
+        assert!(message.contains("at localhost:6600: Connection refused"));
+        assert!(message.contains("  - MPD is not running\n"));
+    }
+
+    #[test]
+    fn test_likely_causes_by_error_kind() {
+        let timed_out: color_eyre::Report =
+            std::io::Error::new(ErrorKind::TimedOut, "no answer within 5s").into();
+        assert!(likely_causes("host:6600", &timed_out)[0].contains("unreachable"));
+
+        let missing: color_eyre::Report =
+            std::io::Error::new(ErrorKind::NotFound, "No such file").into();
+        assert!(likely_causes("/run/mpd/socket", &missing)[0].contains("socket"));
+
+        let not_mpd = color_eyre::eyre::eyre!("invalid greeting");
+        assert!(likely_causes("host:6600", &not_mpd)[0].contains("other than MPD"));
+    }
+}
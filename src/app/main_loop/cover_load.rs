@@ -1,120 +1,428 @@
 use crate::app::{
     SongInfo,
-    ui::cache::cover_cache::{SharedCoverCache, get_prefetch_targets},
+    config::cover::{CoverConfig, CoverPlaceholder, CoverSource},
+    main_loop::{BACKGROUND, cover_placeholder::placeholder_cover},
+    song::TagSettings,
+    ui::cache::cover_cache::{CoverKey, CoverLookup, SharedCoverCache, get_prefetch_targets},
 };
-use mpd_client::Client;
-use std::path::PathBuf;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader};
+use mpd_client::{
+    Client,
+    client::CommandError,
+    commands::{AlbumArt, AlbumArtEmbedded},
+    responses,
+};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::{Semaphore, mpsc, oneshot};
+
+/// How covers are fetched and prepared, taken from the `[cover]` config when the main
+/// loop starts and shared by every load it spawns
+pub struct CoverLoadSettings {
+    /// Sources tried in order (none when covers aren't shown)
+    sources: Vec<CoverSource>,
+    /// Cache one cover per album (shared by all its tracks) instead of one per file
+    cache_by_album: bool,
+    /// Longest side covers are scaled down to before caching (0 = no limit)
+    max_dimension: u32,
+    /// Shown for songs without cover art (None = nothing)
+    placeholder: Option<CoverPlaceholder>,
+    /// Limit on cover art fetches running against MPD at once
+    fetch_permits: Semaphore,
+    /// Groups album artists the same way as the library, for album cover keys
+    tags: Arc<TagSettings>,
+}
+
+impl CoverLoadSettings {
+    /// Settings for `config`. Without `show_covers` (covers disabled or undrawable)
+    /// there are no sources, so nothing is fetched from MPD.
+    pub fn new(config: &CoverConfig, show_covers: bool, tags: Arc<TagSettings>) -> Self {
+        Self {
+            sources: if show_covers {
+                config.cover_sources()
+            } else {
+                Vec::new()
+            },
+            cache_by_album: config.cache_by_album,
+            max_dimension: config.max_dimension,
+            placeholder: config.cover_placeholder().filter(|_| show_covers),
+            fetch_permits: Semaphore::new(config.max_concurrent_fetches.max(1)),
+            tags,
+        }
+    }
+
+    fn cover_key(&self, song: &SongInfo) -> CoverKey {
+        CoverKey::for_song(song, self.cache_by_album, &self.tags)
+    }
+
+    /// `image`, or the configured placeholder for `album` when the song has no cover art
+    fn or_placeholder(&self, image: Option<DynamicImage>, album: &str) -> Option<DynamicImage> {
+        image.or_else(|| self.placeholder.map(|kind| placeholder_cover(kind, album)))
+    }
+}
+
+/// Request one chunk of cover art from the given source
+async fn request_chunk(
+    client: &Client,
+    uri: &str,
+    source: CoverSource,
+    offset: usize,
+) -> Result<Option<responses::AlbumArt>, CommandError> {
+    match source {
+        CoverSource::Embedded => {
+            client
+                .command(AlbumArtEmbedded::new(uri).offset(offset))
+                .await
+        }
+        CoverSource::Folder => client.command(AlbumArt::new(uri).offset(offset)).await,
+    }
+}
+
+/// Load the complete cover art from a single source.
+/// Keeps requesting at increasing offsets until the reported size has arrived,
+/// so large covers are never truncated by the binary limit.
+async fn load_from_source(
+    client: &Client,
+    uri: &str,
+    source: CoverSource,
+) -> Result<Option<Vec<u8>>, CommandError> {
+    let Some(first) = request_chunk(client, uri, source, 0).await? else {
+        return Ok(None);
+    };
+
+    let mut data = first.data;
+    data.reserve(first.size.saturating_sub(data.len()));
+
+    while data.len() < first.size {
+        match request_chunk(client, uri, source, data.len()).await? {
+            Some(chunk) if !chunk.data.is_empty() => data.extend_from_slice(&chunk.data),
+            _ => {
+                log::debug!(
+                    "Incomplete {} cover art for {} ({}/{} bytes)",
+                    source,
+                    uri,
+                    data.len(),
+                    first.size
+                );
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(Some(data.to_vec()))
+}
 
-use tokio::sync::mpsc;
+/// Load cover art for a song, trying each configured source in order. Waits for a
+/// fetch permit first, so skipping through a queue can't flood MPD with requests.
+async fn load_cover_art(
+    client: &Client,
+    uri: &str,
+    settings: &CoverLoadSettings,
+) -> Option<Vec<u8>> {
+    // The semaphore is never closed
+    let _permit = settings.fetch_permits.acquire().await.ok()?;
+    for &source in &settings.sources {
+        match load_from_source(client, uri, source).await {
+            Ok(Some(data)) => return Some(data),
+            Ok(None) => log::trace!("No {} cover art for {}", source, uri),
+            Err(e) => log::debug!("Failed to load {} cover art for {}: {}", source, uri, e),
+        }
+    }
+    None
+}
+
+/// Decode cover art bytes, None when they aren't an image the `image` crate can read
+fn decode_cover(data: &[u8], uri: &str) -> Option<DynamicImage> {
+    let decoded = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(image::ImageError::IoError)
+        .and_then(|reader| reader.decode());
+    match decoded {
+        Ok(image) => {
+            log::trace!(
+                "Decoded cover art for {}: {}x{}",
+                uri,
+                image.width(),
+                image.height()
+            );
+            Some(image)
+        }
+        Err(e) => {
+            log::debug!("Ignoring undecodable cover art for {}: {}", uri, e);
+            None
+        }
+    }
+}
+
+/// Scale `image` down to fit `max_dimension` on its longer side, keeping its aspect
+/// ratio. None when it is small enough already.
+fn downscale_cover(image: &DynamicImage, max_dimension: u32) -> Option<DynamicImage> {
+    if max_dimension == 0 || image.width().max(image.height()) <= max_dimension {
+        return None;
+    }
+    Some(image.resize(max_dimension, max_dimension, FilterType::Lanczos3))
+}
+
+/// Decode cover art on the blocking pool, None when the bytes don't decode.
+/// Covers above `max_dimension` are scaled down, so the cache and the renderer only
+/// ever see the smaller copy. Large covers take long enough to decode that they
+/// would stall the runtime.
+async fn validate_cover(
+    data: Option<Vec<u8>>,
+    uri: String,
+    max_dimension: u32,
+) -> Option<DynamicImage> {
+    let data = data?;
+    tokio::task::spawn_blocking(move || {
+        let image = decode_cover(&data, &uri)?;
+        match downscale_cover(&image, max_dimension) {
+            Some(scaled) => {
+                log::debug!(
+                    "Downscaled cover art for {} from {}x{} to {}x{}",
+                    uri,
+                    image.width(),
+                    image.height(),
+                    scaled.width(),
+                    scaled.height()
+                );
+                Some(scaled)
+            }
+            None => Some(image),
+        }
+    })
+    .await
+    .unwrap_or_default()
+}
 
 /// Message type for cover art loading results
 pub enum CoverArtMessage {
-    Loaded(Option<Vec<u8>>, PathBuf),
+    /// Decoded cover of the file (None when it has no usable cover art)
+    Loaded(Option<DynamicImage>, PathBuf),
+}
+
+impl CoverArtMessage {
+    /// The loaded cover if it belongs to `current`, the file of the song on screen.
+    /// Loads can finish out of order, so a cover of any other file is dropped instead
+    /// of replacing the one shown.
+    pub fn cover_for(self, current: Option<&Path>) -> Option<Option<DynamicImage>> {
+        match self {
+            CoverArtMessage::Loaded(cover, file_path) => {
+                if current == Some(file_path.as_path()) {
+                    Some(cover)
+                } else {
+                    log::debug!("Dropping cover art of {:?}, no longer current", file_path);
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Handle to a cover art load started by [`spawn_cover_art_loader`]. Dropping it
+/// cancels the load if it is still waiting on MPD.
+pub struct CoverLoadHandle {
+    _cancel: oneshot::Sender<()>,
 }
 
-/// Spawn a background task to load cover art with cache support
+/// Spawn a background task to load cover art with cache support. The fetch from MPD
+/// is given up once the returned handle is dropped, so a cover arriving after the
+/// song changed again can't show up late.
 pub fn spawn_cover_art_loader(
     client: &Client,
-    file_path: PathBuf,
+    song: &SongInfo,
     tx: mpsc::Sender<CoverArtMessage>,
     cache: SharedCoverCache,
-) {
+    settings: &Arc<CoverLoadSettings>,
+) -> CoverLoadHandle {
+    let (cancel, mut cancelled) = oneshot::channel();
     let client = client.clone();
-    let file_path_clone = file_path.clone();
-
-    tokio::spawn(async move {
-        // Check cache first
-        {
-            let mut cache_guard = cache.write().await;
-            if let Some(cached) = cache_guard.get(&file_path_clone) {
-                log::debug!("Cover art cache hit: {:?}", file_path_clone);
-                let _ = tx
-                    .send(CoverArtMessage::Loaded(
-                        cached.data.clone(),
-                        file_path_clone,
-                    ))
-                    .await;
-                return;
-            }
+    let settings = settings.clone();
+    let file_path = song.file_path.clone();
+    let album = song.album.clone();
+    let key = settings.cover_key(song);
 
-            // Check if already being fetched
-            if cache_guard.is_pending(&file_path_clone) {
-                log::debug!("Cover art already pending: {:?}", file_path_clone);
-                return;
+    BACKGROUND.spawn(async move {
+        // Use the cached cover, or wait for another task already fetching it (another
+        // track of the album, a prefetch) and look again once that is done
+        loop {
+            let lookup = cache.write().await.lookup(&key);
+            match lookup {
+                CoverLookup::Cached(image) => {
+                    log::debug!("Cover art cache hit: {:?}", key);
+                    let image = settings.or_placeholder(image, &album);
+                    let _ = tx.send(CoverArtMessage::Loaded(image, file_path)).await;
+                    return;
+                }
+                CoverLookup::Pending(mut done) => {
+                    log::debug!("Waiting for pending cover art: {:?}", key);
+                    tokio::select! {
+                        _ = done.changed() => {}
+                        _ = &mut cancelled => return,
+                    }
+                }
+                CoverLookup::Fetch => break,
             }
-
-            // Mark as pending
-            cache_guard.mark_pending(file_path_clone.clone());
         }
 
-        // Fetch from MPD
-        let uri = file_path_clone.to_string_lossy();
-        let result = client.album_art(&uri).await;
-
-        let data = match result {
-            Ok(Some((raw_data, _mime))) => Some(raw_data.to_vec()),
-            Ok(None) => None,
-            Err(e) => {
-                log::debug!("Failed to load cover art: {}", e);
-                None
+        // Fetch from MPD, caching only covers that decode so none reach the renderer
+        let uri = SongInfo::uri_for_path(&file_path).into_owned();
+        let fetch = async {
+            let data = load_cover_art(&client, &uri, &settings).await;
+            validate_cover(data, uri.clone(), settings.max_dimension).await
+        };
+        let image = tokio::select! {
+            loaded = fetch => loaded,
+            _ = &mut cancelled => {
+                log::debug!("Cover art load cancelled: {:?}", key);
+                cache.write().await.unmark_pending(&key);
+                return;
             }
         };
 
         // Store in cache
         {
             let mut cache_guard = cache.write().await;
-            cache_guard.insert(file_path_clone.clone(), data.clone());
+            cache_guard.insert(key, image.clone());
         }
 
         // Send result back (ignore error if receiver dropped)
-        let _ = tx
-            .send(CoverArtMessage::Loaded(data, file_path_clone))
-            .await;
+        let image = settings.or_placeholder(image, &album);
+        let _ = tx.send(CoverArtMessage::Loaded(image, file_path)).await;
     });
+
+    CoverLoadHandle { _cancel: cancel }
 }
 
-/// Spawn background tasks to prefetch cover art for adjacent queue items
+/// Spawn background tasks to prefetch cover art for up to `count` queue items on either
+/// side of the current one (see [`get_prefetch_targets`] for consume mode)
 pub fn spawn_prefetch_loaders(
     client: &Client,
     queue: &[SongInfo],
     current_index: Option<usize>,
+    count: usize,
+    consume: bool,
     cache: SharedCoverCache,
+    settings: &Arc<CoverLoadSettings>,
 ) {
-    let targets = get_prefetch_targets(queue, current_index);
+    for song in get_prefetch_targets(queue, current_index, count, consume) {
+        spawn_prefetch_loader(client, song, cache.clone(), settings);
+    }
+}
 
-    for file_path in targets {
-        let client = client.clone();
-        let cache = cache.clone();
+/// Spawn a background task that puts `song`'s cover art in the cache, unless it is
+/// already cached. A fetch already running for it is waited on, and taken over if
+/// it is given up (its song was skipped).
+pub fn spawn_prefetch_loader(
+    client: &Client,
+    song: &SongInfo,
+    cache: SharedCoverCache,
+    settings: &Arc<CoverLoadSettings>,
+) {
+    let client = client.clone();
+    let settings = settings.clone();
+    let file_path = song.file_path.clone();
+    let key = settings.cover_key(song);
 
-        tokio::spawn(async move {
-            // Check if already cached or pending
-            {
+    BACKGROUND.spawn(async move {
+        loop {
+            let lookup = {
                 let mut cache_guard = cache.write().await;
-                if cache_guard.contains(&file_path) || cache_guard.is_pending(&file_path) {
+                if cache_guard.contains(&key) {
                     return;
                 }
-                cache_guard.mark_pending(file_path.clone());
+                cache_guard.lookup(&key)
+            };
+            match lookup {
+                CoverLookup::Cached(_) => return,
+                CoverLookup::Pending(mut done) => {
+                    let _ = done.changed().await;
+                }
+                CoverLookup::Fetch => break,
             }
+        }
 
-            // Fetch from MPD
-            let uri = file_path.to_string_lossy();
-            let result = client.album_art(&uri).await;
+        // Fetch from MPD
+        let uri = SongInfo::uri_for_path(&file_path).into_owned();
+        let data = load_cover_art(&client, &uri, &settings).await;
+        let image = validate_cover(data, uri, settings.max_dimension).await;
 
-            let data = match result {
-                Ok(Some((raw_data, _mime))) => Some(raw_data.to_vec()),
-                Ok(None) => None,
-                Err(e) => {
-                    log::debug!("Failed to prefetch cover art: {}", e);
-                    None
-                }
-            };
+        // Store in cache (no need to send to channel - it's a prefetch)
+        {
+            let mut cache_guard = cache.write().await;
+            cache_guard.insert(key, image);
+            log::debug!("Prefetched cover art: {:?}", file_path);
+        }
+    });
+}
 
-            // Store in cache (no need to send to channel - it's a prefetch)
-            {
-                let mut cache_guard = cache.write().await;
-                cache_guard.insert(file_path.clone(), data);
-                log::debug!("Prefetched cover art: {:?}", file_path);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cover_settings_follow_config() {
+        let config = CoverConfig {
+            cache_by_album: true,
+            placeholder: "initials".to_string(),
+            ..CoverConfig::default()
+        };
+        let shown = CoverLoadSettings::new(&config, true, Arc::default());
+        assert!(!shown.sources.is_empty());
+        assert!(shown.cache_by_album);
+        assert!(shown.or_placeholder(None, "Album").is_some());
+
+        // Covers that can't be shown are neither fetched nor stood in for
+        let hidden = CoverLoadSettings::new(&config, false, Arc::default());
+        assert!(hidden.sources.is_empty());
+        assert!(hidden.or_placeholder(None, "Album").is_none());
+    }
+
+    #[test]
+    fn test_decode_cover_rejects_malformed_data() {
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(3, 2)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let decoded = decode_cover(&png, "song.flac").unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+
+        // A truncated image and bytes in no known format count as no cover
+        assert!(decode_cover(&png[..png.len() / 2], "song.flac").is_none());
+        assert!(decode_cover(b"not an image", "song.flac").is_none());
+    }
+
+    #[test]
+    fn test_downscale_cover_keeps_aspect_ratio() {
+        let image = DynamicImage::new_rgb8(300, 150);
+        let scaled = downscale_cover(&image, 100).unwrap();
+        assert_eq!((scaled.width(), scaled.height()), (100, 50));
+
+        // Small enough already, or no limit at all
+        assert!(downscale_cover(&image, 300).is_none());
+        assert!(downscale_cover(&image, 0).is_none());
+    }
+
+    #[test]
+    fn test_cover_for_drops_covers_arriving_out_of_order() {
+        let first = PathBuf::from("a/first.flac");
+        let second = PathBuf::from("b/second.flac");
+        let loaded = |width, file: &PathBuf| {
+            CoverArtMessage::Loaded(Some(DynamicImage::new_rgb8(width, 1)), file.clone())
+        };
+
+        // The song changed to `second` before the load of `first` came back
+        let mut shown: Option<DynamicImage> = None;
+        for msg in [loaded(2, &second), loaded(1, &first)] {
+            if let Some(cover) = msg.cover_for(Some(&second)) {
+                shown = cover;
             }
-        });
+        }
+        assert_eq!(shown.map(|cover| cover.width()), Some(2));
+
+        // Nothing playing takes no cover at all
+        assert!(loaded(1, &first).cover_for(None).is_none());
     }
 }
@@ -0,0 +1,187 @@
+use crate::app::config::cover::CoverPlaceholder;
+use image::{DynamicImage, Rgb, RgbImage};
+
+/// Side length of the generated placeholders in pixels; they are scaled like real covers
+const SIZE: u32 = 256;
+
+/// Pixels per font dot: two 5x7 glyphs fill about half the width
+const SCALE: u32 = 11;
+
+/// Rows of a 5x7 glyph, the leftmost column in bit 4
+type Glyph = [u8; 7];
+
+/// Music note of the default placeholder, also drawn when an album has no drawable initials
+#[rustfmt::skip]
+const NOTE: Glyph = [0b00110, 0b00101, 0b00100, 0b00100, 0b11100, 0b11100, 0b00000];
+
+/// Glyph of an uppercase ASCII letter or digit
+#[rustfmt::skip]
+fn glyph(c: char) -> Option<Glyph> {
+    Some(match c {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        _ => return None,
+    })
+}
+
+/// Up to two initials of `album`: the first letter or digit of its first words.
+/// Characters the built-in font can't draw are skipped.
+fn initials(album: &str) -> Vec<char> {
+    album
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|&c| glyph(c).is_some())
+        .take(2)
+        .collect()
+}
+
+/// Stable hash of `text` (FNV-1a), so an album gets the same colors on every run
+fn hash(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Convert a hue (degrees), saturation and value (0-1) to RGB
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let chroma = value * saturation;
+    let sector = (hue / 60.0) % 6.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    [r + m, g + m, b + m]
+}
+
+/// Fill `image` with a diagonal gradient from `from` (top left) to `to` (bottom right)
+fn draw_gradient(image: &mut RgbImage, from: [f32; 3], to: [f32; 3]) {
+    let span = (image.width() + image.height()).saturating_sub(2).max(1) as f32;
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let t = (x + y) as f32 / span;
+        *pixel = Rgb(std::array::from_fn(|i| {
+            ((from[i] + (to[i] - from[i]) * t) * 255.0).round() as u8
+        }));
+    }
+}
+
+/// Draw `glyphs` side by side, centered in `image`
+fn draw_glyphs(image: &mut RgbImage, glyphs: &[Glyph]) {
+    let count = glyphs.len() as u32;
+    let width = count * 5 * SCALE + count.saturating_sub(1) * SCALE;
+    let left = image.width().saturating_sub(width) / 2;
+    let top = image.height().saturating_sub(7 * SCALE) / 2;
+    let color = Rgb([240, 240, 240]);
+
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let glyph_left = left + index as u32 * 6 * SCALE;
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..5 {
+                if bits & (0b10000 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        let x = glyph_left + column * SCALE + dx;
+                        let y = top + row as u32 * SCALE + dy;
+                        if x < image.width() && y < image.height() {
+                            image.put_pixel(x, y, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generate the placeholder shown for a song of `album` without cover art
+pub fn placeholder_cover(kind: CoverPlaceholder, album: &str) -> DynamicImage {
+    let mut image = RgbImage::new(SIZE, SIZE);
+    match kind {
+        CoverPlaceholder::Default => {
+            draw_gradient(&mut image, [0.35; 3], [0.15; 3]);
+            draw_glyphs(&mut image, &[NOTE]);
+        }
+        CoverPlaceholder::Initials => {
+            let hue = (hash(album) % 360) as f32;
+            draw_gradient(
+                &mut image,
+                hsv_to_rgb(hue, 0.55, 0.65),
+                hsv_to_rgb((hue + 40.0) % 360.0, 0.65, 0.35),
+            );
+            let glyphs: Vec<Glyph> = initials(album).into_iter().filter_map(glyph).collect();
+            if glyphs.is_empty() {
+                draw_glyphs(&mut image, &[NOTE]);
+            } else {
+                draw_glyphs(&mut image, &glyphs);
+            }
+        }
+    }
+    DynamicImage::ImageRgb8(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initials_of_album_names() {
+        assert_eq!(initials("The Dark Side of the Moon"), vec!['T', 'D']);
+        assert_eq!(
+            initials("(What's the Story) Morning Glory?"),
+            vec!['W', 'T']
+        );
+        assert_eq!(initials("1989"), vec!['1']);
+        // Names the font can't draw get the note instead
+        assert!(initials("東京 事変").is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_colors_follow_album() {
+        let cover = |album| placeholder_cover(CoverPlaceholder::Initials, album).into_rgb8();
+        let first = cover("Substance");
+        assert_eq!((first.width(), first.height()), (SIZE, SIZE));
+        assert_eq!(first.get_pixel(0, 0), cover("Substance").get_pixel(0, 0));
+        assert_ne!(first.get_pixel(0, 0), cover("Movement").get_pixel(0, 0));
+    }
+}
@@ -0,0 +1,45 @@
+use mpd_client::Client;
+use std::sync::Arc;
+
+use crate::app::{
+    LazyLibrary,
+    config::mpd::GroupBy,
+    song::{LoadProgress, LoadProgressSender, TagSettings},
+};
+
+/// Load everything the first screen needs: the artist names, the first artist's
+/// albums and, when starting in the Albums view, every album.
+/// Runs as a background task so the main loop can draw a loading screen meanwhile.
+pub async fn load_initial_library(
+    client: Client,
+    group_by: GroupBy,
+    tags: Arc<TagSettings>,
+    preload_albums: bool,
+    load_by_artist: bool,
+    progress: LoadProgressSender,
+) -> color_eyre::Result<LazyLibrary> {
+    let mut library = LazyLibrary::init(&client, group_by, tags, Some(&progress)).await?;
+
+    // Load the first artist's albums immediately for better UX
+    if !library.artists.is_empty() {
+        let _ = progress.send(LoadProgress::LoadingAlbums { done: 0, total: 0 });
+        if let Err(e) = library.load_artist(&client, 0).await {
+            log::warn!("Failed to load first artist: {}", e);
+        }
+    }
+
+    if preload_albums {
+        let result = if load_by_artist {
+            library
+                .preload_all_albums_by_artist(&client, Some(&progress))
+                .await
+        } else {
+            library.preload_all_albums(&client, Some(&progress)).await
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to preload all albums: {}", e);
+        }
+    }
+
+    Ok(library)
+}
@@ -1,15 +1,19 @@
-use std::io::Cursor;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use mpd_client::client::{ConnectionEvent, Subsystem};
+use chrono::Timelike;
+use crossterm::event::EventStream;
+use futures::StreamExt;
+use mpd_client::Client;
+use mpd_client::client::{ConnectionEvent, ConnectionEvents, Subsystem};
 use mpd_client::responses::PlayState;
-use ratatui::DefaultTerminal;
-use ratatui_image::picker::Picker;
+use ratatui::{DefaultTerminal, buffer::Buffer, layout::Rect};
 
 #[cfg(target_os = "linux")]
 use crate::app::audio::pipewire;
+use crate::app::config::cover::CoverScaling;
 #[cfg(target_os = "linux")]
 use crate::app::config::pipewire::resolve_bit_perfect_rate;
 #[cfg(target_os = "linux")]
@@ -19,81 +23,113 @@ use tokio::sync::mpsc;
 
 use crate::App;
 use crate::app::LazyLibrary;
-use crate::app::main_loop::connect_to_mpd;
+use crate::app::main_loop::load_initial_library;
 
-use crate::app::song::SongInfo;
-use crate::app::ui::Protocol;
+use crate::app::song::{LoadProgress, SongInfo};
+use crate::app::terminal::detect_image_support;
 use crate::app::ui::WIDTH_CACHE;
-use crate::app::ui::cache::cover_cache::{find_current_index, new_shared_cache};
-use crate::app::ui::rendering::render;
+use crate::app::ui::cache::cover_cache::{find_current_index, is_near_track_end, new_shared_cache};
+use crate::app::ui::rendering::{render, render_loading_screen, render_progress_only};
+use crate::app::ui::{ImageSupport, MenuMode, Protocol};
 use crate::app::{
     MessageType, StatusMessage, event_handlers::EventHandlers, mpd_updates::MPDUpdates,
 };
 
 use crate::app::main_loop::check_song_change;
 
-use crate::app::main_loop::{CoverArtMessage, spawn_cover_art_loader, spawn_prefetch_loaders};
+use crate::app::main_loop::{
+    BACKGROUND, CoverArtMessage, CoverLoadSettings, spawn_cover_art_loader, spawn_prefetch_loader,
+    spawn_prefetch_loaders,
+};
 
 /// Interval for progress bar updates when playing (in milliseconds)
 const PROGRESS_UPDATE_INTERVAL_MS: u64 = 500;
 
+/// Interval for timer-driven work such as key sequence timeouts (in milliseconds)
+const HOUSEKEEPING_INTERVAL_MS: u64 = 50;
+
+/// Interval between loading screen spinner frames (in milliseconds)
+const LOADING_SPINNER_INTERVAL_MS: u64 = 100;
+
+/// Time the terminal size has to stay unchanged before a resize is applied (in milliseconds)
+const RESIZE_DEBOUNCE_MS: u64 = 50;
+
+/// Longest time quitting waits for background tasks before aborting them (in milliseconds)
+const SHUTDOWN_TIMEOUT_MS: u64 = 2000;
+
 /// Trait for main application loop
 pub trait AppMainLoop {
-    async fn run(self, terminal: DefaultTerminal) -> color_eyre::Result<()>
+    async fn run(
+        self,
+        terminal: DefaultTerminal,
+        connection: (Client, ConnectionEvents),
+    ) -> color_eyre::Result<()>
     where
         Self: Sized;
 }
 
 impl AppMainLoop for App {
     /// Run the application's main loop.
-    async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
+    async fn run(
+        mut self,
+        mut terminal: DefaultTerminal,
+        connection: (Client, ConnectionEvents),
+    ) -> color_eyre::Result<()> {
         self.running = true;
 
-        // Connect to MPD
-        log::info!(
-            "Attempting to connect to MPD at: {}",
-            self.config.mpd.address
-        );
+        // Connected in main, before the terminal was taken over
+        let (client, mut state_changes) = connection;
 
-        let (client, mut state_changes) = connect_to_mpd(&self.config.mpd.address)
-            .await
-            .inspect_err(|e| {
-                crate::logging::log_mpd_connection(
-                    &self.config.mpd.address,
-                    false,
-                    Some(&e.to_string()),
-                );
-            })?;
-
-        crate::logging::log_mpd_connection(&self.config.mpd.address, true, None);
-
-        match SongInfo::set_max_art_size(&client, 5 * 1024 * 1024).await {
+        // Cover art is fetched in chunks of this size, so it only trades
+        // round trips against per-response memory, never image completeness
+        let binary_limit_kb = self.config.mpd.binary_limit_kb;
+        match SongInfo::set_max_art_size(&client, binary_limit_kb * 1024).await {
             Ok(_) => {
-                log::debug!("Set MPD binary limit to 5MB");
+                log::debug!("Set MPD binary limit to {}KiB", binary_limit_kb);
             }
             Err(e) => {
                 log::warn!("Failed to set MPD binary limit: {}", e);
             }
         }
 
-        // Load library (lazy - only artist names initially)
-        match LazyLibrary::init(&client).await {
-            Ok(library) => {
-                self.library = Some(library);
+        // Load library (lazy - only artist names initially) in the background,
+        // showing a loading screen with the current phase until it is done
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<LoadProgress>();
+        let mut loader = tokio::spawn(load_initial_library(
+            client.clone(),
+            self.config.mpd.group_by(),
+            Arc::clone(&self.tag_settings),
+            self.menu_mode == MenuMode::Albums,
+            self.config.mpd.load_by_artist(),
+            progress_tx,
+        ));
+
+        let mut phase = LoadProgress::ListingArtists;
+        let mut spinner_frame = 0;
+        let spinner_interval =
+            tokio::time::interval(Duration::from_millis(LOADING_SPINNER_INTERVAL_MS));
+        tokio::pin!(spinner_interval);
+
+        let load_result = loop {
+            terminal
+                .draw(|frame| render_loading_screen(frame, &phase, spinner_frame, &self.config))?;
+
+            tokio::select! {
+                result = &mut loader => {
+                    break result.unwrap_or_else(|e| Err(color_eyre::eyre::eyre!("Library loader failed: {}", e)));
+                }
+                Some(progress) = progress_rx.recv() => phase = progress,
+                _ = spinner_interval.tick() => spinner_frame += 1,
+            }
+        };
 
+        match load_result {
+            Ok(library) => {
                 // Initialize artist selection if library has artists
-                if let Some(ref library) = self.library
-                    && !library.artists.is_empty()
-                {
+                if !library.artists.is_empty() {
                     self.artist_list_state.select(Some(0));
-
-                    // Load the first artist's albums immediately for better UX
-                    if let Some(ref mut lib) = self.library
-                        && let Err(e) = lib.load_artist(&client, 0).await
-                    {
-                        log::warn!("Failed to load first artist: {}", e);
-                    }
                 }
+                self.library = Some(library);
             }
             Err(e) => {
                 log::error!("Failed to initialize music library: {}", e);
@@ -107,12 +143,28 @@ impl AppMainLoop for App {
             }
         }
 
+        // Starting directly in Albums mode needs the album list loaded (already done
+        // by the loader, this only sorts it and selects the first album)
+        if self.menu_mode == MenuMode::Albums {
+            self.preload_albums_for_view(&client);
+        }
+        if self.menu_mode == MenuMode::Files {
+            self.open_directory(&client, "", None).await;
+        }
+        self.start_album_counts(&client);
+
         // Set up the image picker and protocol
-        let mut picker = Picker::from_query_stdio().unwrap();
-        picker.set_background_color([0, 0, 0, 0]);
+        let (mut picker, image_support) = detect_image_support();
+        // Transparent unless a letterbox color is configured
+        let background = match self.config.cover.background_rgb() {
+            Some((r, g, b)) => [r, g, b, 255],
+            None => [0, 0, 0, 0],
+        };
+        picker.set_background_color(background);
 
         // Fetch initial song info and status
         self.run_updates(&client).await?;
+        self.offer_queue_restore();
 
         // Track the current song's file path
         let mut current_song_file: Option<PathBuf> = self
@@ -120,6 +172,12 @@ impl AppMainLoop for App {
             .as_ref()
             .map(|song| song.file_path.clone());
 
+        // Song whose next cover was already warmed near its end (once per track)
+        let mut end_prefetch_song: Option<mpd_client::commands::SongId> = None;
+
+        // Last full frame and where its progress bar is, for redrawing only the bar
+        let mut last_frame: Option<(Buffer, Rect)> = None;
+
         // Update samplerate on startup if needed
         #[cfg(target_os = "linux")]
         {
@@ -142,6 +200,13 @@ impl AppMainLoop for App {
             }
         }
 
+        // With covers disabled (or undrawable) there are no sources, so nothing is fetched from MPD
+        let cover_settings = Arc::new(CoverLoadSettings::new(
+            &self.config.cover,
+            self.config.cover.enabled && image_support != ImageSupport::Unsupported,
+            Arc::clone(&self.tag_settings),
+        ));
+
         // Channel for cover art loading results
         let (cover_tx, mut cover_rx) = mpsc::channel::<CoverArtMessage>(1);
 
@@ -149,23 +214,55 @@ impl AppMainLoop for App {
         let cover_cache = new_shared_cache();
 
         // Load initial cover art in background
-        if let Some(ref song) = self.current_song {
-            let file_path = song.file_path.clone();
-            spawn_cover_art_loader(&client, file_path, cover_tx.clone(), cover_cache.clone());
-        }
+        let mut cover_load = self.current_song.as_ref().map(|song| {
+            spawn_cover_art_loader(
+                &client,
+                song,
+                cover_tx.clone(),
+                cover_cache.clone(),
+                &cover_settings,
+            )
+        });
 
         // Prefetch cover art for adjacent queue items
-        let current_idx = find_current_index(&self.queue, &self.current_song);
-        spawn_prefetch_loaders(&client, &self.queue, current_idx, cover_cache.clone());
+        let current_idx =
+            find_current_index(&self.queue, self.mpd_status.as_ref(), &self.current_song);
+        spawn_prefetch_loaders(
+            &client,
+            &self.queue,
+            current_idx,
+            self.config.cover.prefetch_count,
+            self.mpd_status
+                .as_ref()
+                .is_some_and(|status| status.consume),
+            cover_cache.clone(),
+            &cover_settings,
+        );
 
         // Create protocol with no initial image (will be loaded async)
-        let mut protocol = Protocol { image: None };
+        let mut protocol = Protocol {
+            image: None,
+            support: image_support,
+            picker: Some(picker),
+            fill_source: None,
+        };
 
         // Progress update interval
         let progress_interval =
             tokio::time::interval(Duration::from_millis(PROGRESS_UPDATE_INTERVAL_MS));
         tokio::pin!(progress_interval);
 
+        // Wakes the loop for timers that have no event of their own. Input and MPD
+        // changes (via idle) wake it immediately, so nothing is polled faster than this.
+        let housekeeping_interval =
+            tokio::time::interval(Duration::from_millis(HOUSEKEEPING_INTERVAL_MS));
+        tokio::pin!(housekeeping_interval);
+
+        // Minute the clock showed at the last redraw it was part of
+        let mut shown_clock_minute = None;
+
+        let mut terminal_events = EventStream::new();
+
         // Set up signal handlers for graceful shutdown (Unix only)
         #[cfg(unix)]
         let mut sigint = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt())
@@ -182,15 +279,59 @@ impl AppMainLoop for App {
             self.dirty
                 .check_terminal_size(term_size.width, term_size.height);
 
-            // Only render if something has changed
-            if self.dirty.any_dirty() {
-                terminal.draw(|frame| {
-                    render(
+            // Apply a pending resize once it has settled: clear the screen so no
+            // leftovers of the old layout remain, then redraw everything
+            let resize_settling = match self.resize_pending_since {
+                Some(since) if since.elapsed() < Duration::from_millis(RESIZE_DEBOUNCE_MS) => true,
+                Some(_) => {
+                    self.resize_pending_since = None;
+                    terminal.autoresize()?;
+                    terminal.clear()?;
+                    self.dirty.mark_full_redraw();
+                    self.dirty.mark_cover_art();
+                    false
+                }
+                None => false,
+            };
+
+            // A clock tick with nothing else changed only redraws the progress bar
+            let partial = match last_frame {
+                Some((ref buffer, progress_area))
+                    if self.config.ui.partial_redraw
+                        && self.dirty.is_progress_only()
+                        && !resize_settling
+                        && buffer.area == Rect::new(0, 0, term_size.width, term_size.height) =>
+                {
+                    Some((buffer, progress_area))
+                }
+                _ => None,
+            };
+
+            if let Some((buffer, progress_area)) = partial {
+                let completed = terminal.draw(|frame| {
+                    render_progress_only(
+                        frame,
+                        buffer,
+                        progress_area,
+                        &self.current_song,
+                        &self.mpd_status,
+                        &self.sleep_timer,
+                        &self.config,
+                    )
+                })?;
+                last_frame = Some((completed.buffer.clone(), progress_area));
+                self.dirty.clear_progress();
+            } else if self.dirty.any_dirty() && !resize_settling {
+                // Only render if something has changed
+                let mut progress_area = None;
+                let completed = terminal.draw(|frame| {
+                    progress_area = render(
                         frame,
                         &mut protocol,
                         &self.current_song,
                         &self.queue,
                         &mut self.queue_list_state,
+                        self.queue_filter.as_mut(),
                         &self.config,
                         &self.menu_mode,
                         &self.library,
@@ -201,14 +342,23 @@ impl AppMainLoop for App {
                         &mut self.album_tracks_list_state,
                         &self.panel_focus,
                         &self.expanded_albums,
+                        &mut self.file_browser,
+                        &self.queued,
                         &self.mpd_status,
                         &self.key_binds,
                         self.bit_perfect_enabled,
+                        self.muted_volume.is_some(),
+                        self.now_playing_view,
+                        self.library_stale,
                         self.show_config_warnings_popup,
                         &self.config_warnings,
                         &self.status_message,
+                        &self.sleep_timer,
+                        &self.overlays,
+                        self.toast.as_ref(),
                     )
                 })?;
+                last_frame = progress_area.map(|area| (completed.buffer.clone(), area));
 
                 if let Some(ref mut img) = protocol.image {
                     img.last_encoding_result();
@@ -220,13 +370,21 @@ impl AppMainLoop for App {
 
             // Update key bindings for timeouts and mark dirty if state changed
             let was_awaiting = self.key_binds.is_awaiting_input();
-            self.key_binds.update();
+            let held_action = self.key_binds.update(&self.menu_mode, &self.panel_focus);
             if was_awaiting && !self.key_binds.is_awaiting_input() {
                 // Timeout occurred - need to clear the sequence indicator
                 self.dirty.mark_key_sequence();
             }
+            // A bound digit that no count followed
+            if let Some(action) = held_action {
+                self.on_key_action(action, None, &client).await?;
+            }
 
             self.check_status_message_expiry();
+            self.check_toast_expiry();
+            self.save_queue_snapshot_if_due(false);
+            self.check_album_preload().await;
+            self.check_album_counts();
             self.check_animation_updates();
 
             // Log width cache statistics periodically
@@ -250,28 +408,29 @@ impl AppMainLoop for App {
 
             // Event-driven loop using tokio::select!
             tokio::select! {
-                // Keyboard events (with short timeout for responsive UI)
-                _ = tokio::time::sleep(Duration::from_millis(10)) => {
-                    // Check for keyboard events non-blocking
-                    if crossterm::event::poll(Duration::from_millis(0))? {
-                        self.handle_crossterm_events(&client).await?;
-
-                        // If user action requires update, do it immediately
-                        if self.force_update {
-                            self.run_updates(&client).await?;
-                            self.force_update = false;
-
-                            // Check for song change after update
-                            check_song_change(
-                                &mut current_song_file,
-                                &self.current_song,
-                                &self.queue,
-                                &client,
-                                &cover_tx,
-                                &mut protocol,
-                                cover_cache.clone(),
-                            );
-                        }
+                // Terminal events, pushed by crossterm instead of polled
+                Some(event) = terminal_events.next() => {
+                    self.handle_crossterm_event(event?, &client).await?;
+
+                    // If user action requires update, do it immediately
+                    if self.force_update {
+                        self.run_updates(&client).await?;
+                        self.force_update = false;
+
+                        // Check for song change after update
+                        check_song_change(
+                            &mut current_song_file,
+                            &mut cover_load,
+                            &self.current_song,
+                            &self.mpd_status,
+                            &self.queue,
+                            &client,
+                            &cover_tx,
+                            &mut protocol,
+                            cover_cache.clone(),
+                            &cover_settings,
+                            self.config.cover.prefetch_count,
+                        );
                     }
                 }
 
@@ -304,7 +463,12 @@ impl AppMainLoop for App {
                                     self.run_updates(&client).await?;
                                 }
                                 Subsystem::Update => {
-                                    if self.library_reload_pending {
+                                    // The event comes both when the update starts and when it ends
+                                    let update_running = client
+                                        .command(mpd_client::commands::Status)
+                                        .await
+                                        .is_ok_and(|status| status.update_job.is_some());
+                                    if self.library_reload_pending && !update_running {
                                         let was_user_initiated = self.user_initiated_reload;
                                         self.update_in_progress = false;  // Allow new refreshes
                                         self.user_initiated_reload = false;  // Clear this flag
@@ -321,7 +485,9 @@ impl AppMainLoop for App {
 
                                         // Now reload the music library from MPD
                                         log::info!("Refreshing library...");
-                                        match LazyLibrary::init(&client).await {
+                                        // A preload of the old library would overwrite the new one
+                                        self.cancel_album_preload();
+                                        match LazyLibrary::init(&client, self.library_group_by(), Arc::clone(&self.tag_settings), None).await {
                                             Ok(new_library) => {
                                                 log::info!("Library refreshed successfully");
 
@@ -364,9 +530,17 @@ impl AppMainLoop for App {
                                                 self.album_display_list_state.select(None);
                                                 self.expanded_albums.clear();
 
+                                                self.library_stale = false;
+                                                self.start_album_counts(&client);
+                                                // Reload every album the same way as at startup
+                                                if self.menu_mode == MenuMode::Albums {
+                                                    self.preload_albums_for_view(&client);
+                                                }
+                                                if self.menu_mode == MenuMode::Files {
+                                                    self.refresh_file_browser(&client).await;
+                                                }
                                                 // Mark library as dirty for re-render
                                                 self.dirty.mark_library();
-
                                             }
                                             Err(e) => {
                                                 log::error!("Failed to refresh library: {}", e);
@@ -387,54 +561,77 @@ impl AppMainLoop for App {
                                         self.library_reload_pending = false;
                                         self.pending_artist_index = None;
                                     } else {
-                                        log::debug!("Database update completed (external), reloading silently...");
-
-                                        // Get current artist name (if any) to try to restore after reload
-                                        let current_artist_name = self.artist_list_state.selected()
-                                            .and_then(|idx| self.library.as_ref()?.artists.get(idx).map(|a| a.name.clone()));
-
-                                        match LazyLibrary::init(&client).await {
-                                            Ok(new_library) => {
-                                                self.library = Some(new_library);
-
-                                                // Restore artist selection (find by name to handle removals/renames)
-                                                if let Some(ref name) = current_artist_name {
-                                                    if let Some(ref mut library) = self.library {
-                                                        if let Some(new_idx) = library.artists.iter().position(|a| &a.name == name) {
-                                                            self.artist_list_state.select(Some(new_idx));
-                                                            // Artist still exists, keep album selections
-                                                        } else {
-                                                            // Artist no longer exists, select first and clear album selections
-                                                            if !library.artists.is_empty() {
-                                                                self.artist_list_state.select(Some(0));
-                                                            }
-                                                            self.album_list_state.select(None);
-                                                            self.album_display_list_state.select(None);
-                                                            self.expanded_albums.clear();
+                                        // External updates are picked up by the Database change they cause
+                                        log::debug!("Database update started or finished (running: {})", update_running);
+                                    }
+                                }
+                                // A Refresh reloads the library itself once its update has finished
+                                Subsystem::Database if self.library_reload_pending => {
+                                    log::debug!("Database changed by our own update");
+                                }
+                                Subsystem::Database if !self.config.mpd.auto_refresh_library => {
+                                    log::info!("MPD database changed, the library is out of date");
+                                    self.library_stale = true;
+                                    self.dirty.mark_full_redraw();
+                                }
+                                Subsystem::Database => {
+                                    log::debug!("MPD database changed (external), reloading silently...");
+
+                                    // Get current artist name (if any) to try to restore after reload
+                                    let current_artist_name = self.artist_list_state.selected()
+                                        .and_then(|idx| self.library.as_ref()?.artists.get(idx).map(|a| a.name.clone()));
+
+                                    // A preload of the old library would overwrite the new one
+                                    self.cancel_album_preload();
+                                    match LazyLibrary::init(&client, self.library_group_by(), Arc::clone(&self.tag_settings), None).await {
+                                        Ok(new_library) => {
+                                            self.library = Some(new_library);
+
+                                            // Restore artist selection (find by name to handle removals/renames)
+                                            if let Some(ref name) = current_artist_name {
+                                                if let Some(ref mut library) = self.library {
+                                                    if let Some(new_idx) = library.artists.iter().position(|a| &a.name == name) {
+                                                        self.artist_list_state.select(Some(new_idx));
+                                                        // Artist still exists, keep album selections
+                                                    } else {
+                                                        // Artist no longer exists, select first and clear album selections
+                                                        if !library.artists.is_empty() {
+                                                            self.artist_list_state.select(Some(0));
                                                         }
+                                                        self.album_list_state.select(None);
+                                                        self.album_display_list_state.select(None);
+                                                        self.expanded_albums.clear();
                                                     }
-                                                } else if let Some(ref mut library) = self.library {
-                                                    // No artist was selected, select first
-                                                    if !library.artists.is_empty() {
-                                                        self.artist_list_state.select(Some(0));
-                                                        if let Err(e) = library.load_artist(&client, 0).await {
-                                                            log::warn!("Failed to load first artist after refresh: {}", e);
-                                                        }
+                                                }
+                                            } else if let Some(ref mut library) = self.library {
+                                                // No artist was selected, select first
+                                                if !library.artists.is_empty() {
+                                                    self.artist_list_state.select(Some(0));
+                                                    if let Err(e) = library.load_artist(&client, 0).await {
+                                                        log::warn!("Failed to load first artist after refresh: {}", e);
                                                     }
                                                 }
+                                            }
 
-                                                // Mark library as dirty for re-render
-                                                self.dirty.mark_library();
+                                            self.library_stale = false;
+                                            self.start_album_counts(&client);
+                                            // Reload every album the same way as at startup
+                                            if self.menu_mode == MenuMode::Albums {
+                                                self.preload_albums_for_view(&client);
                                             }
-                                            Err(e) => {
-                                                log::error!("Failed to reload library after external update: {}", e);
+                                            if self.menu_mode == MenuMode::Files {
+                                                self.refresh_file_browser(&client).await;
                                             }
+                                            // Mark library as dirty for re-render
+                                            self.dirty.mark_library();
+                                        }
+                                        Err(e) => {
+                                            log::error!("Failed to reload library after external update: {}", e);
                                         }
                                     }
                                 }
-                                // Database, output, sticker, etc. - typically don't affect current playback
-                                Subsystem::Database
-                                | Subsystem::Output
+                                // Output, sticker, etc. - typically don't affect current playback
+                                Subsystem::Output
                                 | Subsystem::Sticker
                                 | Subsystem::Subscription
                                 | Subsystem::Message
@@ -452,27 +649,39 @@ impl AppMainLoop for App {
                                 }
                             }
 
+                            self.check_queue_end(&client).await;
+
                             // Check for song change
                             check_song_change(
                                 &mut current_song_file,
+                                &mut cover_load,
                                 &self.current_song,
+                                &self.mpd_status,
                                 &self.queue,
                                 &client,
                                 &cover_tx,
                                 &mut protocol,
                                 cover_cache.clone(),
+                                &cover_settings,
+                                self.config.cover.prefetch_count,
                             );
 
                             // Handle PipeWire sample rate changes
                             #[cfg(target_os = "linux")]
-                            handle_pipewire_state_change(
+                            if let Some(warning) = handle_pipewire_state_change(
                                 &self.config,
                                 self.bit_perfect_enabled,
                                 &self.mpd_status,
                                 &self.current_song,
                                 &mut self.last_play_state,
                                 &mut self.last_sample_rate,
-                            );
+                            ) {
+                                self.set_status_message(StatusMessage {
+                                    text: warning,
+                                    created_at: std::time::Instant::now(),
+                                    message_type: MessageType::Error,
+                                });
+                            }
                         }
                         Some(ConnectionEvent::ConnectionClosed(err)) => {
                             log::error!("MPD connection closed: {:?}", err);
@@ -485,8 +694,22 @@ impl AppMainLoop for App {
                     }
                 }
 
+                // Key sequence timeouts, status expiry, resize debounce and other timers
+                _ = housekeeping_interval.tick() => {}
+
                 // Progress bar updates (only when playing)
                 _ = progress_interval.tick() => {
+                    self.check_sleep_timer(&client).await;
+
+                    // The clock only changes once a minute, even while paused
+                    if self.config.ui.clock_format().is_some() {
+                        let minute = chrono::Local::now().minute();
+                        if shown_clock_minute != Some(minute) {
+                            shown_clock_minute = Some(minute);
+                            self.dirty.mark_progress();
+                        }
+                    }
+
                     // Only fetch status for progress updates when playing
                     if let Some(ref status) = self.mpd_status
                         && status.state == PlayState::Playing
@@ -504,6 +727,28 @@ impl AppMainLoop for App {
                                 song.update_playback_info(Some(new_status.state), progress);
                                 song.update_time_info(new_status.elapsed, new_status.duration);
                             }
+
+                            // Warm the next track's cover before it starts, so the
+                            // transition doesn't show an empty cover while it loads
+                            if let Some((_, song_id)) = new_status.current_song
+                                && end_prefetch_song != Some(song_id)
+                                && is_near_track_end(new_status.elapsed, new_status.duration)
+                            {
+                                end_prefetch_song = Some(song_id);
+                                if let Some(next) = new_status
+                                    .next_song
+                                    .and_then(|(position, _)| self.queue.get(position.0))
+                                {
+                                    log::debug!("Near track end, prefetching {:?}", next.file_path);
+                                    spawn_prefetch_loader(&client, next, cover_cache.clone(), &cover_settings);
+                                }
+                            }
+                            // The live bitrate sits outside the progress bar
+                            if self.mpd_status.as_ref().map(|status| status.bitrate)
+                                != Some(new_status.bitrate)
+                            {
+                                self.dirty.mark_status();
+                            }
                             self.mpd_status = Some(new_status);
 
                             // Mark progress as dirty to trigger redraw
@@ -514,25 +759,15 @@ impl AppMainLoop for App {
 
                 // Cover art loading results
                 Some(msg) = cover_rx.recv() => {
-                    match msg {
-                        CoverArtMessage::Loaded(data, file_path) => {
-                            // Only update if this is still the current song
-                            if current_song_file.as_ref() == Some(&file_path) {
-                                protocol.image = data
-                                    .as_ref()
-                                    .and_then(|raw_data| {
-                                        image::ImageReader::new(Cursor::new(raw_data))
-                                            .with_guessed_format()
-                                            .ok()
-                                    })
-                                    .and_then(|reader| reader.decode().ok())
-                                    .map(|dyn_img| picker.new_resize_protocol(dyn_img));
-
-                                // Mark cover art as dirty to trigger redraw
-                                self.dirty.mark_cover_art();
-                                log::debug!("Cover art loaded for {:?}", file_path);
-                            }
-                        }
+                    // Only a cover of the song playing now may replace the shown one
+                    let current = self.current_song.as_ref().map(|song| song.file_path.as_path());
+                    if let Some(cover) = msg.cover_for(current) {
+                        let fill = self.config.cover.cover_scaling() == CoverScaling::Fill;
+                        protocol.set_cover(cover, fill);
+
+                        // Mark cover art as dirty to trigger redraw
+                        self.dirty.mark_cover_art();
+                        log::debug!("Cover art loaded for {:?}", current);
                     }
                 }
             }
@@ -560,6 +795,21 @@ impl AppMainLoop for App {
 
         log::info!("Exiting main loop");
 
+        // Don't leave a preload running against a connection that is going away
+        self.cancel_album_preload();
+        self.cancel_album_counts();
+
+        self.save_queue_snapshot_if_due(true);
+
+        // Return MPD to the default output if it was moved during the session
+        self.reset_output_sink().await;
+
+        // Stop cover loads and let sample rate changes finish, so the reset below
+        // isn't overtaken by a late one
+        BACKGROUND
+            .shutdown(Duration::from_millis(SHUTDOWN_TIMEOUT_MS))
+            .await;
+
         // Reset PipeWire sample rate on exit
         #[cfg(target_os = "linux")]
         if self.bit_perfect_enabled && self.config.pipewire.is_available() {
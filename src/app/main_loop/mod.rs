@@ -1,6 +1,14 @@
+pub mod album_counts;
+pub mod background;
 pub mod connection;
 pub mod cover_load;
+pub mod cover_placeholder;
+pub mod library_load;
 pub mod mloop;
+pub mod output_sink;
+pub mod queue_end;
+pub mod queue_snapshot;
+pub mod sleep_timer;
 
 pub mod state;
 
@@ -9,6 +17,14 @@ pub use state::check_song_change;
 #[cfg(target_os = "linux")]
 pub use state::handle_pipewire_state_change;
 
-pub use connection::connect_to_mpd;
-pub use cover_load::{CoverArtMessage, spawn_cover_art_loader, spawn_prefetch_loaders};
+pub use album_counts::AlbumCountLoader;
+pub use background::BACKGROUND;
+pub use connection::{connect_with_timeout, connection_failure_message};
+pub use cover_load::{
+    CoverArtMessage, CoverLoadHandle, CoverLoadSettings, spawn_cover_art_loader,
+    spawn_prefetch_loader, spawn_prefetch_loaders,
+};
+pub use library_load::load_initial_library;
 pub use mloop::AppMainLoop;
+pub use queue_snapshot::QueueSnapshot;
+pub use sleep_timer::SleepTimer;
@@ -0,0 +1,121 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use std::time::Instant;
+
+use crate::App;
+use crate::app::audio::AudioSink;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_select_popup;
+use crate::app::{Config, MessageType, StatusMessage};
+
+/// Sinks listed in the output sink overlay and the selected entry
+#[derive(Debug, Clone)]
+pub struct SinkMenu {
+    pub sinks: Vec<AudioSink>,
+    pub selected: usize,
+}
+
+impl App {
+    /// Open the output sink overlay with the sinks PipeWire currently offers
+    pub async fn open_sink_popup(&mut self) {
+        #[cfg(target_os = "linux")]
+        let result = crate::app::audio::pipewire::list_sinks_async().await;
+        #[cfg(not(target_os = "linux"))]
+        let result: Result<Vec<AudioSink>, String> =
+            Err("Output switching needs PipeWire (Linux only)".to_string());
+
+        match result {
+            Ok(sinks) => {
+                self.push_overlay(SinkMenu { sinks, selected: 0 });
+            }
+            Err(e) => {
+                log::error!("Failed to list output sinks: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Could not list outputs".to_string(),
+                    created_at: Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+
+    /// Move MPD's stream to `sink`, or back to the default sink when `None`
+    pub async fn set_output_sink(&mut self, sink: Option<AudioSink>) {
+        let label = sink
+            .as_ref()
+            .map_or("default output".to_string(), |s| s.description.clone());
+
+        #[cfg(target_os = "linux")]
+        let result = crate::app::audio::pipewire::move_mpd_to_sink_async(sink.clone()).await;
+        #[cfg(not(target_os = "linux"))]
+        let result: Result<usize, String> =
+            Err("Output switching needs PipeWire (Linux only)".to_string());
+
+        let message = match result {
+            Ok(_) => {
+                self.sink_moved = sink.is_some();
+                StatusMessage {
+                    text: format!("Output: {}", label),
+                    created_at: Instant::now(),
+                    message_type: MessageType::Success,
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to move MPD to {}: {}", label, e);
+                StatusMessage {
+                    text: "Could not switch output".to_string(),
+                    created_at: Instant::now(),
+                    message_type: MessageType::Error,
+                }
+            }
+        };
+        self.set_status_message(message);
+    }
+
+    /// Hand MPD's stream back to the default sink if it was moved, so the
+    /// override doesn't outlive the session
+    pub async fn reset_output_sink(&mut self) {
+        if self.sink_moved {
+            log::debug!("Resetting MPD output sink on exit");
+            self.set_output_sink(None).await;
+        }
+    }
+}
+
+impl Overlay for SinkMenu {
+    /// Entry 0 returns to the default sink, the rest map to the listed sinks
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        let entries = self.sinks.len() + 1;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + entries - 1) % entries;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % entries,
+            KeyCode::Enter => {
+                let sink = self
+                    .selected
+                    .checked_sub(1)
+                    .and_then(|i| self.sinks.get(i).cloned());
+                return OverlayKey::CloseAndRun(OverlayCommand::SetOutputSink(sink));
+            }
+            KeyCode::Char('q') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        let entries: Vec<String> = std::iter::once("Default".to_string())
+            .chain(self.sinks.iter().map(|sink| sink.description.clone()))
+            .collect();
+
+        render_select_popup(
+            frame,
+            " Output ",
+            &entries,
+            self.selected,
+            "Enter: switch  Esc: close",
+            config,
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::hash::{BuildHasher, RandomState};
+use std::path::PathBuf;
+
+use mpd_client::{Client, commands};
+
+use crate::App;
+use crate::app::config::mpd::{AddPosition, QueueEnd};
+use crate::app::{MessageType, PlayState, StatusMessage};
+
+impl App {
+    /// Apply the configured end of queue behavior when the last track just finished.
+    /// Call after every status update so a stop on the last track is seen as it happens.
+    pub async fn check_queue_end(&mut self, client: &Client) {
+        let Some(ref status) = self.mpd_status else {
+            return;
+        };
+
+        // MPD forgets the current song when the queue runs out, a user stop keeps it
+        let ended = self.playing_last_track
+            && status.state == PlayState::Stopped
+            && status.current_song.is_none()
+            && (status.playlist_length > 0 || status.consume);
+        self.playing_last_track = status.state == PlayState::Playing
+            && !status.repeat
+            && status
+                .current_song
+                .is_some_and(|(position, _)| position.0 + 1 == status.playlist_length);
+        if !ended {
+            return;
+        }
+
+        match self.config.mpd.on_queue_end() {
+            QueueEnd::Stop => {}
+            QueueEnd::Repeat if status.playlist_length > 0 => {
+                log::info!("Queue ended, playing it again");
+                if let Err(e) = client
+                    .command(commands::Play::song(commands::SongPosition(0)))
+                    .await
+                {
+                    self.report_mpd_error("Could not restart the queue", &e);
+                }
+            }
+            QueueEnd::Repeat => {}
+            QueueEnd::RandomAlbum => self.append_random_album(client).await,
+        }
+    }
+
+    /// Add a random album of a random library artist to the queue and play it
+    async fn append_random_album(&mut self, client: &Client) {
+        let Some(ref mut library) = self.library else {
+            return;
+        };
+        if library.artists.is_empty() {
+            return;
+        }
+
+        let index = random_index(library.artists.len());
+        if let Err(e) = library.load_artist(client, index).await {
+            log::warn!("Failed to load artist for a random album: {}", e);
+            return;
+        }
+        let Some(artist) = library.get_artist(index) else {
+            return;
+        };
+        if artist.albums.is_empty() {
+            return;
+        }
+        let album = &artist.albums[random_index(artist.albums.len())];
+
+        log::info!(
+            "Queue ended, appending '{}' by '{}'",
+            album.name,
+            artist.name
+        );
+        let files: Vec<PathBuf> = album
+            .tracks
+            .iter()
+            .map(|song| song.file_path.clone())
+            .collect();
+        self.enqueue_songs(client, &files, true, AddPosition::End)
+            .await;
+        self.set_status_message(StatusMessage {
+            text: format!("Queue ended, playing {} by {}", album.name, artist.name),
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Success,
+        });
+    }
+}
+
+/// Index below `len`, random enough to pick something to listen to
+fn random_index(len: usize) -> usize {
+    let seed = RandomState::new().hash_one(std::time::SystemTime::now());
+    (seed % len as u64) as usize
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use mpd_client::Client;
+use mpd_client::client::CommandError;
+
+use crate::App;
+use crate::app::constructor::{load_queue_snapshot, save_queue_snapshot};
+use crate::app::mpd_handler::{MPDAction, add_uris, command_error_message, partial_add};
+use crate::app::{MessageType, SongInfo, StatusMessage};
+
+/// Minimum time between two writes of the queue snapshot
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Songs added per command list while restoring
+const RESTORE_BATCH_SIZE: usize = 500;
+
+/// Bookkeeping for the copy of the queue kept in the state directory, so a queue
+/// MPD lost on a restart can be brought back
+#[derive(Debug, Default)]
+pub struct QueueSnapshot {
+    /// The queue changed since the snapshot was last written
+    pending: bool,
+    last_saved: Option<Instant>,
+    /// Files of the last session's queue while their restore is being offered
+    restorable: Vec<PathBuf>,
+}
+
+impl QueueSnapshot {
+    /// Note that the queue changed and the snapshot needs writing
+    pub fn mark_changed(&mut self) {
+        self.pending = true;
+    }
+
+    /// Forget the offered snapshot once restoring it was turned down
+    pub fn decline_restore(&mut self) {
+        self.restorable.clear();
+    }
+}
+
+impl App {
+    /// Offer to restore the last session's queue if MPD came back with an empty one
+    pub fn offer_queue_restore(&mut self) {
+        if !self.config.mpd.restore_queue || !self.queue.is_empty() {
+            return;
+        }
+
+        let files = load_queue_snapshot();
+        if files.is_empty() {
+            return;
+        }
+        log::info!("Queue is empty, offering to restore {} songs", files.len());
+        self.queue_snapshot.restorable = files;
+        self.ask_confirmation(MPDAction::RestoreQueue);
+    }
+
+    /// Write the queue snapshot if the queue changed and the last write is long enough ago.
+    /// With `force` (on exit) the interval is ignored.
+    pub fn save_queue_snapshot_if_due(&mut self, force: bool) {
+        let snapshot = &mut self.queue_snapshot;
+        // Saving now would replace the snapshot that is being offered
+        if !snapshot.restorable.is_empty() {
+            return;
+        }
+        let due = snapshot
+            .last_saved
+            .is_none_or(|saved| saved.elapsed() >= SNAPSHOT_INTERVAL);
+        if !snapshot.pending || !(due || force) {
+            return;
+        }
+
+        snapshot.pending = false;
+        snapshot.last_saved = Some(Instant::now());
+        let files: Vec<PathBuf> = self
+            .queue
+            .iter()
+            .map(|song| song.file_path.clone())
+            .collect();
+        if let Err(e) = save_queue_snapshot(&files) {
+            log::warn!("Failed to save queue snapshot: {}", e);
+        }
+    }
+
+    /// Add the offered snapshot back to the queue
+    pub async fn restore_queue(&mut self, client: &Client) {
+        let files = std::mem::take(&mut self.queue_snapshot.restorable);
+        match add_files_batched(client, &files).await {
+            Ok(skipped) => {
+                let restored = files.len() - skipped;
+                let text = if skipped > 0 {
+                    format!(
+                        "Restored {} songs, {} are no longer in the library",
+                        restored, skipped
+                    )
+                } else {
+                    format!("Restored {} songs", restored)
+                };
+                self.set_status_message(StatusMessage {
+                    text,
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Success,
+                });
+                self.dirty.mark_queue();
+            }
+            Err(e) => self.report_mpd_error("Could not restore the queue", &e),
+        }
+    }
+}
+
+/// Append `files` to the queue in command lists of [`RESTORE_BATCH_SIZE`].
+/// Files MPD doesn't know (anymore) are skipped; returns how many were.
+async fn add_files_batched(client: &Client, files: &[PathBuf]) -> Result<usize, CommandError> {
+    let mut rest = files;
+    let mut skipped = 0;
+    while !rest.is_empty() {
+        let batch = &rest[..rest.len().min(RESTORE_BATCH_SIZE)];
+        let uris: Vec<_> = batch
+            .iter()
+            .map(|file| SongInfo::uri_for_path(file))
+            .collect();
+        match add_uris(client, &uris, None).await {
+            Ok(_) => rest = &rest[batch.len()..],
+            Err(e) => {
+                // Everything before the refused file was added, carry on after it
+                let Some((failed, _)) = partial_add(&e) else {
+                    return Err(e);
+                };
+                log::warn!(
+                    "Not restoring {:?}: {}",
+                    batch.get(failed),
+                    command_error_message(&e)
+                );
+                skipped += 1;
+                rest = &rest[(failed + 1).min(rest.len())..];
+            }
+        }
+    }
+    Ok(skipped)
+}
@@ -0,0 +1,191 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::{Client, commands, responses::PlayState};
+use ratatui::Frame;
+use std::time::{Duration, Instant};
+
+use crate::App;
+use crate::app::event_handlers::EventHandlers;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_select_popup;
+use crate::app::{Config, MessageType, StatusMessage};
+
+/// Countdown that pauses or stops playback once it runs out
+#[derive(Debug, Clone)]
+pub struct SleepTimer {
+    /// When playback should stop
+    pub deadline: Instant,
+    /// Volume before the fade-out started, restored once playback has stopped
+    pub original_volume: Option<u8>,
+    /// Last displayed remaining seconds (to redraw only when the countdown changes)
+    last_shown_secs: u64,
+}
+
+impl SleepTimer {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + duration,
+            original_volume: None,
+            last_shown_secs: duration.as_secs(),
+        }
+    }
+
+    /// Time left until the timer fires
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+}
+
+/// Presets offered in the sleep timer overlay and the selected entry
+#[derive(Debug, Clone)]
+pub struct SleepTimerMenu {
+    pub presets: Vec<u64>,
+    pub selected: usize,
+}
+
+impl App {
+    /// Open the sleep timer overlay with the configured presets
+    pub fn open_sleep_timer(&mut self) {
+        self.push_overlay(SleepTimerMenu {
+            presets: self.config.sleep_timer.presets.clone(),
+            selected: 0,
+        });
+    }
+
+    /// Start a sleep timer, or cancel the running one when `minutes` is None.
+    /// A timer replaced in the middle of its fade-out puts the volume back first.
+    pub async fn set_sleep_timer(&mut self, client: &Client, minutes: Option<u64>) {
+        if let Some(volume) = self
+            .sleep_timer
+            .take()
+            .and_then(|timer| timer.original_volume)
+            && let Err(e) = client.command(commands::SetVolume(volume)).await
+        {
+            log::warn!("Sleep timer failed to restore volume: {}", e);
+        }
+
+        match minutes {
+            Some(minutes) => {
+                log::info!("Sleep timer set for {} minutes", minutes);
+                let duration = Duration::from_secs(minutes.saturating_mul(60));
+                self.sleep_timer = Some(SleepTimer::new(duration));
+            }
+            None => log::info!("Sleep timer cancelled"),
+        }
+        self.dirty.mark_status();
+    }
+
+    /// Advance the sleep timer: refresh the countdown, fade out, and stop playback when done.
+    /// Called from the main loop's periodic tick, so it never blocks on its own.
+    pub async fn check_sleep_timer(&mut self, client: &Client) {
+        let Some(ref mut timer) = self.sleep_timer else {
+            return;
+        };
+
+        let remaining = timer.remaining();
+        if remaining.as_secs() != timer.last_shown_secs {
+            timer.last_shown_secs = remaining.as_secs();
+            self.dirty.mark_status();
+        }
+
+        let is_playing = self
+            .mpd_status
+            .as_ref()
+            .is_some_and(|s| s.state == PlayState::Playing);
+
+        // Fade out over the last few seconds by lowering the volume step by step
+        let fade_out = Duration::from_secs(self.config.sleep_timer.fade_out_secs);
+        if !remaining.is_zero()
+            && remaining <= fade_out
+            && is_playing
+            && let Some(ref status) = self.mpd_status
+        {
+            let original = *timer.original_volume.get_or_insert(status.volume);
+            let target = (original as f64 * remaining.as_secs_f64() / fade_out.as_secs_f64()) as u8;
+            if target < status.volume
+                && let Err(e) = client.command(commands::SetVolume(target)).await
+            {
+                log::warn!("Sleep timer failed to lower volume: {}", e);
+            }
+            return;
+        }
+
+        if !remaining.is_zero() {
+            return;
+        }
+
+        let original_volume = timer.original_volume;
+        self.sleep_timer = None;
+        log::info!("Sleep timer expired");
+
+        if is_playing {
+            let result = if self.config.sleep_timer.stops_playback() {
+                client.command(commands::Stop).await
+            } else {
+                client.command(commands::SetPause(true)).await
+            };
+            if let Err(e) = result {
+                log::error!("Sleep timer failed to stop playback: {}", e);
+            }
+        }
+
+        // Restore the volume so the next playback isn't silent
+        if let Some(volume) = original_volume
+            && let Err(e) = client.command(commands::SetVolume(volume)).await
+        {
+            log::warn!("Sleep timer failed to restore volume: {}", e);
+        }
+
+        self.dirty.mark_status();
+        self.set_status_message(StatusMessage {
+            text: "Sleep timer expired".to_string(),
+            created_at: Instant::now(),
+            message_type: MessageType::Success,
+        });
+
+        if self.config.sleep_timer.quit_on_expire {
+            self.quit();
+        }
+    }
+}
+
+impl Overlay for SleepTimerMenu {
+    /// Entry 0 cancels the timer, the rest map to the presets
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        let entries = self.presets.len() + 1;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + entries - 1) % entries;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % entries,
+            KeyCode::Enter => {
+                let minutes = self
+                    .selected
+                    .checked_sub(1)
+                    .and_then(|i| self.presets.get(i).copied());
+                return OverlayKey::CloseAndRun(OverlayCommand::SetSleepTimer(minutes));
+            }
+            KeyCode::Char('q') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        let entries: Vec<String> = std::iter::once("Off".to_string())
+            .chain(
+                self.presets
+                    .iter()
+                    .map(|minutes| format!("{} min", minutes)),
+            )
+            .collect();
+
+        render_select_popup(
+            frame,
+            " Sleep Timer ",
+            &entries,
+            self.selected,
+            "Enter: set  Esc: close",
+            config,
+        );
+    }
+}
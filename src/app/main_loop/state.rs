@@ -1,10 +1,14 @@
 use mpd_client::Client;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::app::Config;
 use crate::app::PlayState;
 use crate::app::SongInfo;
-use crate::app::main_loop::{CoverArtMessage, spawn_cover_art_loader, spawn_prefetch_loaders};
+use crate::app::main_loop::{
+    CoverArtMessage, CoverLoadHandle, CoverLoadSettings, spawn_cover_art_loader,
+    spawn_prefetch_loaders,
+};
 use crate::app::ui::Protocol;
 use crate::app::ui::cache::cover_cache::{SharedCoverCache, find_current_index};
 
@@ -12,19 +16,28 @@ use crate::app::ui::cache::cover_cache::{SharedCoverCache, find_current_index};
 use crate::app::audio::pipewire::{
     get_supported_rates, reset_sample_rate_async, set_sample_rate_async,
 };
-use crate::app::config::pipewire::resolve_bit_perfect_rate;
+use crate::app::config::pipewire::{bit_perfect_rate_warning, resolve_bit_perfect_rate};
+#[cfg(target_os = "linux")]
+use crate::app::main_loop::BACKGROUND;
 
 use tokio::sync::mpsc;
 
-/// Check if the song changed and trigger cover art loading if needed
+/// Check if the song changed and trigger cover art loading if needed, prefetching
+/// `prefetch_count` covers around it. The load for the previous song is cancelled
+/// before the new one starts, prefetches keep running.
+#[allow(clippy::too_many_arguments)]
 pub fn check_song_change(
     current_song_file: &mut Option<PathBuf>,
+    cover_load: &mut Option<CoverLoadHandle>,
     current_song: &Option<SongInfo>,
+    mpd_status: &Option<mpd_client::responses::Status>,
     queue: &[SongInfo],
     client: &Client,
     cover_tx: &mpsc::Sender<CoverArtMessage>,
     protocol: &mut Protocol,
     cache: SharedCoverCache,
+    settings: &Arc<CoverLoadSettings>,
+    prefetch_count: usize,
 ) {
     let new_song_file: Option<PathBuf> = current_song.as_ref().map(|song| song.file_path.clone());
 
@@ -37,23 +50,39 @@ pub fn check_song_change(
 
         // Clear protocol image when there's no current song
         if current_song.is_none() {
-            protocol.image = None;
+            protocol.set_cover(None, false);
         }
 
+        // Cancel the previous song's load first: a fetch it gives up on (the same album
+        // cover, say) is then picked up by the new load instead of being waited on
+        *cover_load = None;
+
         // Start loading cover art in background (uses cache internally)
-        if let Some(ref file_path) = new_song_file {
-            spawn_cover_art_loader(client, file_path.clone(), cover_tx.clone(), cache.clone());
-        }
+        *cover_load = current_song.as_ref().map(|song| {
+            spawn_cover_art_loader(client, song, cover_tx.clone(), cache.clone(), settings)
+        });
 
         // Prefetch adjacent queue items
-        let current_idx = find_current_index(queue, current_song);
-        spawn_prefetch_loaders(client, queue, current_idx, cache);
+        let current_idx = find_current_index(queue, mpd_status.as_ref(), current_song);
+        let consume = mpd_status.as_ref().is_some_and(|status| status.consume);
+        spawn_prefetch_loaders(
+            client,
+            queue,
+            current_idx,
+            prefetch_count,
+            consume,
+            cache,
+            settings,
+        );
 
         *current_song_file = new_song_file;
     }
 }
 
-/// Handle PipeWire sample rate changes based on playback state and song changes
+/// Handle PipeWire sample rate changes based on playback state and song changes.
+///
+/// Returns a warning when a newly played rate can't be matched and PipeWire
+/// will resample; pausing and resuming the same song doesn't repeat it.
 #[cfg(target_os = "linux")]
 pub fn handle_pipewire_state_change(
     config: &Config,
@@ -62,11 +91,13 @@ pub fn handle_pipewire_state_change(
     current_song: &Option<SongInfo>,
     last_play_state: &mut Option<PlayState>,
     last_sample_rate: &mut Option<u32>,
-) {
+) -> Option<String> {
     if !bit_perfect_enabled || !config.pipewire.is_available() {
-        return;
+        return None;
     }
 
+    let mut warning = None;
+
     let current_play_state = mpd_status.as_ref().map(|s| s.state);
     let current_sample_rate = current_song.as_ref().and_then(|s| s.sample_rate());
 
@@ -89,8 +120,14 @@ pub fn handle_pipewire_state_change(
                         target_rate,
                         song_rate
                     );
+                    if rate_changed {
+                        warning = bit_perfect_rate_warning(song_rate, target_rate);
+                        if let Some(ref text) = warning {
+                            log::warn!("Bit-perfect: {}", text);
+                        }
+                    }
                     // Fire-and-forget async call to avoid blocking the UI
-                    tokio::spawn(async move {
+                    BACKGROUND.spawn_to_completion(async move {
                         let _ = set_sample_rate_async(target_rate).await;
                     });
                 }
@@ -105,7 +142,7 @@ pub fn handle_pipewire_state_change(
                     last_play_state
                 );
                 // Fire-and-forget async call to avoid blocking the UI
-                tokio::spawn(async {
+                BACKGROUND.spawn_to_completion(async {
                     let _ = reset_sample_rate_async().await;
                 });
             }
@@ -114,4 +151,6 @@ pub fn handle_pipewire_state_change(
 
     *last_play_state = current_play_state;
     *last_sample_rate = current_sample_rate;
+
+    warning
 }
@@ -5,7 +5,7 @@ pub use crate::app::mpd::mpd_updates;
 use crate::app::song::{LazyLibrary, SongInfo};
 use crate::app::ui::{DirtyFlags, MenuMode, PanelFocus};
 use binds_handler::KeyBinds;
-pub use main::{App, MessageType, StatusMessage};
+pub use main::{App, MessageType, StatusMessage, Toast};
 use mpd_client::responses::PlayState;
 use ratatui::widgets::ListState;
 use std::cell::Cell;
@@ -2,13 +2,14 @@ use crate::app::Config;
 use crate::logging::log_mpd_command;
 use mpd_client::{
     client::CommandError,
-    commands,
+    commands::{self, Command as _, SongId},
+    protocol::{Command as RawCommand, CommandList as RawCommandList},
     responses::{PlayState, Status},
 };
 use std::fmt;
 
 /// Actions that can be performed on MPD
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MPDAction {
     // Playback
     TogglePlayPause,
@@ -20,6 +21,8 @@ pub enum MPDAction {
     Repeat,
     Single,
     Consume,
+    /// Step the repeat button: off, repeat all, repeat one
+    CycleRepeat,
 
     // Volume
     VolumeUp,
@@ -45,12 +48,15 @@ pub enum MPDAction {
 
     // Application
     Quit,
+    /// Back out of the current state (count, filter, panel), quitting when there is none
+    Back,
     Refresh,
 
     // Menu mode
     SwitchToQueueMenu,
     SwitchToArtists,
     SwitchToAlbums,
+    SwitchToFiles,
 
     // Panel focus
     SwitchPanelLeft,
@@ -71,6 +77,8 @@ pub enum MPDAction {
     // Scrolling
     ScrollUp,
     ScrollDown,
+    PageUp,
+    PageDown,
 
     // Jump to top/bottom
     GoToTop,
@@ -78,6 +86,77 @@ pub enum MPDAction {
 
     // PipeWire bit-perfect mode
     ToggleBitPerfect,
+
+    // Stop after the current song
+    StopAfterCurrent,
+
+    // Sleep timer
+    OpenSleepTimer,
+
+    // Queue maintenance
+    DeduplicateQueue,
+    CropQueue,
+    ShuffleQueue,
+    /// Remove every queued track of the selected entry's album
+    RemoveAlbumFromQueue,
+
+    // Absolute volume input
+    EnterVolume,
+
+    // Library
+    AddAndPlay,
+    AddToQueueAlternate,
+
+    // Output
+    OpenOutputSinks,
+
+    // Track info
+    ShowTrackInfo,
+
+    // Search
+    SearchDatabase,
+
+    // MPD settings overlay
+    ShowMpdSettings,
+
+    // Library statistics overlay
+    ShowLibraryStats,
+
+    // Clipboard
+    CopySongInfo,
+
+    // Help
+    ShowHelp,
+
+    // Library grouping
+    ToggleGrouping,
+
+    // Now playing view
+    ToggleNowPlaying,
+
+    // Partitions
+    ShowPartitions,
+
+    // Context actions
+    ShowActions,
+    /// Jump to the artist of the selected queue entry (or the action menu's song)
+    GoToArtist,
+    /// Jump to the album of the selected queue entry (or the action menu's song)
+    GoToAlbum,
+    /// Select the playing song in the queue
+    GoToCurrent,
+
+    // Queue snapshot, only offered at startup
+    RestoreQueue,
+
+    // Large folder from the Files view, only run once confirmed
+    AddFolder,
+
+    /// Clear the queue, add the selected album and play its first track
+    ReplaceQueueWithAlbum,
+
+    // Queue filter
+    FilterQueue,
 }
 
 impl fmt::Display for MPDAction {
@@ -90,6 +169,7 @@ impl fmt::Display for MPDAction {
             MPDAction::Repeat => write!(f, "Repeat"),
             MPDAction::Single => write!(f, "Single"),
             MPDAction::Consume => write!(f, "Consume"),
+            MPDAction::CycleRepeat => write!(f, "CycleRepeat"),
             MPDAction::VolumeUp => write!(f, "VolumeUp"),
             MPDAction::VolumeUpFine => write!(f, "VolumeUpFine"),
             MPDAction::VolumeDown => write!(f, "VolumeDown"),
@@ -109,6 +189,7 @@ impl fmt::Display for MPDAction {
             MPDAction::SwitchToQueueMenu => write!(f, "SwitchToQueueMenu"),
             MPDAction::SwitchToArtists => write!(f, "SwitchToArtists"),
             MPDAction::SwitchToAlbums => write!(f, "SwitchToAlbums"),
+            MPDAction::SwitchToFiles => write!(f, "SwitchToFiles"),
             MPDAction::SwitchPanelLeft => write!(f, "SwitchPanelLeft"),
             MPDAction::SwitchPanelRight => write!(f, "SwitchPanelRight"),
             MPDAction::NavigateUp => write!(f, "NavigateUp"),
@@ -122,11 +203,72 @@ impl fmt::Display for MPDAction {
             MPDAction::GoToTop => write!(f, "GoToTop"),
             MPDAction::GoToBottom => write!(f, "GoToBottom"),
             MPDAction::ToggleBitPerfect => write!(f, "ToggleBitPerfect"),
+            MPDAction::StopAfterCurrent => write!(f, "StopAfterCurrent"),
+            MPDAction::OpenSleepTimer => write!(f, "OpenSleepTimer"),
+            MPDAction::DeduplicateQueue => write!(f, "DeduplicateQueue"),
+            MPDAction::CropQueue => write!(f, "CropQueue"),
+            MPDAction::ShuffleQueue => write!(f, "ShuffleQueue"),
+            MPDAction::EnterVolume => write!(f, "EnterVolume"),
+            MPDAction::AddAndPlay => write!(f, "AddAndPlay"),
+            MPDAction::OpenOutputSinks => write!(f, "OpenOutputSinks"),
+            MPDAction::ShowTrackInfo => write!(f, "ShowTrackInfo"),
+            MPDAction::SearchDatabase => write!(f, "SearchDatabase"),
+            MPDAction::AddToQueueAlternate => write!(f, "AddToQueueAlternate"),
+            MPDAction::ShowMpdSettings => write!(f, "ShowMpdSettings"),
+            MPDAction::ShowLibraryStats => write!(f, "ShowLibraryStats"),
+            MPDAction::CopySongInfo => write!(f, "CopySongInfo"),
+            MPDAction::ShowHelp => write!(f, "ShowHelp"),
+            MPDAction::PageUp => write!(f, "PageUp"),
+            MPDAction::PageDown => write!(f, "PageDown"),
+            MPDAction::ToggleGrouping => write!(f, "ToggleGrouping"),
+            MPDAction::ToggleNowPlaying => write!(f, "ToggleNowPlaying"),
+            MPDAction::ShowPartitions => write!(f, "ShowPartitions"),
+            MPDAction::ShowActions => write!(f, "ShowActions"),
+            MPDAction::GoToArtist => write!(f, "GoToArtist"),
+            MPDAction::GoToAlbum => write!(f, "GoToAlbum"),
+            MPDAction::GoToCurrent => write!(f, "GoToCurrent"),
+            MPDAction::RestoreQueue => write!(f, "RestoreQueue"),
+            MPDAction::AddFolder => write!(f, "AddFolder"),
+            MPDAction::ReplaceQueueWithAlbum => write!(f, "ReplaceQueueWithAlbum"),
+            MPDAction::RemoveAlbumFromQueue => write!(f, "RemoveAlbumFromQueue"),
+            MPDAction::FilterQueue => write!(f, "FilterQueue"),
+            MPDAction::Back => write!(f, "Back"),
         }
     }
 }
 
 impl MPDAction {
+    /// Question shown in the confirmation overlay before running this action
+    pub fn confirmation_prompt(&self) -> &'static str {
+        match self {
+            MPDAction::ClearQueue => "Clear the whole queue?",
+            MPDAction::CropQueue => "Remove all songs except the current one?",
+            MPDAction::RestoreQueue => "The queue is empty. Restore the last session's queue?",
+            MPDAction::ReplaceQueueWithAlbum => "Replace the queue with this album?",
+            _ => "Are you sure?",
+        }
+    }
+
+    /// Returns true if a count typed before the key repeats this action
+    pub fn accepts_count(&self) -> bool {
+        matches!(
+            self,
+            MPDAction::QueueUp
+                | MPDAction::QueueDown
+                | MPDAction::NavigateUp
+                | MPDAction::NavigateDown
+        )
+    }
+
+    /// Returns true if a count prefix changes what this action does
+    pub fn uses_count(&self) -> bool {
+        self.accepts_count()
+            || matches!(
+                self,
+                MPDAction::GoToTop | MPDAction::GoToBottom | MPDAction::Back
+            )
+    }
+
     /// Returns true if this action sends commands to MPD
     fn is_mpd_command(&self) -> bool {
         matches!(
@@ -138,14 +280,15 @@ impl MPDAction {
                 | MPDAction::Repeat
                 | MPDAction::Single
                 | MPDAction::Consume
+                | MPDAction::CycleRepeat
                 | MPDAction::VolumeUp
                 | MPDAction::VolumeUpFine
                 | MPDAction::VolumeDown
                 | MPDAction::VolumeDownFine
-                | MPDAction::ToggleMute
                 | MPDAction::SeekForward
                 | MPDAction::SeekBackward
                 | MPDAction::ClearQueue
+                | MPDAction::ShuffleQueue
         )
     }
 
@@ -198,60 +341,50 @@ impl MPDAction {
                 client.command(commands::Next).await?;
             }
             MPDAction::Previous => {
-                client.command(commands::Previous).await?;
-            }
-            MPDAction::VolumeUp => {
-                let increment = config.mpd.volume_increment as u8;
-                // Use cached status if available
-                let current_volume = if let Some(status) = cached_status {
-                    status.volume
+                // Like most players, restart the track unless it only just started
+                let elapsed = if let Some(status) = cached_status {
+                    status.elapsed
                 } else {
-                    client.command(commands::Status).await?.volume
+                    client.command(commands::Status).await?.elapsed
                 };
-                let new_volume = std::cmp::min(100, current_volume + increment);
-                client.command(commands::SetVolume(new_volume)).await?;
-            }
-            MPDAction::VolumeUpFine => {
-                let increment = config.mpd.volume_increment_fine as u8;
-                let current_volume = if let Some(status) = cached_status {
-                    status.volume
+                let threshold = std::time::Duration::from_secs(config.mpd.previous_restart_secs);
+                if config.mpd.previous_restart_secs > 0
+                    && elapsed.is_some_and(|elapsed| elapsed > threshold)
+                {
+                    client
+                        .command(commands::Seek(commands::SeekMode::Absolute(
+                            std::time::Duration::ZERO,
+                        )))
+                        .await?;
                 } else {
-                    client.command(commands::Status).await?.volume
-                };
-                let new_volume = std::cmp::min(100, current_volume + increment);
-                client.command(commands::SetVolume(new_volume)).await?;
+                    client.command(commands::Previous).await?;
+                }
             }
-            MPDAction::VolumeDown => {
-                let increment = config.mpd.volume_increment as u8;
-                let current_volume = if let Some(status) = cached_status {
-                    status.volume
-                } else {
-                    client.command(commands::Status).await?.volume
+            MPDAction::VolumeUp
+            | MPDAction::VolumeUpFine
+            | MPDAction::VolumeDown
+            | MPDAction::VolumeDownFine => {
+                let step = match self {
+                    MPDAction::VolumeUpFine | MPDAction::VolumeDownFine => {
+                        config.mpd.volume_increment_fine
+                    }
+                    _ => config.mpd.volume_increment,
                 };
-                let new_volume = current_volume.saturating_sub(increment);
-                client.command(commands::SetVolume(new_volume)).await?;
-            }
-            MPDAction::VolumeDownFine => {
-                let increment = config.mpd.volume_increment_fine as u8;
+                // Use cached status if available
                 let current_volume = if let Some(status) = cached_status {
                     status.volume
                 } else {
                     client.command(commands::Status).await?.volume
                 };
-                let new_volume = current_volume.saturating_sub(increment);
-                client.command(commands::SetVolume(new_volume)).await?;
-            }
-            MPDAction::ToggleMute => {
-                let current_volume = if let Some(status) = cached_status {
-                    status.volume
-                } else {
-                    client.command(commands::Status).await?.volume
+                let new_volume = match self {
+                    MPDAction::VolumeUp | MPDAction::VolumeUpFine => {
+                        (current_volume as u32).saturating_add(step).min(100)
+                    }
+                    _ => (current_volume as u32).saturating_sub(step),
                 };
-                if current_volume > 0 {
-                    client.command(commands::SetVolume(0)).await?;
-                } else {
-                    client.command(commands::SetVolume(50)).await?;
-                }
+                client
+                    .command(commands::SetVolume(new_volume as u8))
+                    .await?;
             }
             MPDAction::SeekForward => {
                 client
@@ -281,6 +414,17 @@ impl MPDAction {
                 };
                 client.command(commands::SetRandom(!random)).await?;
             }
+            MPDAction::CycleRepeat => {
+                let mode = if let Some(status) = cached_status {
+                    RepeatMode::from_status(status)
+                } else {
+                    RepeatMode::from_status(&client.command(commands::Status).await?)
+                };
+                let (repeat, single) = mode.next().settings();
+                client
+                    .command_list((commands::SetRepeat(repeat), commands::SetSingle(single)))
+                    .await?;
+            }
             MPDAction::Repeat => {
                 let repeat = if let Some(status) = cached_status {
                     status.repeat
@@ -310,6 +454,9 @@ impl MPDAction {
                 };
                 client.command(commands::SetConsume(!consume)).await?;
             }
+            MPDAction::ShuffleQueue => {
+                client.command(commands::Shuffle::all()).await?;
+            }
             MPDAction::QueueUp
             | MPDAction::QueueDown
             | MPDAction::PlaySelected
@@ -320,6 +467,7 @@ impl MPDAction {
             | MPDAction::SwitchToQueueMenu
             | MPDAction::SwitchToArtists
             | MPDAction::SwitchToAlbums
+            | MPDAction::SwitchToFiles
             | MPDAction::SwitchPanelLeft
             | MPDAction::SwitchPanelRight
             | MPDAction::NavigateUp
@@ -332,10 +480,410 @@ impl MPDAction {
             | MPDAction::ScrollDown
             | MPDAction::GoToTop
             | MPDAction::GoToBottom
-            | MPDAction::ToggleBitPerfect => {
+            | MPDAction::ToggleBitPerfect
+            | MPDAction::OpenSleepTimer
+            | MPDAction::DeduplicateQueue
+            | MPDAction::CropQueue
+            | MPDAction::EnterVolume
+            | MPDAction::AddAndPlay
+            | MPDAction::OpenOutputSinks
+            | MPDAction::ShowTrackInfo
+            | MPDAction::SearchDatabase
+            | MPDAction::AddToQueueAlternate
+            | MPDAction::ShowMpdSettings
+            | MPDAction::ShowHelp
+            | MPDAction::PageUp
+            | MPDAction::PageDown
+            | MPDAction::ToggleMute
+            | MPDAction::StopAfterCurrent
+            | MPDAction::ToggleGrouping
+            | MPDAction::ToggleNowPlaying
+            | MPDAction::ShowPartitions
+            | MPDAction::ShowActions
+            | MPDAction::GoToArtist
+            | MPDAction::GoToAlbum
+            | MPDAction::RestoreQueue
+            | MPDAction::AddFolder
+            | MPDAction::ReplaceQueueWithAlbum
+            | MPDAction::RemoveAlbumFromQueue
+            | MPDAction::FilterQueue
+            | MPDAction::Back
+            | MPDAction::ShowLibraryStats
+            | MPDAction::CopySongInfo
+            | MPDAction::GoToCurrent => {
                 // These are handled by the main application
             }
         }
         Ok(())
     }
 }
+
+/// State of the repeat button, made of MPD's repeat and single options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatMode {
+    Off,
+    /// Repeat the whole queue
+    All,
+    /// Repeat the current song (repeat with single)
+    One,
+}
+
+impl RepeatMode {
+    pub fn from_status(status: &Status) -> Self {
+        match (status.repeat, status.single) {
+            (false, _) => RepeatMode::Off,
+            (true, commands::SingleMode::Enabled) => RepeatMode::One,
+            (true, _) => RepeatMode::All,
+        }
+    }
+
+    /// The mode the repeat button goes to next
+    pub fn next(self) -> Self {
+        match self {
+            RepeatMode::Off => RepeatMode::All,
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Off,
+        }
+    }
+
+    /// MPD's repeat and single options for this mode. Leaving repeat one turns single
+    /// off as well, so playback doesn't stop after the current song.
+    fn settings(self) -> (bool, commands::SingleMode) {
+        match self {
+            RepeatMode::Off | RepeatMode::All => {
+                (self == RepeatMode::All, commands::SingleMode::Disabled)
+            }
+            RepeatMode::One => (true, commands::SingleMode::Enabled),
+        }
+    }
+}
+
+/// Short, user-facing description of a failed command
+///
+/// MPD's own message (e.g. "Bad song index") is more useful than the wrapped error text.
+pub fn command_error_message(error: &CommandError) -> String {
+    match error {
+        CommandError::ErrorResponse { error, .. } => error.message.to_string(),
+        CommandError::ConnectionClosed => "lost the connection to MPD".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Add the song at `uri` to the queue at queue index `position`, or at the end for None.
+/// Returns the id of the new queue entry.
+///
+/// MPD command: addid
+#[allow(dead_code)]
+pub async fn add_uri(
+    client: &mpd_client::Client,
+    uri: &str,
+    position: Option<usize>,
+) -> Result<SongId, CommandError> {
+    let result = client.command(add_command(uri, position)).await;
+    let description = format!("addid {}", uri);
+    match &result {
+        Ok(_) => log_mpd_command(&description, true, None),
+        Err(e) => log_mpd_command(&description, false, Some(&e.to_string())),
+    }
+    result
+}
+
+/// Add the songs at `uris` to the queue in one command list, in order from queue index
+/// `position`, or at the end for None. Returns the ids of the new queue entries.
+///
+/// When one of them fails MPD stops there: the songs before it stay added, and the
+/// error's `command_index` is the index of the failed uri (see [`partial_add`]).
+///
+/// MPD command: addid (per uri, in a command list)
+pub async fn add_uris<S: AsRef<str>>(
+    client: &mpd_client::Client,
+    uris: &[S],
+    position: Option<usize>,
+) -> Result<Vec<SongId>, CommandError> {
+    let adds = add_commands(uris, position);
+    let result = client.command_list(adds).await;
+    let description = format!("addid ({} songs)", uris.len());
+    match &result {
+        Ok(_) => log_mpd_command(&description, true, None),
+        Err(e) => log_mpd_command(&description, false, Some(&e.to_string())),
+    }
+    result
+}
+
+/// For an [`add_uris`] error, the index of the uri MPD refused and the id of the first
+/// song it added before that. None when nothing tells how far the adds got (the
+/// connection was lost).
+pub fn partial_add(error: &CommandError) -> Option<(usize, Option<SongId>)> {
+    let CommandError::ErrorResponse {
+        error,
+        succesful_frames,
+    } = error
+    else {
+        return None;
+    };
+    let first_id = succesful_frames
+        .first()
+        .and_then(|frame| frame.find("Id"))
+        .and_then(|id| id.parse().ok())
+        .map(SongId);
+    Some((error.command_index as usize, first_id))
+}
+
+fn add_command(uri: &str, position: Option<usize>) -> commands::Add<'_> {
+    match position {
+        Some(position) => commands::Add::uri(uri).at(position),
+        None => commands::Add::uri(uri),
+    }
+}
+
+/// One add per uri, numbered on from `position` so the songs keep their order
+fn add_commands<S: AsRef<str>>(uris: &[S], position: Option<usize>) -> Vec<commands::Add<'_>> {
+    uris.iter()
+        .enumerate()
+        .map(|(i, uri)| add_command(uri.as_ref(), position.map(|start| start + i)))
+        .collect()
+}
+
+/// Replace the queue with `uris` and play the first of them. Everything goes out as one
+/// command list, so clients never see the queue empty in between.
+///
+/// MPD commands: clear, addid (per song), play
+pub async fn replace_queue<S: AsRef<str>>(
+    client: &mpd_client::Client,
+    uris: &[S],
+) -> Result<(), CommandError> {
+    let mut list = RawCommandList::new(commands::ClearQueue.command());
+    list.extend(add_commands(uris, None).iter().map(|add| add.command()));
+    list.add(commands::Play::song(commands::SongPosition(0)).command());
+    let result = client.raw_command_list(list).await.map(|_| ());
+    let description = format!("clear, addid ({} songs), play", uris.len());
+    match &result {
+        Ok(()) => log_mpd_command(&description, true, None),
+        Err(e) => log_mpd_command(&description, false, Some(&e.to_string())),
+    }
+    result
+}
+
+/// Add the directory `path` of the music directory with everything below it, inserted
+/// at `position` or appended. MPD walks the directory itself, so this is one command
+/// however many songs it holds.
+///
+/// MPD command: add (addid only takes single songs)
+pub async fn add_directory(
+    client: &mpd_client::Client,
+    path: &str,
+    position: Option<usize>,
+) -> Result<(), CommandError> {
+    let mut command = RawCommand::new("add").argument(path);
+    if let Some(position) = position {
+        command = command.argument(position.to_string());
+    }
+    let result = client.raw_command(command).await.map(|_| ());
+    let description = format!("add {}", path);
+    match &result {
+        Ok(()) => log_mpd_command(&description, true, None),
+        Err(e) => log_mpd_command(&description, false, Some(&e.to_string())),
+    }
+    result
+}
+
+/// Number of songs in the directory `path` of the music directory and below it
+///
+/// MPD command: count with a base filter (mpd_client's filters only compare tags)
+pub async fn count_directory(client: &mpd_client::Client, path: &str) -> Result<u64, CommandError> {
+    let frame = client
+        .raw_command(RawCommand::new("count").argument(base_filter(path)))
+        .await?;
+    Ok(frame
+        .fields()
+        .find(|(key, _)| *key == "songs")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0))
+}
+
+/// Filter expression matching everything below the directory `path`, with the value
+/// escaped for the filter (the command argument is escaped again when sent)
+fn base_filter(path: &str) -> String {
+    format!(
+        "(base \"{}\")",
+        path.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Names of the server's partitions (MPD 0.22 and newer)
+pub async fn list_partitions(client: &mpd_client::Client) -> Result<Vec<String>, CommandError> {
+    let frame = client
+        .raw_command(RawCommand::new("listpartitions"))
+        .await?;
+    Ok(frame
+        .fields()
+        .filter(|(key, _)| *key == "partition")
+        .map(|(_, value)| value.to_string())
+        .collect())
+}
+
+/// Raw `lsinfo` response for the directory `path` of the music directory ("" is the root)
+///
+/// The fields are kept in order, so directories and songs can be told apart by their keys.
+pub async fn list_directory(
+    client: &mpd_client::Client,
+    path: &str,
+) -> Result<Vec<(String, String)>, CommandError> {
+    // Empty arguments are rejected, the root is listed without one
+    let mut command = RawCommand::new("lsinfo");
+    if !path.is_empty() {
+        command = command.argument(path);
+    }
+    let frame = client.raw_command(command).await?;
+    Ok(frame
+        .fields()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+/// Move this client to the partition `name`
+///
+/// Everything after this, including the idle events, refers to the new partition.
+pub async fn switch_partition(client: &mpd_client::Client, name: &str) -> Result<(), CommandError> {
+    let result = client
+        .raw_command(RawCommand::new("partition").argument(name))
+        .await
+        .map(|_| ());
+    match &result {
+        Ok(()) => log_mpd_command(&format!("partition {}", name), true, None),
+        Err(e) => log_mpd_command(&format!("partition {}", name), false, Some(&e.to_string())),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpd_client::Client;
+    use mpd_client::commands::Command;
+    use std::io::{BufRead, BufReader, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// Client talking to a stand-in server that answers each command (or command list)
+    /// with the next of `replies`. The commands it got are collected in the returned list.
+    async fn fake_mpd(replies: &'static [&'static str]) -> (Client, Arc<Mutex<Vec<String>>>) {
+        let (server, client) = std::os::unix::net::UnixStream::pair().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        std::thread::spawn(move || {
+            let mut writer = server.try_clone().unwrap();
+            writer.write_all(b"OK MPD 0.23.5\n").unwrap();
+            let mut replies = replies.iter();
+            let mut lines = BufReader::new(server).lines();
+            while let Some(Ok(line)) = lines.next() {
+                match line.as_str() {
+                    // The client idles between commands and cancels that before each one
+                    "idle" => continue,
+                    "noidle" => {
+                        writer.write_all(b"OK\n").unwrap();
+                        continue;
+                    }
+                    "command_list_begin" | "command_list_ok_begin" => {
+                        for line in lines.by_ref().map_while(Result::ok) {
+                            if line == "command_list_end" {
+                                break;
+                            }
+                            log.lock().unwrap().push(line);
+                        }
+                    }
+                    _ => log.lock().unwrap().push(line),
+                }
+                let Some(reply) = replies.next() else {
+                    return;
+                };
+                writer.write_all(reply.as_bytes()).unwrap();
+            }
+        });
+
+        client.set_nonblocking(true).unwrap();
+        let client = tokio::net::UnixStream::from_std(client).unwrap();
+        let (client, _events) = Client::connect(client).await.unwrap();
+        (client, received)
+    }
+
+    #[tokio::test]
+    async fn test_add_uri_returns_the_new_id() {
+        let (client, received) = fake_mpd(&["Id: 7\nOK\n"]).await;
+        let id = add_uri(&client, "a.flac", Some(2)).await.unwrap();
+        assert_eq!(id, SongId(7));
+        assert_eq!(*received.lock().unwrap(), vec!["addid a.flac 2"]);
+    }
+
+    #[tokio::test]
+    async fn test_partial_add_of_a_refused_first_uri() {
+        let (client, _) = fake_mpd(&["ACK [50@0] {addid} No such song\n"]).await;
+        let error = add_uris(&client, &["gone.flac", "b.flac"], None)
+            .await
+            .unwrap_err();
+        assert_eq!(partial_add(&error), Some((0, None)));
+    }
+
+    #[tokio::test]
+    async fn test_partial_add_keeps_the_id_added_before_the_refusal() {
+        let (client, _) = fake_mpd(&["Id: 7\nlist_OK\nACK [50@1] {addid} No such song\n"]).await;
+        let error = add_uris(&client, &["a.flac", "gone.flac", "c.flac"], None)
+            .await
+            .unwrap_err();
+        assert_eq!(partial_add(&error), Some((1, Some(SongId(7)))));
+    }
+
+    #[test]
+    fn test_partial_add_knows_nothing_after_a_lost_connection() {
+        assert_eq!(partial_add(&CommandError::ConnectionClosed), None);
+    }
+
+    #[test]
+    fn test_add_commands_keep_order_from_position() {
+        let adds = add_commands(&["a.flac", "b.flac"], Some(3));
+        let raw: Vec<RawCommand> = adds.iter().map(Command::command).collect();
+        assert_eq!(
+            raw,
+            vec![
+                RawCommand::new("addid")
+                    .argument("a.flac")
+                    .argument(commands::SongPosition(3)),
+                RawCommand::new("addid")
+                    .argument("b.flac")
+                    .argument(commands::SongPosition(4)),
+            ]
+        );
+
+        let appended = add_commands(&["a.flac"], None);
+        assert_eq!(
+            appended[0].command(),
+            RawCommand::new("addid").argument("a.flac")
+        );
+    }
+    #[test]
+    fn test_repeat_button_cycles_through_modes() {
+        let mut mode = RepeatMode::Off;
+        let mut settings = Vec::new();
+        for _ in 0..3 {
+            mode = mode.next();
+            settings.push(mode.settings());
+        }
+        assert_eq!(mode, RepeatMode::Off);
+        assert_eq!(
+            settings,
+            vec![
+                (true, commands::SingleMode::Disabled),
+                (true, commands::SingleMode::Enabled),
+                (false, commands::SingleMode::Disabled),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_base_filter_escapes_quotes() {
+        assert_eq!(base_filter("Music/Album"), r#"(base "Music/Album")"#);
+        assert_eq!(
+            base_filter(r#"Music/"Live" \ 1999"#),
+            r#"(base "Music/\"Live\" \\ 1999")"#
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use mpd_client::{Client, commands};
+use mpd_client::{Client, commands, commands::SongId};
 
 use crate::App;
 use crate::app::SongInfo;
@@ -55,14 +55,24 @@ impl MPDUpdates for App {
                 status.playlist_version
             );
             let queue_songs = client.command(commands::Queue).await?;
+            let new_ids: Vec<SongId> = queue_songs.iter().map(|entry| entry.id).collect();
             self.queue = queue_songs
                 .into_iter()
-                .map(|song_in_queue| SongInfo::from_song(&song_in_queue.song))
+                .map(|song_in_queue| SongInfo::from_song(&song_in_queue.song, &self.tag_settings))
                 .collect();
-
-            // Update selected index to stay within bounds
+            self.queued
+                .rebuild(&self.queue, self.library_group_by(), &self.tag_settings);
+
+            // Keep the cursor on the same song when the queue was changed (possibly
+            // by another client), then keep the selected index within bounds
+            let selected =
+                follow_selection(&self.queue_ids, &new_ids, self.queue_list_state.selected());
+            self.queue_list_state.select(selected);
+            self.queue_ids = new_ids;
             self.update_queue_selection();
             self.last_playlist_version = Some(status.playlist_version);
+            self.queue_snapshot.mark_changed();
+            self.refresh_queue_filter();
 
             // Mark queue as dirty for rendering
             self.dirty.mark_queue();
@@ -77,7 +87,14 @@ impl MPDUpdates for App {
             );
             match client.command(commands::CurrentSong).await? {
                 Some(song_in_queue) => {
-                    self.current_song = Some(SongInfo::from_song(&song_in_queue.song));
+                    let mut song = SongInfo::from_song(&song_in_queue.song, &self.tag_settings);
+                    // Not every file type (or MPD build) supports comments, the gain
+                    // then just isn't shown
+                    match SongInfo::fetch_replay_gain(client, &song.file_path).await {
+                        Ok(gain) => song.replay_gain = Some(gain),
+                        Err(e) => log::debug!("Could not read ReplayGain tags: {}", e),
+                    }
+                    self.current_song = Some(song);
                 }
                 None => {
                     self.current_song = None;
@@ -91,6 +108,7 @@ impl MPDUpdates for App {
         }
 
         // Always update playback info from status
+        self.finish_stop_after_current(client, &status).await;
         self.update_from_status(status);
 
         Ok(())
@@ -99,6 +117,7 @@ impl MPDUpdates for App {
     /// Lightweight status-only update (for progress bar, volume changes, etc.)
     async fn update_status_only(&mut self, client: &Client) -> color_eyre::Result<()> {
         let status = client.command(commands::Status).await?;
+        self.finish_stop_after_current(client, &status).await;
         self.update_from_status(status);
         Ok(())
     }
@@ -139,6 +158,11 @@ impl App {
             song.update_time_info(status.elapsed, status.duration);
         }
 
+        // Another client (or the volume input) raised the volume, we are no longer muted
+        if status.volume > 0 {
+            self.muted_volume = None;
+        }
+
         // Mark status and progress as dirty
         self.dirty.mark_status();
         self.dirty.mark_progress();
@@ -146,3 +170,54 @@ impl App {
         self.mpd_status = Some(status);
     }
 }
+
+/// Position to select after the queue changed from `old_ids` to `new_ids`: where the
+/// selected song moved to, or when it was removed, where the first remaining song
+/// after it is now. Consume mode removes played songs from in front of the cursor, so
+/// keeping the position alone would skip ahead. Without anything after it, the
+/// position stays and is clamped to the queue.
+fn follow_selection(
+    old_ids: &[SongId],
+    new_ids: &[SongId],
+    selected: Option<usize>,
+) -> Option<usize> {
+    let selected = selected?;
+    old_ids
+        .get(selected..)
+        .unwrap_or_default()
+        .iter()
+        .find_map(|id| new_ids.iter().position(|new_id| new_id == id))
+        .or(Some(selected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[u64]) -> Vec<SongId> {
+        ids.iter().map(|&id| SongId(id)).collect()
+    }
+
+    #[test]
+    fn test_follow_selection_tracks_moved_song() {
+        // Another client moved the selected song (id 3) to the front
+        let selected = follow_selection(&ids(&[1, 2, 3, 4]), &ids(&[3, 1, 2, 4]), Some(2));
+        assert_eq!(selected, Some(0));
+
+        // Songs added in front of it shift the cursor along
+        let selected = follow_selection(&ids(&[1, 2]), &ids(&[7, 8, 1, 2]), Some(1));
+        assert_eq!(selected, Some(3));
+    }
+
+    #[test]
+    fn test_follow_selection_keeps_position_of_removed_song() {
+        let selected = follow_selection(&ids(&[1, 2, 3]), &ids(&[1, 3]), Some(1));
+        assert_eq!(selected, Some(1));
+
+        // Consume removed the selected song and the one before it, the cursor stays
+        // on the song that came next instead of skipping it
+        let selected = follow_selection(&ids(&[1, 2, 3, 4]), &ids(&[3, 4]), Some(1));
+        assert_eq!(selected, Some(0));
+        assert_eq!(follow_selection(&ids(&[1]), &ids(&[]), None), None);
+    }
+}
@@ -0,0 +1,228 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::navigation::Navigation;
+use crate::app::navigation::file_browser::FileEntry;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_select_popup;
+use crate::app::ui::{DisplayItem, compute_album_display_list};
+use crate::app::{Config, MenuMode, MessageType, PanelFocus, SongInfo, StatusMessage};
+
+/// Actions offered for the selected item and the song they act on
+#[derive(Debug, Clone)]
+pub struct ActionMenu {
+    pub actions: Vec<MPDAction>,
+    pub labels: Vec<String>,
+    pub selected: usize,
+    /// Song the info and "go to" entries refer to (the album's first track for albums)
+    pub target: SongInfo,
+}
+
+/// What the selection in the current view points at
+enum Selection {
+    Song(SongInfo),
+    Album(SongInfo),
+}
+
+impl App {
+    /// Open the action menu for the item selected in the current view
+    pub fn open_action_menu(&mut self) {
+        let Some(selection) = self.menu_selection() else {
+            self.set_status_message(StatusMessage {
+                text: "No actions for this item".to_string(),
+                created_at: std::time::Instant::now(),
+                message_type: MessageType::Error,
+            });
+            return;
+        };
+
+        let actions = match (&self.menu_mode, &selection) {
+            (MenuMode::Queue, _) => vec![
+                MPDAction::PlaySelected,
+                MPDAction::ShowTrackInfo,
+                MPDAction::GoToArtist,
+                MPDAction::GoToAlbum,
+                MPDAction::RemoveFromQueue,
+                MPDAction::RemoveAlbumFromQueue,
+            ],
+            (MenuMode::Artists, Selection::Song(_)) => vec![
+                MPDAction::AddSongToQueue,
+                MPDAction::AddAndPlay,
+                MPDAction::AddToQueueAlternate,
+                MPDAction::ShowTrackInfo,
+                MPDAction::GoToAlbum,
+            ],
+            (MenuMode::Artists, Selection::Album(_)) => vec![
+                MPDAction::AddSongToQueue,
+                MPDAction::AddAndPlay,
+                MPDAction::AddToQueueAlternate,
+                MPDAction::ReplaceQueueWithAlbum,
+                MPDAction::GoToAlbum,
+                MPDAction::RemoveAlbumFromQueue,
+            ],
+            (MenuMode::Albums, Selection::Song(_)) => vec![
+                MPDAction::AddSongToQueue,
+                MPDAction::AddAndPlay,
+                MPDAction::AddToQueueAlternate,
+                MPDAction::ShowTrackInfo,
+                MPDAction::GoToArtist,
+            ],
+            (MenuMode::Albums, Selection::Album(_)) => vec![
+                MPDAction::AddSongToQueue,
+                MPDAction::AddAndPlay,
+                MPDAction::AddToQueueAlternate,
+                MPDAction::ReplaceQueueWithAlbum,
+                MPDAction::GoToArtist,
+                MPDAction::RemoveAlbumFromQueue,
+            ],
+            (MenuMode::Files, _) => vec![
+                MPDAction::AddSongToQueue,
+                MPDAction::AddAndPlay,
+                MPDAction::AddToQueueAlternate,
+                MPDAction::ShowTrackInfo,
+                MPDAction::GoToArtist,
+                MPDAction::GoToAlbum,
+            ],
+        };
+
+        let is_album = matches!(selection, Selection::Album(_));
+        let labels = actions
+            .iter()
+            .map(|action| action_label(action, is_album).to_string())
+            .collect();
+        let target = match selection {
+            Selection::Song(song) | Selection::Album(song) => song,
+        };
+
+        self.push_overlay(ActionMenu {
+            actions,
+            labels,
+            selected: 0,
+            target,
+        });
+    }
+
+    /// Run an entry of the action menu. Entries that act on the selection go through the
+    /// regular action handling, the rest use the menu's song.
+    pub async fn run_menu_action(&mut self, action: MPDAction, target: SongInfo, client: &Client) {
+        match action {
+            MPDAction::ShowTrackInfo => self.open_track_info_for(client, &target).await,
+            MPDAction::GoToArtist => self.go_to_artist(&target, client).await,
+            MPDAction::GoToAlbum => self.go_to_album(&target, client).await,
+            MPDAction::RemoveAlbumFromQueue => self.remove_album_of(&target, client).await,
+            action => {
+                if let Err(e) = self.handle_navigation_action(action, client).await {
+                    log::error!("Action menu entry failed: {}", e);
+                }
+            }
+        }
+    }
+
+    /// The song or album selected in the current view, if any
+    fn menu_selection(&self) -> Option<Selection> {
+        let library = self.library.as_ref();
+        match self.menu_mode {
+            MenuMode::Queue => {
+                let index = self.queue_list_state.selected()?;
+                self.queue.get(index).cloned().map(Selection::Song)
+            }
+            MenuMode::Artists => {
+                if self.panel_focus != PanelFocus::Albums {
+                    return None;
+                }
+                let artist = library?.get_artist(self.artist_list_state.selected()?)?;
+                let (display_items, _album_indices) =
+                    compute_album_display_list(&artist, &self.expanded_albums);
+                match display_items.get(self.album_display_list_state.selected()?)? {
+                    DisplayItem::Album(album_name) => artist
+                        .albums
+                        .iter()
+                        .find(|album| &album.name == album_name)
+                        .and_then(|album| album.tracks.first().cloned())
+                        .map(Selection::Album),
+                    DisplayItem::Song(_title, _duration, file_path) => artist
+                        .albums
+                        .iter()
+                        .flat_map(|album| album.tracks.iter())
+                        .find(|song| &song.file_path == file_path)
+                        .cloned()
+                        .map(Selection::Song),
+                }
+            }
+            MenuMode::Albums => {
+                let (_, album) = library?
+                    .all_albums
+                    .get(self.all_albums_list_state.selected()?)?;
+                match self.panel_focus {
+                    PanelFocus::AlbumTracks => album
+                        .tracks
+                        .get(self.album_tracks_list_state.selected()?)
+                        .cloned()
+                        .map(Selection::Song),
+                    _ => album.tracks.first().cloned().map(Selection::Album),
+                }
+            }
+            // Directories have no song to act on
+            MenuMode::Files => match self.file_browser.selected_entry()? {
+                FileEntry::Song(song) => Some(Selection::Song(song.as_ref().clone())),
+                FileEntry::Directory(_) => None,
+            },
+        }
+    }
+}
+
+impl Overlay for ActionMenu {
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        let entries = self.actions.len().max(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + entries - 1) % entries;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % entries,
+            KeyCode::Enter => {
+                return match self.actions.get(self.selected) {
+                    Some(action) => OverlayKey::CloseAndRun(OverlayCommand::MenuAction(
+                        action.clone(),
+                        self.target.clone(),
+                    )),
+                    None => OverlayKey::Close,
+                };
+            }
+            KeyCode::Char('q') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_select_popup(
+            frame,
+            " Actions ",
+            &self.labels,
+            self.selected,
+            "Enter: run  Esc: close",
+            config,
+        );
+    }
+}
+
+/// Menu text for an action, `album` when the selection is a whole album
+fn action_label(action: &MPDAction, album: bool) -> &'static str {
+    match action {
+        MPDAction::PlaySelected => "Play",
+        MPDAction::AddSongToQueue if album => "Add album to queue",
+        MPDAction::AddSongToQueue => "Add to queue",
+        MPDAction::AddAndPlay => "Add and play",
+        MPDAction::AddToQueueAlternate => "Add (alternate position)",
+        MPDAction::ShowTrackInfo => "Show info",
+        MPDAction::GoToArtist => "Go to artist",
+        MPDAction::GoToAlbum => "Go to album",
+        MPDAction::RemoveFromQueue => "Remove from queue",
+        MPDAction::RemoveAlbumFromQueue => "Remove album from queue",
+        MPDAction::ReplaceQueueWithAlbum => "Replace queue with album",
+        _ => "",
+    }
+}
@@ -1,12 +1,20 @@
 use crate::App;
-use log::error;
-use mpd_client::{Client, commands};
+use crate::app::config::mpd::AddPosition;
+use crate::app::mpd_handler::{MPDAction, replace_queue};
+use crate::app::song::Album;
+use crate::app::ui::{DisplayItem, MenuMode, compute_album_display_list};
+use crate::app::{MessageType, SongInfo, StatusMessage};
+use mpd_client::Client;
+use std::sync::Arc;
 
 impl App {
-    /// Handle adding a specific song to queue in Albums mode (L key in songs pane)
+    /// Handle adding a specific song to queue in Albums mode (L key in songs pane).
+    /// With `play` the song starts playing right away.
     pub async fn handle_add_song_in_album_view(
         &mut self,
         client: &Client,
+        play: bool,
+        position: AddPosition,
     ) -> color_eyre::Result<()> {
         if let (Some(library), Some(selected_album_index)) =
             (&self.library, self.all_albums_list_state.selected())
@@ -16,48 +24,108 @@ impl App {
             && let Some(selected_song) = album.tracks.get(selected_track_index)
         {
             // Add the specific song to queue
-            let queue_was_empty = self.queue.is_empty();
-            if let Err(e) = client
-                .command(commands::Add::uri(
-                    selected_song.file_path.to_str().unwrap(),
-                ))
-                .await
-            {
-                error!("Error adding song to queue: {}", e);
-            } else if queue_was_empty {
-                // Start playback if queue was empty
-                if let Err(e) = client.command(commands::Play::current()).await {
-                    error!("Error starting playback: {}", e);
-                }
-            }
+            let files = vec![selected_song.file_path.clone()];
+            self.enqueue_songs(client, &files, play, position).await;
         }
         Ok(())
     }
 
-    /// Handle adding an entire album to queue in Albums mode (A/Enter key in albums pane)
+    /// Handle adding an entire album to queue in Albums mode (A/Enter key in albums pane).
+    /// With `play` the album's first track starts playing right away.
     pub async fn handle_add_album_in_album_view(
         &mut self,
         client: &Client,
+        play: bool,
+        position: AddPosition,
     ) -> color_eyre::Result<()> {
         if let (Some(library), Some(selected_album_index)) =
             (&self.library, self.all_albums_list_state.selected())
             && let Some((_, album)) = library.all_albums.get(selected_album_index)
         {
             // Add all songs from the album to queue
-            let queue_was_empty = self.queue.is_empty();
-            for song in &album.tracks {
-                if let Err(e) = client
-                    .command(commands::Add::uri(song.file_path.to_str().unwrap()))
-                    .await
-                {
-                    error!("Error adding song to queue: {}", e);
-                }
+            let files: Vec<_> = album.tracks.iter().map(|s| s.file_path.clone()).collect();
+            self.enqueue_songs(client, &files, play, position).await;
+        }
+        Ok(())
+    }
+
+    /// Album under the cursor: the selected album in the Albums view, or the selected
+    /// album (or the album of the selected song) in the Artists view
+    fn selected_album(&self) -> Option<Arc<Album>> {
+        let library = self.library.as_ref()?;
+        match self.menu_mode {
+            MenuMode::Albums => {
+                let index = self.all_albums_list_state.selected()?;
+                library
+                    .all_albums
+                    .get(index)
+                    .map(|(_, album)| album.clone())
             }
-            // Start playback if queue was empty
-            if queue_was_empty && let Err(e) = client.command(commands::Play::current()).await {
-                error!("Error starting playback: {}", e);
+            MenuMode::Artists => {
+                let artist = library.get_artist(self.artist_list_state.selected()?)?;
+                let (display_items, _album_indices) =
+                    compute_album_display_list(&artist, &self.expanded_albums);
+                let display_index = self.album_display_list_state.selected()?;
+                artist
+                    .albums
+                    .iter()
+                    .find(|album| match display_items.get(display_index) {
+                        Some(DisplayItem::Album(album_name)) => &album.name == album_name,
+                        Some(DisplayItem::Song(_title, _duration, file_path)) => {
+                            album.tracks.iter().any(|song| &song.file_path == file_path)
+                        }
+                        None => false,
+                    })
+                    .cloned()
             }
+            MenuMode::Queue | MenuMode::Files => None,
         }
-        Ok(())
+    }
+
+    /// Replace the queue with the selected album and play it from the first track.
+    /// Asks first when `[confirm]` says so and the queue holds songs from elsewhere.
+    pub async fn replace_queue_with_selected_album(&mut self, client: &Client) {
+        let Some(album) = self.selected_album() else {
+            return;
+        };
+        let other_songs_queued = self.queue.iter().any(|queued| {
+            !album
+                .tracks
+                .iter()
+                .any(|song| song.file_path == queued.file_path)
+        });
+
+        let action = MPDAction::ReplaceQueueWithAlbum;
+        if other_songs_queued && self.config.confirm.requires(&action) {
+            self.pending_album_replacement = Some(album);
+            self.ask_confirmation(action);
+        } else {
+            self.replace_queue_with_album(client, &album).await;
+        }
+    }
+
+    /// Clear the queue, add `album` and play its first track in one command list
+    pub async fn replace_queue_with_album(&mut self, client: &Client, album: &Album) {
+        let uris: Vec<_> = album
+            .tracks
+            .iter()
+            .map(|song| SongInfo::uri_for_path(&song.file_path))
+            .collect();
+        if uris.is_empty() {
+            return;
+        }
+        if let Err(e) = replace_queue(client, &uris).await {
+            self.report_mpd_error("Error replacing queue", &e);
+            return;
+        }
+
+        self.set_status_message(StatusMessage {
+            text: format!("Playing {}", album.name),
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Success,
+        });
+        self.queue_list_state.select(Some(0));
+        self.selected_queue_index = self.queue_list_state.selected();
+        self.dirty.mark_queue();
     }
 }
@@ -1,7 +1,8 @@
 use crate::App;
+use crate::app::config::mpd::AddPosition;
 use crate::app::ui::{DisplayItem, compute_album_display_list};
-use log::error;
-use mpd_client::{Client, commands};
+use mpd_client::Client;
+use std::path::PathBuf;
 
 impl App {
     /// Handle album expansion toggle
@@ -28,18 +29,9 @@ impl App {
                     }
                     DisplayItem::Song(_title, _duration, file_path) => {
                         // Add specific song to queue
-                        let queue_was_empty = self.queue.is_empty();
-                        if let Err(e) = client
-                            .command(commands::Add::uri(file_path.to_str().unwrap()))
-                            .await
-                        {
-                            error!("Error adding song to queue: {}", e);
-                        } else if queue_was_empty {
-                            // Start playback if queue was empty
-                            if let Err(e) = client.command(commands::Play::current()).await {
-                                error!("Error starting playback: {}", e);
-                            }
-                        }
+                        let files = vec![file_path.clone()];
+                        let position = self.config.mpd.add_position();
+                        self.enqueue_songs(client, &files, false, position).await;
                     }
                 }
             }
@@ -50,10 +42,13 @@ impl App {
     }
 
     /// Handle adding to queue in Artists mode - context-aware based on what's selected
-    /// If on a song, add the song; if on an album, add the album
+    /// If on a song, add the song; if on an album, add the album.
+    /// With `play` the (first) added song starts playing right away.
     pub async fn handle_add_to_queue_context_aware(
         &mut self,
         client: &Client,
+        play: bool,
+        position: AddPosition,
     ) -> color_eyre::Result<()> {
         if let (Some(library), Some(selected_artist_index)) =
             (&self.library, self.artist_list_state.selected())
@@ -63,47 +58,20 @@ impl App {
             let (display_items, _album_indices) =
                 compute_album_display_list(&selected_artist, &self.expanded_albums);
 
-            if let Some(display_item) = display_items.get(display_index) {
-                match display_item {
-                    DisplayItem::Album(album_name) => {
-                        // Add entire album to queue
-                        if let Some(album) = selected_artist
-                            .albums
-                            .iter()
-                            .find(|a| &a.name == album_name)
-                        {
-                            let queue_was_empty = self.queue.is_empty();
-                            for song in &album.tracks {
-                                if let Err(e) = client
-                                    .command(commands::Add::uri(song.file_path.to_str().unwrap()))
-                                    .await
-                                {
-                                    error!("Error adding song to queue: {}", e);
-                                }
-                            }
-                            if queue_was_empty
-                                && let Err(e) = client.command(commands::Play::current()).await
-                            {
-                                error!("Error starting playback: {}", e);
-                            }
-                        }
-                    }
-                    DisplayItem::Song(_title, _duration, file_path) => {
-                        // Add specific song to queue
-                        let queue_was_empty = self.queue.is_empty();
-                        if let Err(e) = client
-                            .command(commands::Add::uri(file_path.to_str().unwrap()))
-                            .await
-                        {
-                            error!("Error adding song to queue: {}", e);
-                        } else if queue_was_empty
-                            && let Err(e) = client.command(commands::Play::current()).await
-                        {
-                            error!("Error starting playback: {}", e);
-                        }
-                    }
-                }
-            }
+            let files: Vec<PathBuf> = match display_items.get(display_index) {
+                // Add entire album to queue
+                Some(DisplayItem::Album(album_name)) => selected_artist
+                    .albums
+                    .iter()
+                    .find(|a| &a.name == album_name)
+                    .map(|album| album.tracks.iter().map(|s| s.file_path.clone()).collect())
+                    .unwrap_or_default(),
+                // Add specific song to queue
+                Some(DisplayItem::Song(_title, _duration, file_path)) => vec![file_path.clone()],
+                None => Vec::new(),
+            };
+
+            self.enqueue_songs(client, &files, play, position).await;
         }
         Ok(())
     }
@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::App;
+use crate::app::{MessageType, SongInfo, StatusMessage};
+
+/// Clipboard commands tried in order: the program, its arguments and the environment
+/// variable that must be set for it to have a clipboard to write to
+const CLIPBOARD_COMMANDS: &[(&str, &[&str], Option<&str>)] = &[
+    ("wl-copy", &[], Some("WAYLAND_DISPLAY")),
+    ("xclip", &["-selection", "clipboard"], Some("DISPLAY")),
+    ("xsel", &["--clipboard", "--input"], Some("DISPLAY")),
+    ("pbcopy", &[], None),
+];
+
+/// Longest wait for a clipboard command to take the text, e.g. xclip without a
+/// reachable display
+const CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Fill the placeholders of `template` with `song`'s fields: {title}, {artist},
+/// {artists} (every artist joined with `separator`), {album}, {album_artist} and {file}.
+/// Filled in one pass, so braces inside the fields are copied as they are.
+fn format_song_info(template: &str, song: &SongInfo, separator: &str) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let value = match &rest[1..end] {
+            "title" => Cow::Borrowed(song.title.as_str()),
+            "artist" => Cow::Borrowed(song.artist.as_str()),
+            "artists" => song.display_artists(separator),
+            "album" => Cow::Borrowed(song.album.as_str()),
+            "album_artist" => {
+                Cow::Borrowed(song.album_artist.as_ref().unwrap_or(&song.artist).as_str())
+            }
+            "file" => song.file_path.to_string_lossy(),
+            // Not a placeholder: keep the brace and look for one after it
+            _ => {
+                text.push('{');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        text.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Write `text` to the system clipboard with the first clipboard command that works.
+/// Returns the command used, or None when there is no clipboard (e.g. a headless session).
+/// Blocks for up to [`CLIPBOARD_TIMEOUT`] per command.
+fn copy_to_clipboard(text: &str) -> Option<&'static str> {
+    CLIPBOARD_COMMANDS
+        .iter()
+        .filter(|(_, _, env)| env.is_none_or(|env| std::env::var_os(env).is_some()))
+        .find_map(|&(program, args, _)| {
+            let mut child = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            // The tools fork to serve the clipboard, so waiting only covers the hand-over
+            let written = child.stdin.take()?.write_all(text.as_bytes());
+            let success = wait_with_timeout(&mut child, CLIPBOARD_TIMEOUT);
+            match (written, success) {
+                (Ok(()), true) => Some(program),
+                _ => {
+                    log::debug!("{} could not take the clipboard text", program);
+                    None
+                }
+            }
+        })
+}
+
+/// Wait for `child` to exit successfully, killing it once `timeout` has passed
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.success(),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                log::warn!("Clipboard command did not finish, killing it");
+                let _ = child.kill();
+                let _ = child.wait();
+                return false;
+            }
+            Err(_) => return false,
+        }
+    }
+}
+
+impl App {
+    /// Copy the current song's info, formatted by `[ui] copy_format`, to the clipboard
+    pub async fn copy_song_info(&mut self) {
+        let Some(ref song) = self.current_song else {
+            self.show_toast("Nothing playing to copy".to_string());
+            return;
+        };
+        let text = format_song_info(
+            &self.config.ui.copy_format,
+            song,
+            &self.config.ui.artist_separator,
+        );
+
+        // The clipboard tools are external processes, keep them off the event loop
+        let copied = {
+            let text = text.clone();
+            tokio::task::spawn_blocking(move || copy_to_clipboard(&text))
+                .await
+                .ok()
+                .flatten()
+        };
+        match copied {
+            Some(program) => {
+                log::debug!("Copied {:?} with {}", text, program);
+                self.set_status_message(StatusMessage {
+                    text: "Copied to clipboard".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Success,
+                });
+            }
+            None => self
+                .show_toast("No clipboard available (install wl-copy, xclip or xsel)".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_song_info_placeholders() {
+        let song = SongInfo {
+            title: "Blue Monday".to_string(),
+            artist: "New Order".to_string(),
+            artists: vec!["New Order".to_string(), "Guest".to_string()],
+            album: "Substance".to_string(),
+            file_path: PathBuf::from("New Order/Substance/01 Blue Monday.flac"),
+            ..SongInfo::default()
+        };
+        assert_eq!(
+            format_song_info("{artist} - {title}", &song, ", "),
+            "New Order - Blue Monday"
+        );
+        assert_eq!(
+            format_song_info("{artists} | {album_artist} / {album}", &song, " & "),
+            "New Order & Guest | New Order / Substance"
+        );
+        assert_eq!(
+            format_song_info("{file} {unknown}", &song, ", "),
+            "New Order/Substance/01 Blue Monday.flac {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_format_song_info_leaves_braces_in_fields_alone() {
+        let song = SongInfo {
+            title: "Song {album} {file}".to_string(),
+            artist: "Artist".to_string(),
+            artists: vec!["Artist".to_string()],
+            album: "Album".to_string(),
+            file_path: PathBuf::from("song.flac"),
+            ..SongInfo::default()
+        };
+        assert_eq!(
+            format_song_info("{title} / {album} {", &song, ", "),
+            "Song {album} {file} / Album {"
+        );
+        assert_eq!(format_song_info("{{album}}", &song, ", "), "{Album}");
+    }
+}
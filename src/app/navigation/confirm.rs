@@ -0,0 +1,143 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_confirm_popup;
+use crate::app::{Config, MessageType, StatusMessage};
+
+/// Destructive action waiting for a yes/no answer in the confirmation overlay
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub action: MPDAction,
+    pub prompt: String,
+}
+
+impl App {
+    /// Run a destructive action, asking for a yes/no confirmation first
+    /// when the `[confirm]` config requires it
+    pub async fn confirm_and_run(&mut self, action: MPDAction, client: &Client) {
+        if self.config.confirm.requires(&action) {
+            self.ask_confirmation(action);
+        } else {
+            self.run_destructive_action(action, client).await;
+        }
+    }
+
+    /// Open the confirmation overlay for `action`
+    pub fn ask_confirmation(&mut self, action: MPDAction) {
+        let prompt = self.confirmation_prompt(&action);
+        self.push_overlay(Confirmation { action, prompt });
+    }
+
+    /// Question shown in the confirmation overlay for `action`
+    fn confirmation_prompt(&self, action: &MPDAction) -> String {
+        match (
+            action,
+            &self.file_browser.pending_add,
+            &self.pending_album_replacement,
+        ) {
+            (MPDAction::AddFolder, Some(add), _) => format!(
+                "Add all {} songs in {}?",
+                add.songs,
+                add.path.rsplit('/').next().unwrap_or(&add.path)
+            ),
+            (MPDAction::ReplaceQueueWithAlbum, _, Some(album)) => format!(
+                "Replace the {} queued songs with {}?",
+                self.queue.len(),
+                album.name
+            ),
+            _ => action.confirmation_prompt().to_string(),
+        }
+    }
+
+    /// Run the confirmed action, or drop what was kept around for it
+    pub async fn answer_confirmation(
+        &mut self,
+        action: MPDAction,
+        confirmed: bool,
+        client: &Client,
+    ) {
+        if confirmed {
+            log::info!("Confirmed {}", action);
+            self.run_destructive_action(action, client).await;
+            self.force_update = true;
+            return;
+        }
+
+        log::info!("Cancelled {}", action);
+        match action {
+            MPDAction::AddFolder => self.file_browser.pending_add = None,
+            MPDAction::ReplaceQueueWithAlbum => self.pending_album_replacement = None,
+            MPDAction::RestoreQueue => self.queue_snapshot.decline_restore(),
+            _ => {}
+        }
+    }
+
+    /// Run an action that may need confirmation, once it has been confirmed or
+    /// when confirmation is turned off for it
+    pub async fn run_destructive_action(&mut self, action: MPDAction, client: &Client) {
+        match action {
+            MPDAction::CropQueue => match self.crop_queue(client).await {
+                Ok(Some(removed)) => {
+                    self.set_status_message(StatusMessage {
+                        text: format!("Removed {} songs", removed),
+                        created_at: std::time::Instant::now(),
+                        message_type: MessageType::Success,
+                    });
+                    self.queue_list_state.select(Some(0));
+                    self.selected_queue_index = self.queue_list_state.selected();
+                    self.dirty.mark_queue();
+                }
+                Ok(None) => {
+                    self.set_status_message(StatusMessage {
+                        text: "Nothing is playing, queue not cropped".to_string(),
+                        created_at: std::time::Instant::now(),
+                        message_type: MessageType::Error,
+                    });
+                }
+                Err(e) => {
+                    self.report_mpd_error("Error cropping queue", &e);
+                }
+            },
+            MPDAction::RestoreQueue => self.restore_queue(client).await,
+            MPDAction::AddFolder => {
+                if let Some(add) = self.file_browser.pending_add.take() {
+                    self.add_folder(client, &add.path, add.play, add.position)
+                        .await;
+                }
+            }
+            MPDAction::ReplaceQueueWithAlbum => {
+                if let Some(album) = self.pending_album_replacement.take() {
+                    self.replace_queue_with_album(client, &album).await;
+                }
+            }
+            _ => {
+                if let Err(e) = action
+                    .execute(client, &self.config, self.mpd_status.as_ref())
+                    .await
+                {
+                    self.report_mpd_error(&format!("{} failed", action), &e);
+                }
+            }
+        }
+    }
+}
+
+impl Overlay for Confirmation {
+    /// `y`/Enter runs the action, `n`/Esc/`q` cancels it, other keys are ignored
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        let confirmed = match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => true,
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Char('q') => false,
+            _ => return OverlayKey::Ignored,
+        };
+        OverlayKey::CloseAndRun(OverlayCommand::Confirm(self.action.clone(), confirmed))
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_confirm_popup(frame, &self.prompt, config);
+    }
+}
@@ -0,0 +1,256 @@
+use mpd_client::Client;
+use mpd_client::commands::{self, SongPosition};
+use ratatui::widgets::ListState;
+
+use crate::App;
+use crate::app::SongInfo;
+use crate::app::config::mpd::AddPosition;
+use crate::app::mpd_handler::{MPDAction, add_directory, count_directory, list_directory};
+use crate::app::song::TagSettings;
+
+/// One row of the Files view
+#[derive(Debug, Clone)]
+pub enum FileEntry {
+    /// Subdirectory, by its path relative to the music directory
+    Directory(String),
+    Song(Box<SongInfo>),
+}
+
+impl FileEntry {
+    /// Last component of the entry's path, as shown in the list
+    pub fn name(&self) -> &str {
+        let path = match self {
+            FileEntry::Directory(path) => path.as_str(),
+            FileEntry::Song(song) => song.file_path.to_str().unwrap_or_default(),
+        };
+        path.rsplit('/').next().unwrap_or(path)
+    }
+}
+
+/// Directory shown in the Files view and the selection within it
+#[derive(Debug, Clone, Default)]
+pub struct FileBrowser {
+    /// Path relative to the music directory, empty for its root
+    pub path: String,
+    /// Subdirectories first, then songs, both in MPD's order
+    pub entries: Vec<FileEntry>,
+    pub list_state: ListState,
+    /// Folder add waiting for confirmation
+    pub pending_add: Option<FolderAdd>,
+}
+
+/// Adding a folder too large to add without asking
+#[derive(Debug, Clone)]
+pub struct FolderAdd {
+    pub path: String,
+    pub songs: u64,
+    pub play: bool,
+    pub position: AddPosition,
+}
+
+impl FileBrowser {
+    /// Entry under the cursor
+    pub fn selected_entry(&self) -> Option<&FileEntry> {
+        self.entries.get(self.list_state.selected()?)
+    }
+
+    /// Directory containing the shown one, or None at the root
+    fn parent_path(&self) -> Option<&str> {
+        if self.path.is_empty() {
+            return None;
+        }
+        Some(self.path.rsplit_once('/').map_or("", |(parent, _)| parent))
+    }
+}
+
+/// Split an `lsinfo` response into subdirectories and songs.
+/// Playlists stored in the music directory are left out.
+fn parse_entries(fields: &[(String, String)], tags: &TagSettings) -> Vec<FileEntry> {
+    let directories = fields
+        .iter()
+        .filter(|(key, _)| key == "directory")
+        .map(|(_, path)| FileEntry::Directory(path.clone()));
+    let songs = SongInfo::from_fields(
+        fields
+            .iter()
+            // A playlist's fields would otherwise end up on the song before it
+            .take_while(|(key, _)| key != "playlist")
+            .map(|(key, value)| (key.as_str(), value.as_str())),
+        tags,
+    )
+    .into_iter()
+    .map(|song| FileEntry::Song(Box::new(song)));
+    directories.chain(songs).collect()
+}
+
+impl App {
+    /// Show the directory `path` in the Files view, selecting the entry whose path is
+    /// `select` when it's there and the first entry otherwise
+    pub async fn open_directory(&mut self, client: &Client, path: &str, select: Option<&str>) {
+        let fields = match list_directory(client, path).await {
+            Ok(fields) => fields,
+            Err(e) => {
+                self.report_mpd_error("Could not list directory", &e);
+                return;
+            }
+        };
+
+        let entries = parse_entries(&fields, &self.tag_settings);
+        let selected = select
+            .and_then(|select| {
+                entries.iter().position(|entry| match entry {
+                    FileEntry::Directory(path) => path == select,
+                    FileEntry::Song(song) => song.file_path.to_str() == Some(select),
+                })
+            })
+            .or((!entries.is_empty()).then_some(0));
+
+        self.file_browser.path = path.to_string();
+        self.file_browser.entries = entries;
+        self.file_browser.list_state = ListState::default().with_selected(selected);
+        self.dirty.mark_library();
+    }
+
+    /// Reload the shown directory, keeping the selected entry selected
+    pub async fn refresh_file_browser(&mut self, client: &Client) {
+        let path = self.file_browser.path.clone();
+        let selected = self.file_browser.selected_entry().map(|entry| match entry {
+            FileEntry::Directory(path) => path.clone(),
+            FileEntry::Song(song) => song.file_path.to_string_lossy().into_owned(),
+        });
+        self.open_directory(client, &path, selected.as_deref())
+            .await;
+    }
+
+    /// Descend into the selected directory. Returns false when a song is selected.
+    pub async fn enter_selected_directory(&mut self, client: &Client) -> bool {
+        let Some(FileEntry::Directory(path)) = self.file_browser.selected_entry() else {
+            return false;
+        };
+        let path = path.clone();
+        self.open_directory(client, &path, None).await;
+        true
+    }
+
+    /// Go up to the parent directory, selecting the directory just left.
+    /// Returns false at the root of the music directory.
+    pub async fn open_parent_directory(&mut self, client: &Client) -> bool {
+        let Some(parent) = self.file_browser.parent_path() else {
+            return false;
+        };
+        let parent = parent.to_string();
+        let left = self.file_browser.path.clone();
+        self.open_directory(client, &parent, Some(&left)).await;
+        true
+    }
+
+    /// Add the selected song, or the selected directory with everything below it, to the
+    /// queue at `position`. With `play` the (first) added song starts playing.
+    pub async fn add_selected_entry(&mut self, client: &Client, play: bool, position: AddPosition) {
+        let Some(entry) = self.file_browser.selected_entry().cloned() else {
+            return;
+        };
+        let path = match entry {
+            FileEntry::Song(song) => {
+                self.enqueue_songs(client, &[song.file_path], play, position)
+                    .await;
+                return;
+            }
+            FileEntry::Directory(path) => path,
+        };
+
+        // A whole box set is easy to add by accident, ask first above the threshold
+        let threshold = self.config.confirm.add_folder_threshold as u64;
+        if threshold > 0 {
+            match count_directory(client, &path).await {
+                Ok(songs) if songs > threshold => {
+                    self.file_browser.pending_add = Some(FolderAdd {
+                        path,
+                        songs,
+                        play,
+                        position,
+                    });
+                    self.ask_confirmation(MPDAction::AddFolder);
+                    return;
+                }
+                Ok(_) => {}
+                // Better to add without asking than not at all
+                Err(e) => log::warn!("Could not count songs in {}: {}", path, e),
+            }
+        }
+        self.add_folder(client, &path, play, position).await;
+    }
+
+    /// Add the folder `path` with everything below it in a single command, inserted at
+    /// `position`. With `play` its first song starts playing.
+    pub async fn add_folder(
+        &mut self,
+        client: &Client,
+        path: &str,
+        play: bool,
+        position: AddPosition,
+    ) {
+        let queue_was_empty = self.queue.is_empty();
+        let insert_at = self.queue_insert_position(position);
+        if let Err(e) = add_directory(client, path, insert_at).await {
+            self.report_mpd_error("Error adding directory to queue", &e);
+            return;
+        }
+
+        let first = insert_at.unwrap_or(self.queue.len());
+        let result = if play {
+            client
+                .command(commands::Play::song(SongPosition(first)))
+                .await
+        } else if queue_was_empty {
+            client.command(commands::Play::current()).await
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            self.report_mpd_error("Error starting playback", &e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let fields = fields(&[
+            ("directory", "Music/Album"),
+            ("Last-Modified", "2024-01-01T00:00:00Z"),
+            ("file", "Music/one.flac"),
+            ("Title", "One"),
+            ("file", "Music/two.flac"),
+            ("playlist", "Music/list.m3u"),
+            ("Last-Modified", "2024-01-01T00:00:00Z"),
+        ]);
+        let entries = parse_entries(&fields, &TagSettings::default());
+
+        assert_eq!(entries.len(), 3);
+        assert!(matches!(&entries[0], FileEntry::Directory(path) if path == "Music/Album"));
+        assert!(matches!(&entries[1], FileEntry::Song(song) if song.title == "One"));
+        assert_eq!(entries[0].name(), "Album");
+        assert_eq!(entries[2].name(), "two.flac");
+    }
+
+    #[test]
+    fn test_parent_path() {
+        let mut browser = FileBrowser::default();
+        assert_eq!(browser.parent_path(), None);
+        browser.path = "Music".to_string();
+        assert_eq!(browser.parent_path(), Some(""));
+        browser.path = "Music/Album".to_string();
+        assert_eq!(browser.parent_path(), Some("Music"));
+    }
+}
@@ -0,0 +1,110 @@
+use mpd_client::Client;
+
+use crate::App;
+use crate::app::ui::cache::cover_cache::find_current_index;
+use crate::app::ui::{DisplayItem, compute_album_display_list};
+use crate::app::{MessageType, PanelFocus, SongInfo, StatusMessage};
+
+impl App {
+    /// Select the playing song in the queue. Uses MPD's position of it rather than
+    /// searching by file, which stays right when consume mode has shifted the queue
+    /// or the same file is queued twice. A queue filter hiding it is dropped.
+    pub fn select_current_in_queue(&mut self) {
+        let Some(index) =
+            find_current_index(&self.queue, self.mpd_status.as_ref(), &self.current_song)
+        else {
+            self.show_toast("Nothing playing".to_string());
+            return;
+        };
+
+        if self
+            .queue_filter
+            .as_ref()
+            .is_some_and(|filter| !filter.matches.contains(&index))
+        {
+            self.clear_queue_filter();
+        }
+        self.queue_list_state.select(Some(index));
+        self.selected_queue_index = Some(index);
+        self.dirty.mark_queue_selection();
+    }
+
+    /// Switch to the Artists view with the artist of `song` selected
+    pub async fn go_to_artist(&mut self, song: &SongInfo, client: &Client) {
+        let Some(index) = self.find_library_artist(song, client).await else {
+            self.report_not_in_library("Artist");
+            return;
+        };
+
+        self.show_library_artist(index);
+        self.panel_focus = PanelFocus::Artists;
+        self.album_list_state.select(None);
+        self.album_display_list_state.select(None);
+        self.dirty.mark_library();
+    }
+
+    /// Switch to the Artists view with the album of `song` expanded and selected
+    pub async fn go_to_album(&mut self, song: &SongInfo, client: &Client) {
+        let Some(index) = self.find_library_artist(song, client).await else {
+            self.report_not_in_library("Artist");
+            return;
+        };
+
+        self.show_library_artist(index);
+        self.album_list_state.select(None);
+        self.album_display_list_state.select(None);
+        self.dirty.mark_library();
+
+        let Some(artist) = self
+            .library
+            .as_ref()
+            .and_then(|library| library.get_artist(index))
+        else {
+            return;
+        };
+        let Some(album_index) = artist.albums.iter().position(|a| a.name == song.album) else {
+            // Tags changed since the file was queued, the artist is the closest match
+            self.panel_focus = PanelFocus::Artists;
+            self.report_not_in_library("Album");
+            return;
+        };
+
+        self.expanded_albums
+            .insert((artist.name.clone(), song.album.clone()));
+        let (display_items, _album_indices) =
+            compute_album_display_list(&artist, &self.expanded_albums);
+        let display_index = display_items
+            .iter()
+            .position(|item| matches!(item, DisplayItem::Album(name) if *name == song.album));
+        self.album_list_state.select(Some(album_index));
+        self.album_display_list_state.select(display_index);
+        self.panel_focus = PanelFocus::Albums;
+    }
+
+    /// Index of the artist `song` is listed under in the library, loading its albums
+    async fn find_library_artist(&mut self, song: &SongInfo, client: &Client) -> Option<usize> {
+        let library = self.library.as_mut()?;
+        let name = song.library_artist(library.group_by, &library.tags);
+        let index = library.artists.iter().position(|a| a.name == name)?;
+        if let Err(e) = library.load_artist(client, index).await {
+            log::warn!("Failed to load artist: {}", e);
+        }
+        Some(index)
+    }
+
+    /// Switch to the Artists view and select the artist at `index`
+    fn show_library_artist(&mut self, index: usize) {
+        self.switch_to_artists_view();
+        self.artist_list_state.select(Some(index));
+    }
+
+    /// Tell the user the song's artist or album isn't in the library, e.g. for a file
+    /// that was queued by path
+    fn report_not_in_library(&mut self, what: &str) {
+        self.set_status_message(StatusMessage {
+            text: format!("{} not found in the library", what),
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Error,
+        });
+    }
+}
@@ -0,0 +1,45 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::Config;
+use crate::app::navigation::overlay::{Overlay, OverlayKey};
+use crate::app::ui::widgets::render_help_popup;
+
+/// Keybindings listed in the help overlay and how far it is scrolled
+#[derive(Debug, Clone)]
+pub struct Help {
+    pub sections: Vec<(&'static str, Vec<(String, String)>)>,
+    pub scroll: usize,
+}
+
+impl App {
+    /// Open the keybinding help overlay at the top
+    pub fn open_help(&mut self) {
+        self.push_overlay(Help {
+            sections: self.key_binds.help_sections(),
+            scroll: 0,
+        });
+    }
+}
+
+impl Overlay for Help {
+    /// Scroll keys scroll, any other key closes the overlay
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        // One title line and one spacer per section, the renderer clamps to the popup height
+        let line_count: usize = self.sections.iter().map(|(_, rows)| rows.len() + 2).sum();
+        let last = line_count.saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            _ => return OverlayKey::Close,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_help_popup(frame, &self.sections, self.scroll, config);
+    }
+}
@@ -1,18 +1,219 @@
 use crate::App;
-use mpd_client::Client;
+use crate::app::SongInfo;
+use crate::app::config::mpd::{AddPosition, GroupBy};
+use crate::app::mpd_handler::{add_uris, partial_add};
+use crate::app::song::TagSettings;
+use crate::app::{LazyLibrary, MessageType, StatusMessage};
+use crate::app::{MenuMode, PanelFocus};
+use mpd_client::{Client, client::CommandError, commands};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 impl App {
-    /// Preload all albums for Albums view and initialize selection
-    pub async fn preload_albums_for_view(&mut self, client: &Client) {
-        if let Some(ref mut library) = self.library
+    /// Start preloading all albums for Albums view in the background and initialize selection.
+    /// The task fills a copy of the library (sharing the albums loaded so far) that
+    /// only replaces it once complete, so cancelling it never leaves a half-built
+    /// album list behind.
+    pub fn preload_albums_for_view(&mut self, client: &Client) {
+        if let Some(ref library) = self.library
             && !library.all_albums_complete
+            && self.album_preload.is_none()
         {
             log::info!("Preloading all albums for Albums view...");
-            if let Err(e) = library.preload_all_albums(client).await {
-                log::warn!("Failed to preload all albums: {}", e);
+            let mut library = library.clone();
+            let client = client.clone();
+            let load_by_artist = self.config.mpd.load_by_artist();
+            self.album_preload = Some(tokio::spawn(async move {
+                if load_by_artist {
+                    library.preload_all_albums_by_artist(&client, None).await?;
+                } else {
+                    library.preload_all_albums(&client, None).await?;
+                }
+                Ok(library)
+            }));
+        }
+
+        self.select_first_album();
+    }
+
+    /// Back out of the innermost state: a typed count (`had_count`), the queue filter,
+    /// the right panel of a library view, then a subdirectory in the Files view.
+    /// Returns false when there was nothing left to back out of.
+    pub async fn go_back(&mut self, had_count: bool, client: &Client) -> bool {
+        // The count was already dropped when this key ended it
+        if had_count {
+            return true;
+        }
+        match (&self.menu_mode, &self.panel_focus) {
+            (MenuMode::Queue, _) if self.queue_filter.is_some() => self.clear_queue_filter(),
+            (MenuMode::Artists, PanelFocus::Albums) => {
+                self.panel_focus = PanelFocus::Artists;
+                self.dirty.mark_panel_focus();
+            }
+            (MenuMode::Albums, PanelFocus::AlbumTracks) => {
+                self.panel_focus = PanelFocus::AlbumList;
+                self.dirty.mark_panel_focus();
+            }
+            (MenuMode::Files, _) => return self.open_parent_directory(client).await,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Install the preloaded library once the background preload has finished
+    pub async fn check_album_preload(&mut self) {
+        let Some(handle) = self.album_preload.take_if(|handle| handle.is_finished()) else {
+            return;
+        };
+
+        match handle.await {
+            Ok(Ok(library)) => {
+                // The complete list is ordered differently, keep the selected album selected
+                let selected = self.selected_album_key();
+                self.library = Some(library);
+                if let Some((artist, album)) = selected {
+                    self.select_album(&artist, &album);
+                }
+                self.select_first_album();
+                // Every artist is loaded now, bringing its own count
+                self.cancel_album_counts();
+                self.dirty.mark_library();
             }
+            Ok(Err(e)) => log::warn!("Failed to preload all albums: {}", e),
+            Err(e) => log::warn!("Album preload task failed: {}", e),
+        }
+    }
+
+    /// Abort a running album preload, keeping the library as it was before it started
+    pub fn cancel_album_preload(&mut self) {
+        if let Some(handle) = self.album_preload.take() {
+            handle.abort();
+            log::info!("Cancelled album preload");
         }
+    }
 
+    /// Show the Artists view, restoring the panel focus it had when it was left
+    pub fn switch_to_artists_view(&mut self) {
+        // Save current panel focus before leaving
+        match self.menu_mode {
+            MenuMode::Artists => {} // Already in Artists mode
+            MenuMode::Albums => {
+                self.albums_panel_focus = self.panel_focus.clone();
+                self.cancel_album_preload();
+            }
+            MenuMode::Queue | MenuMode::Files => {}
+        }
+        self.menu_mode = MenuMode::Artists;
+        // Restore cached panel focus for Artists mode
+        self.panel_focus = self.artists_panel_focus.clone();
+        self.dirty.mark_menu_mode();
+    }
+
+    /// Show the Files view in the directory it was left in, reloaded so changes to the
+    /// music directory show up
+    pub async fn switch_to_files_view(&mut self, client: &Client) {
+        // Save current panel focus before leaving
+        match self.menu_mode {
+            MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
+            MenuMode::Albums => {
+                self.albums_panel_focus = self.panel_focus.clone();
+                self.cancel_album_preload();
+            }
+            MenuMode::Queue | MenuMode::Files => {}
+        }
+        self.menu_mode = MenuMode::Files;
+        self.dirty.mark_menu_mode();
+        self.refresh_file_browser(client).await;
+    }
+
+    /// Tag the library is grouped by: the current library's, or the configured one
+    /// before the first load
+    pub fn library_group_by(&self) -> GroupBy {
+        self.library
+            .as_ref()
+            .map_or_else(|| self.config.mpd.group_by(), |library| library.group_by)
+    }
+
+    /// Switch between grouping the library by album artist and by artist,
+    /// rebuilding it from the new tag
+    pub async fn toggle_library_grouping(&mut self, client: &Client) {
+        let group_by = self.library_group_by().toggled();
+
+        // A preload of the old library would overwrite the new one
+        self.cancel_album_preload();
+        match LazyLibrary::init(client, group_by, Arc::clone(&self.tag_settings), None).await {
+            Ok(mut library) => {
+                let has_artists = !library.artists.is_empty();
+                if has_artists && let Err(e) = library.load_artist(client, 0).await {
+                    log::warn!("Failed to load first artist after regrouping: {}", e);
+                }
+                self.library = Some(library);
+
+                // Artist indices and albums belong to the old grouping
+                self.artist_list_state.select(has_artists.then_some(0));
+                self.album_list_state.select(None);
+                self.album_display_list_state.select(None);
+                self.all_albums_list_state.select(None);
+                self.album_tracks_list_state.select(None);
+                self.expanded_albums.clear();
+                self.queued
+                    .rebuild(&self.queue, group_by, &self.tag_settings);
+                if self.menu_mode == MenuMode::Albums {
+                    self.preload_albums_for_view(client);
+                }
+                self.start_album_counts(client);
+                self.dirty.mark_library();
+
+                self.set_status_message(StatusMessage {
+                    text: match group_by {
+                        GroupBy::AlbumArtist => "Grouping by album artist".to_string(),
+                        GroupBy::Artist => "Grouping by artist".to_string(),
+                    },
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Success,
+                });
+            }
+            Err(e) => {
+                log::error!("Failed to regroup library: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Could not regroup library".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+
+    /// Artist and name of the album selected in the Albums view
+    fn selected_album_key(&self) -> Option<(String, String)> {
+        let library = self.library.as_ref()?;
+        let (artist, album) = library
+            .all_albums
+            .get(self.all_albums_list_state.selected()?)?;
+        Some((artist.clone(), album.name.clone()))
+    }
+
+    /// Select an album in the Albums view by artist and name.
+    /// Returns false if the album isn't in the album list (yet).
+    fn select_album(&mut self, artist: &str, album: &str) -> bool {
+        if let Some(ref mut library) = self.library {
+            library.ensure_albums_sorted();
+        }
+        let index = self.library.as_ref().and_then(|library| {
+            library
+                .all_albums
+                .iter()
+                .position(|(a, entry)| a == artist && entry.name == album)
+        });
+        if index.is_some() {
+            self.all_albums_list_state.select(index);
+        }
+        index.is_some()
+    }
+
+    /// Sort the album list and select its first entry if nothing is selected yet
+    fn select_first_album(&mut self) {
         if let Some(ref mut library) = self.library {
             library.ensure_albums_sorted();
         }
@@ -24,4 +225,242 @@ impl App {
             self.all_albums_list_state.select(Some(0));
         }
     }
+
+    /// Remove every queue entry whose file already appeared earlier in the queue.
+    /// Fetches the queue from MPD and deletes by song id, so entries that moved
+    /// since the last refresh are still removed correctly.
+    /// Returns the number of removed entries.
+    pub async fn deduplicate_queue(&mut self, client: &Client) -> Result<usize, CommandError> {
+        let queue = client.command(commands::Queue).await?;
+
+        let mut seen = HashSet::new();
+        let duplicates: Vec<commands::Delete> = queue
+            .iter()
+            .filter(|entry| !seen.insert(entry.song.url.as_str()))
+            .map(|entry| commands::Delete::id(entry.id))
+            .collect();
+
+        let removed = duplicates.len();
+        if removed > 0 {
+            client.command_list(duplicates).await?;
+        }
+
+        log::info!("Removed {} duplicate entries from the queue", removed);
+        Ok(removed)
+    }
+
+    /// Remove every queued track of `song`'s album and report how many went, keeping
+    /// the queue selection on the same entry (or the one after the removed tracks)
+    pub async fn remove_album_of(&mut self, song: &SongInfo, client: &Client) {
+        let Some(album) = song.album_key(&self.tag_settings) else {
+            self.set_status_message(StatusMessage {
+                text: "Song is not part of an album".to_string(),
+                created_at: std::time::Instant::now(),
+                message_type: MessageType::Error,
+            });
+            return;
+        };
+
+        match self.remove_album_from_queue(client, &album).await {
+            Ok(removed_positions) => {
+                if let Some(selected) = self.queue_list_state.selected() {
+                    let removed_before = removed_positions
+                        .iter()
+                        .filter(|&&position| position < selected)
+                        .count();
+                    let remaining = self.queue.len().saturating_sub(removed_positions.len());
+                    let selection =
+                        (remaining > 0).then(|| (selected - removed_before).min(remaining - 1));
+                    self.queue_list_state.select(selection);
+                    self.selected_queue_index = selection;
+                }
+                self.set_status_message(StatusMessage {
+                    text: format!(
+                        "Removed {} tracks of {} from the queue",
+                        removed_positions.len(),
+                        album.1
+                    ),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Success,
+                });
+                self.dirty.mark_queue();
+            }
+            Err(e) => self.report_mpd_error("Error removing album from queue", &e),
+        }
+    }
+
+    /// Remove every queue entry of the album identified by `album` (album artist, album
+    /// name), deleting by song id from the back so earlier positions stay valid.
+    /// Returns the queue positions the removed entries had.
+    pub async fn remove_album_from_queue(
+        &mut self,
+        client: &Client,
+        album: &(String, String),
+    ) -> Result<Vec<usize>, CommandError> {
+        let queue = client.command(commands::Queue).await?;
+
+        let (positions, deletions): (Vec<usize>, Vec<commands::Delete>) = queue
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, entry)| {
+                SongInfo::from_song(&entry.song, &self.tag_settings)
+                    .album_key(&self.tag_settings)
+                    .as_ref()
+                    == Some(album)
+            })
+            .map(|(position, entry)| (position, commands::Delete::id(entry.id)))
+            .unzip();
+
+        if !deletions.is_empty() {
+            client.command_list(deletions).await?;
+        }
+
+        log::info!(
+            "Removed {} entries of '{}' from the queue",
+            positions.len(),
+            album.1
+        );
+        Ok(positions)
+    }
+
+    /// Remove everything from the queue except the currently playing song.
+    /// Returns the number of removed entries, or None when nothing is playing.
+    pub async fn crop_queue(&mut self, client: &Client) -> Result<Option<usize>, CommandError> {
+        let status = client.command(commands::Status).await?;
+        let Some((_, current_id)) = status.current_song else {
+            return Ok(None);
+        };
+
+        let queue = client.command(commands::Queue).await?;
+        let deletions: Vec<commands::Delete> = queue
+            .iter()
+            .filter(|entry| entry.id != current_id)
+            .map(|entry| commands::Delete::id(entry.id))
+            .collect();
+
+        let removed = deletions.len();
+        if removed > 0 {
+            client.command_list(deletions).await?;
+        }
+
+        log::info!("Cropped queue, removed {} entries", removed);
+        Ok(Some(removed))
+    }
+
+    /// Queue index songs added at `position` go to, None to append
+    pub fn queue_insert_position(&self, position: AddPosition) -> Option<usize> {
+        let current = self
+            .mpd_status
+            .as_ref()
+            .and_then(|status| status.current_song)
+            .map(|(position, _)| position.0);
+        insert_position(position, &self.queue, current, &self.tag_settings)
+    }
+
+    /// Add songs to the queue at `position`. With `play` the first added song starts
+    /// playing right away, otherwise playback only starts when the queue was empty.
+    /// A song MPD refuses (a file moved since the last update) is reported and skipped,
+    /// the others are still added.
+    pub async fn enqueue_songs(
+        &mut self,
+        client: &Client,
+        files: &[PathBuf],
+        play: bool,
+        position: AddPosition,
+    ) {
+        let queue_was_empty = self.queue.is_empty();
+        let insert_at = self.queue_insert_position(position);
+
+        let uris: Vec<_> = files
+            .iter()
+            .map(|file| SongInfo::uri_for_path(file))
+            .collect();
+        let mut first_id = None;
+        let mut next = 0;
+        let mut skipped = 0;
+        while next < uris.len() {
+            // Skipped songs leave no gap, the rest follow the ones already added
+            let at = insert_at.map(|start| start + next - skipped);
+            match add_uris(client, &uris[next..], at).await {
+                Ok(ids) => {
+                    first_id = first_id.or(ids.first().copied());
+                    break;
+                }
+                Err(e) => {
+                    self.report_mpd_error("Error adding song to queue", &e);
+                    // MPD stops at the refused song: carry on after it
+                    let Some((failed, added_id)) = partial_add(&e) else {
+                        break;
+                    };
+                    first_id = first_id.or(added_id);
+                    next += failed + 1;
+                    skipped += 1;
+                }
+            }
+        }
+
+        let result = match first_id {
+            Some(id) if play => client.command(commands::Play::song(id)).await,
+            Some(_) if queue_was_empty => client.command(commands::Play::current()).await,
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            self.report_mpd_error("Error starting playback", &e);
+        }
+    }
+}
+
+/// Queue index songs added at `position` are inserted at, given the queue index of the
+/// current song. None appends, which is also the fallback when nothing is playing.
+fn insert_position(
+    position: AddPosition,
+    queue: &[SongInfo],
+    current: Option<usize>,
+    tags: &TagSettings,
+) -> Option<usize> {
+    let current = current.filter(|&current| current < queue.len())?;
+    match position {
+        AddPosition::End => None,
+        AddPosition::Next => Some(current + 1),
+        AddPosition::AfterCurrentAlbum => {
+            let Some(album) = queue[current].album_key(tags) else {
+                return Some(current + 1);
+            };
+            let rest_of_album = queue[current + 1..]
+                .iter()
+                .take_while(|song| song.album_key(tags).as_ref() == Some(&album))
+                .count();
+            Some(current + 1 + rest_of_album)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(album: &str) -> SongInfo {
+        SongInfo {
+            title: "Song".to_string(),
+            artist: "Artist".to_string(),
+            artists: vec!["Artist".to_string()],
+            album: album.to_string(),
+            file_path: PathBuf::from(format!("{}.flac", album)),
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_position() {
+        let queue = vec![song("A"), song("B"), song("B"), song("B"), song("C")];
+        let at =
+            |position, current| insert_position(position, &queue, current, &TagSettings::default());
+
+        assert_eq!(at(AddPosition::End, Some(1)), None);
+        assert_eq!(at(AddPosition::Next, Some(1)), Some(2));
+        assert_eq!(at(AddPosition::AfterCurrentAlbum, Some(1)), Some(4));
+        // Nothing playing: always append
+        assert_eq!(at(AddPosition::Next, None), None);
+    }
 }
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use mpd_client::commands;
+use mpd_client::responses::Stats;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::navigation::overlay::{Overlay, OverlayKey};
+use crate::app::ui::widgets::render_fields_popup;
+use crate::app::{Config, LazyLibrary, MessageType, StatusMessage};
+
+/// Totals of the database and the loaded library, shown in the library stats overlay
+#[derive(Debug, Clone)]
+pub struct LibraryStats {
+    pub fields: Vec<(String, String)>,
+}
+
+impl LibraryStats {
+    /// Query the server's totals and add the ones counted from `library`
+    pub async fn fetch(client: &Client, library: Option<&LazyLibrary>) -> color_eyre::Result<Self> {
+        let stats = client.command(commands::Stats).await?;
+        Ok(Self {
+            fields: stats_fields(&stats, library),
+        })
+    }
+}
+
+/// Rows of the overlay: MPD's totals first, then what the library adds to them
+fn stats_fields(stats: &Stats, library: Option<&LazyLibrary>) -> Vec<(String, String)> {
+    let mut fields = vec![
+        ("Artists".to_string(), stats.artists.to_string()),
+        ("Albums".to_string(), stats.albums.to_string()),
+        ("Songs".to_string(), stats.songs.to_string()),
+        ("Total time".to_string(), format_span(stats.db_playtime)),
+    ];
+
+    if let Some(library) = library {
+        fields.push((
+            "Listed artists".to_string(),
+            library.artists.len().to_string(),
+        ));
+        // Songs are only in memory once their albums are loaded
+        let partial = if library.all_albums_complete {
+            ""
+        } else {
+            " (loaded)"
+        };
+        fields.push((
+            format!("File types{}", partial),
+            file_types(library).unwrap_or_else(|| "none loaded".to_string()),
+        ));
+    }
+
+    fields.push(("Time played".to_string(), format_span(stats.playtime)));
+    fields.push(("MPD uptime".to_string(), format_span(stats.uptime)));
+    let updated = chrono::DateTime::from_timestamp(stats.db_last_update as i64, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    fields.push(("Database updated".to_string(), updated));
+    fields
+}
+
+/// Song counts by file extension over the loaded albums, most common first,
+/// e.g. "FLAC 1200, MP3 310". None when no songs are loaded.
+fn file_types(library: &LazyLibrary) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (_, album) in &library.all_albums {
+        for track in &album.tracks {
+            let extension = track
+                .file_path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_uppercase())
+                .unwrap_or_else(|| "OTHER".to_string());
+            *counts.entry(extension).or_default() += 1;
+        }
+    }
+    if counts.is_empty() {
+        return None;
+    }
+
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a_type, a_count), (b_type, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_type.cmp(b_type))
+    });
+    Some(
+        counts
+            .iter()
+            .map(|(file_type, count)| format!("{} {}", file_type, count))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Format a long stretch of time as days, hours and minutes, e.g. "3d 4h 12m"
+fn format_span(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+impl App {
+    /// Fetch the database totals and open the library stats overlay
+    pub async fn open_library_stats(&mut self, client: &Client) {
+        match LibraryStats::fetch(client, self.library.as_ref()).await {
+            Ok(stats) => self.push_overlay(stats),
+            Err(e) => {
+                log::error!("Error fetching MPD stats: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Could not read library stats".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+}
+
+impl Overlay for LibraryStats {
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('I') => OverlayKey::Close,
+            _ => OverlayKey::Ignored,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_fields_popup(frame, "Library Stats", &self.fields, config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_span() {
+        assert_eq!(format_span(Duration::from_secs(59)), "0m");
+        assert_eq!(format_span(Duration::from_secs(3 * 3600 + 5 * 60)), "3h 5m");
+        assert_eq!(
+            format_span(Duration::from_secs(2 * 86400 + 3600 + 60)),
+            "2d 1h 1m"
+        );
+    }
+}
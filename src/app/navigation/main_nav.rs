@@ -1,4 +1,3 @@
-use log::error;
 use mpd_client::{Client, commands};
 
 use crate::App;
@@ -22,6 +21,29 @@ impl Navigation for App {
         action: MPDAction,
         client: &Client,
     ) -> color_eyre::Result<()> {
+        // Switching views leaves the fullscreen now playing view
+        if self.now_playing_view
+            && matches!(
+                action,
+                MPDAction::SwitchToQueueMenu
+                    | MPDAction::SwitchToArtists
+                    | MPDAction::SwitchToAlbums
+                    | MPDAction::SwitchToFiles
+                    | MPDAction::CycleModeLeft
+                    | MPDAction::CycleModeRight
+                    | MPDAction::GoToArtist
+                    | MPDAction::GoToAlbum
+            )
+        {
+            self.now_playing_view = false;
+            self.dirty.mark_full_redraw();
+        }
+
+        // A queue filter narrows movement to its matches
+        if self.menu_mode == MenuMode::Queue && self.move_in_queue_filter(&action) {
+            return Ok(());
+        }
+
         match action {
             MPDAction::QueueUp => {
                 match self.menu_mode {
@@ -42,7 +64,7 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         // Navigation is now handled by NavigateUp/Down actions based on panel focus
                     }
-                    MenuMode::Albums => {
+                    MenuMode::Albums | MenuMode::Files => {
                         // Navigation is handled by NavigateUp/Down actions based on panel focus
                     }
                 }
@@ -65,7 +87,7 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         // Navigation is now handled by NavigateUp/Down actions based on panel focus
                     }
-                    MenuMode::Albums => {
+                    MenuMode::Albums | MenuMode::Files => {
                         // Navigation is handled by NavigateUp/Down actions based on panel focus
                     }
                 }
@@ -82,18 +104,27 @@ impl Navigation for App {
                                 .command(mpd_client::commands::Play::song(song_position))
                                 .await
                             {
-                                error!("Error playing selected song: {}", e);
+                                self.report_mpd_error("Error playing selected song", &e);
                             }
                         }
                     }
                     MenuMode::Albums => {
                         // Albums mode: add selected song to queue (AlbumTracks panel)
                         // Note: In AlbumList panel, binds.rs maps this to SwitchPanelRight
-                        self.handle_add_song_in_album_view(client).await?;
+                        let position = self.config.mpd.add_position();
+                        self.handle_add_song_in_album_view(client, false, position)
+                            .await?;
                     }
                     MenuMode::Artists => {
                         // Artists mode: handled via ToggleAlbumExpansion in binds.rs
                     }
+                    MenuMode::Files => {
+                        // Files mode: descend into a directory, add a song
+                        if !self.enter_selected_directory(client).await {
+                            let position = self.config.mpd.add_position();
+                            self.add_selected_entry(client, false, position).await;
+                        }
+                    }
                 }
             }
             MPDAction::MoveUpInQueue => {
@@ -108,13 +139,9 @@ impl Navigation for App {
                         .command(mpd_client::commands::Move::position(from_pos).to_position(to_pos))
                         .await
                     {
-                        error!("Error moving song up in queue: {}", e);
-                    } else {
-                        // Update selected index to follow the moved song
-                        self.queue_list_state.select(Some(selected - 1));
-                        self.selected_queue_index = self.queue_list_state.selected();
-                        self.dirty.mark_queue();
+                        self.report_mpd_error("Error moving song up in queue", &e);
                     }
+                    // The queue refresh follows the moved song by id
                 }
             }
             MPDAction::MoveDownInQueue => {
@@ -128,13 +155,9 @@ impl Navigation for App {
                         .command(mpd_client::commands::Move::position(from_pos).to_position(to_pos))
                         .await
                     {
-                        error!("Error moving song down in queue: {}", e);
-                    } else {
-                        // Update selected index to follow the moved song
-                        self.queue_list_state.select(Some(selected + 1));
-                        self.selected_queue_index = self.queue_list_state.selected();
-                        self.dirty.mark_queue();
+                        self.report_mpd_error("Error moving song down in queue", &e);
                     }
+                    // The queue refresh follows the moved song by id
                 }
             }
 
@@ -148,7 +171,7 @@ impl Navigation for App {
                         .command(mpd_client::commands::Delete::position(song_position))
                         .await
                     {
-                        error!("Error removing song from queue: {}", e);
+                        self.report_mpd_error("Error removing song from queue", &e);
                     } else {
                         // Update selected index to stay within bounds
                         if self.queue.is_empty() {
@@ -191,14 +214,7 @@ impl Navigation for App {
                         })
                     }
                     Err(e) => {
-                        error!("Failed to start MPD database update: {}", e);
-                        // Continue with library reload anyway
-                        //
-                        self.set_status_message(StatusMessage {
-                            text: String::new(),
-                            created_at: std::time::Instant::now(),
-                            message_type: MessageType::Error,
-                        })
+                        self.report_mpd_error("Failed to start MPD database update", &e);
                     }
                 }
             }
@@ -206,38 +222,35 @@ impl Navigation for App {
                 // Save current panel focus before leaving
                 match self.menu_mode {
                     MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
+                    MenuMode::Albums => {
+                        self.albums_panel_focus = self.panel_focus.clone();
+                        self.cancel_album_preload();
+                    }
+                    MenuMode::Queue | MenuMode::Files => {}
                 }
                 self.menu_mode = MenuMode::Queue;
                 self.dirty.mark_menu_mode();
                 // Queue mode doesn't use panel focus
             }
             MPDAction::SwitchToArtists => {
-                // Save current panel focus before leaving
-                match self.menu_mode {
-                    MenuMode::Artists => {} // Already in Artists mode
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
-                }
-                self.menu_mode = MenuMode::Artists;
-                // Restore cached panel focus for Artists mode
-                self.panel_focus = self.artists_panel_focus.clone();
-                self.dirty.mark_menu_mode();
+                self.switch_to_artists_view();
             }
             MPDAction::SwitchToAlbums => {
                 // Save current panel focus before leaving
                 match self.menu_mode {
                     MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
                     MenuMode::Albums => {} // Already in Albums mode
-                    MenuMode::Queue => {}
+                    MenuMode::Queue | MenuMode::Files => {}
                 }
                 self.menu_mode = MenuMode::Albums;
                 // Restore cached panel focus for Albums mode
                 self.panel_focus = self.albums_panel_focus.clone();
                 self.dirty.mark_menu_mode();
 
-                self.preload_albums_for_view(client).await;
+                self.preload_albums_for_view(client);
+            }
+            MPDAction::SwitchToFiles => {
+                self.switch_to_files_view(client).await;
             }
             MPDAction::SwitchPanelLeft => {
                 match self.menu_mode {
@@ -275,6 +288,9 @@ impl Navigation for App {
                             }
                         }
                     }
+                    MenuMode::Files => {
+                        self.open_parent_directory(client).await;
+                    }
                     MenuMode::Queue => {
                         // Queue mode doesn't have panels
                     }
@@ -336,6 +352,9 @@ impl Navigation for App {
                             }
                         }
                     }
+                    MenuMode::Files => {
+                        self.enter_selected_directory(client).await;
+                    }
                     MenuMode::Queue => {
                         // Queue mode doesn't have panels
                     }
@@ -345,23 +364,32 @@ impl Navigation for App {
                 self.handle_panel_navigation(action, client).await;
             }
             MPDAction::GoToTop | MPDAction::GoToBottom => {
-                self.handle_go_to_edge(action, client).await;
+                self.handle_go_to_edge(action, None, client).await;
             }
             MPDAction::ToggleAlbumExpansion => {
                 self.handle_album_toggle(client).await?;
             }
-            MPDAction::AddSongToQueue => {
+            MPDAction::AddSongToQueue | MPDAction::AddAndPlay | MPDAction::AddToQueueAlternate => {
+                // AddAndPlay also starts playing the (first) added song
+                let play = matches!(action, MPDAction::AddAndPlay);
+                // AddToQueueAlternate adds at the opposite of the configured position
+                let position = match action {
+                    MPDAction::AddToQueueAlternate => self.config.mpd.add_position().alternate(),
+                    _ => self.config.mpd.add_position(),
+                };
                 match self.menu_mode {
                     MenuMode::Albums => {
                         // Albums mode: context-aware add
                         match self.panel_focus {
                             PanelFocus::AlbumTracks => {
                                 // In tracks panel: add selected song
-                                self.handle_add_song_in_album_view(client).await?;
+                                self.handle_add_song_in_album_view(client, play, position)
+                                    .await?;
                             }
                             PanelFocus::AlbumList => {
                                 // In album list panel: add entire album
-                                self.handle_add_album_in_album_view(client).await?;
+                                self.handle_add_album_in_album_view(client, play, position)
+                                    .await?;
                             }
                             _ => {}
                         }
@@ -369,27 +397,48 @@ impl Navigation for App {
                     MenuMode::Artists => {
                         // Artists mode: context-aware based on what's selected
                         // If on a song, add the song; if on an album, add the album
-                        self.handle_add_to_queue_context_aware(client).await?;
+                        self.handle_add_to_queue_context_aware(client, play, position)
+                            .await?;
+                    }
+                    MenuMode::Files => {
+                        // Files mode: Enter opens directories unless configured to add
+                        // them, the other adds add them
+                        if !(matches!(action, MPDAction::AddAndPlay)
+                            && !self.config.ui.enter_adds_folder
+                            && self.enter_selected_directory(client).await)
+                        {
+                            self.add_selected_entry(client, play, position).await;
+                        }
                     }
                     MenuMode::Queue => {
                         // Queue mode: no action
                     }
                 }
             }
+            MPDAction::ReplaceQueueWithAlbum => {
+                self.replace_queue_with_selected_album(client).await;
+            }
             MPDAction::CycleModeLeft => {
-                // Cycle modes left: Queue -> Albums -> Artists -> Queue
+                // Cycle modes left: Queue -> Files -> Albums -> Artists -> Queue
                 // Save current panel focus before leaving
                 match self.menu_mode {
                     MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
+                    MenuMode::Albums => {
+                        self.albums_panel_focus = self.panel_focus.clone();
+                        self.cancel_album_preload();
+                    }
+                    MenuMode::Queue | MenuMode::Files => {}
                 }
                 match self.menu_mode {
                     MenuMode::Queue => {
+                        self.menu_mode = MenuMode::Files;
+                        self.refresh_file_browser(client).await;
+                    }
+                    MenuMode::Files => {
                         self.menu_mode = MenuMode::Albums;
                         self.panel_focus = self.albums_panel_focus.clone();
 
-                        self.preload_albums_for_view(client).await;
+                        self.preload_albums_for_view(client);
                     }
                     MenuMode::Artists => {
                         self.menu_mode = MenuMode::Queue;
@@ -402,12 +451,15 @@ impl Navigation for App {
                 self.dirty.mark_menu_mode();
             }
             MPDAction::CycleModeRight => {
-                // Cycle modes right: Queue -> Artists -> Albums -> Queue
+                // Cycle modes right: Queue -> Artists -> Albums -> Files -> Queue
                 // Save current panel focus before leaving
                 match self.menu_mode {
                     MenuMode::Artists => self.artists_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Albums => self.albums_panel_focus = self.panel_focus.clone(),
-                    MenuMode::Queue => {}
+                    MenuMode::Albums => {
+                        self.albums_panel_focus = self.panel_focus.clone();
+                        self.cancel_album_preload();
+                    }
+                    MenuMode::Queue | MenuMode::Files => {}
                 }
                 match self.menu_mode {
                     MenuMode::Queue => {
@@ -418,24 +470,136 @@ impl Navigation for App {
                         self.menu_mode = MenuMode::Albums;
                         self.panel_focus = self.albums_panel_focus.clone();
 
-                        self.preload_albums_for_view(client).await;
+                        self.preload_albums_for_view(client);
                     }
                     MenuMode::Albums => {
+                        self.menu_mode = MenuMode::Files;
+                        self.refresh_file_browser(client).await;
+                    }
+                    MenuMode::Files => {
                         self.menu_mode = MenuMode::Queue;
                     }
                 };
                 self.dirty.mark_menu_mode();
             }
-            MPDAction::ScrollUp | MPDAction::ScrollDown => {
+            MPDAction::ScrollUp
+            | MPDAction::ScrollDown
+            | MPDAction::PageUp
+            | MPDAction::PageDown => {
                 self.handle_scroll(action, client).await;
             }
+            MPDAction::DeduplicateQueue => match self.deduplicate_queue(client).await {
+                Ok(removed) => {
+                    self.set_status_message(StatusMessage {
+                        text: format!("Removed {} duplicates", removed),
+                        created_at: std::time::Instant::now(),
+                        message_type: MessageType::Success,
+                    });
+                    self.dirty.mark_queue();
+                }
+                Err(e) => {
+                    self.report_mpd_error("Error removing duplicates from queue", &e);
+                }
+            },
+            MPDAction::FilterQueue => {
+                if self.menu_mode == MenuMode::Queue {
+                    self.open_queue_filter();
+                }
+            }
+            MPDAction::RemoveAlbumFromQueue => {
+                if self.menu_mode == MenuMode::Queue
+                    && let Some(song) = self
+                        .queue_list_state
+                        .selected()
+                        .and_then(|index| self.queue.get(index))
+                        .cloned()
+                {
+                    self.remove_album_of(&song, client).await;
+                }
+            }
+            MPDAction::ClearQueue | MPDAction::CropQueue => {
+                self.confirm_and_run(action, client).await;
+            }
+            MPDAction::EnterVolume => {
+                self.open_volume_input();
+            }
+            MPDAction::OpenSleepTimer => {
+                self.open_sleep_timer();
+            }
+            MPDAction::OpenOutputSinks => {
+                self.open_sink_popup().await;
+            }
+            MPDAction::ShowPartitions => {
+                self.open_partition_popup(client).await;
+            }
+            MPDAction::ShowActions => {
+                self.open_action_menu();
+            }
+            MPDAction::GoToArtist | MPDAction::GoToAlbum => {
+                if self.menu_mode == MenuMode::Queue
+                    && let Some(song) = self
+                        .queue_list_state
+                        .selected()
+                        .and_then(|index| self.queue.get(index))
+                        .cloned()
+                {
+                    if matches!(action, MPDAction::GoToArtist) {
+                        self.go_to_artist(&song, client).await;
+                    } else {
+                        self.go_to_album(&song, client).await;
+                    }
+                }
+            }
+            MPDAction::GoToCurrent => {
+                if self.menu_mode == MenuMode::Queue {
+                    self.select_current_in_queue();
+                }
+            }
+            MPDAction::ToggleNowPlaying => {
+                self.now_playing_view = !self.now_playing_view;
+                // The cover changes size, so everything is drawn again
+                self.dirty.mark_full_redraw();
+                self.dirty.mark_cover_art();
+            }
+            MPDAction::ToggleGrouping => {
+                self.toggle_library_grouping(client).await;
+            }
+            MPDAction::ShowHelp => {
+                self.open_help();
+            }
+            MPDAction::ToggleMute => {
+                self.toggle_mute(client).await;
+            }
+            MPDAction::StopAfterCurrent => {
+                self.toggle_stop_after_current(client).await;
+            }
+            MPDAction::VolumeUp
+            | MPDAction::VolumeUpFine
+            | MPDAction::VolumeDown
+            | MPDAction::VolumeDownFine
+                if self.adjust_muted_volume(&action) => {}
+            MPDAction::ShowMpdSettings => {
+                self.open_mpd_settings(client).await;
+            }
+            MPDAction::ShowLibraryStats => {
+                self.open_library_stats(client).await;
+            }
+            MPDAction::ShowTrackInfo => {
+                self.open_track_info(client).await;
+            }
+            MPDAction::CopySongInfo => {
+                self.copy_song_info().await;
+            }
+            MPDAction::SearchDatabase => {
+                self.open_database_search();
+            }
             _ => {
                 // Execute MPD command for other actions, passing cached status
                 if let Err(e) = action
                     .execute(client, &self.config, self.mpd_status.as_ref())
                     .await
                 {
-                    error!("Error executing MPD command: {}", e);
+                    self.report_mpd_error(&format!("{} failed", action), &e);
                 }
             }
         }
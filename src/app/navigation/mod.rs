@@ -1,8 +1,24 @@
+pub mod action_menu;
 pub mod albums_nav;
 pub mod artists_nav;
+pub mod clipboard;
+pub mod confirm;
+pub mod file_browser;
+pub mod goto;
+pub mod help;
 pub mod helpers;
+pub mod library_stats;
 pub mod main_nav;
+pub mod mpd_settings;
+pub mod mute;
+pub mod overlay;
 pub mod panel_nav;
+pub mod partition;
+pub mod queue_filter;
 pub mod scrolling;
+pub mod search;
+pub mod stop_after;
+pub mod track_info;
+pub mod volume_input;
 
 pub use main_nav::Navigation;
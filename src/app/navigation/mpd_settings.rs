@@ -0,0 +1,101 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use mpd_client::commands::{self, ReplayGainMode};
+use mpd_client::protocol::Command as RawCommand;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::navigation::overlay::{Overlay, OverlayKey};
+use crate::app::ui::widgets::render_fields_popup;
+use crate::app::{Config, MessageType, StatusMessage};
+
+/// Server playback settings shown in the read-only MPD settings overlay
+#[derive(Debug, Clone)]
+pub struct MpdSettings {
+    pub fields: Vec<(String, String)>,
+}
+
+impl MpdSettings {
+    /// Query the server for its current playback settings
+    pub async fn fetch(client: &Client) -> color_eyre::Result<Self> {
+        let status = client.command(commands::Status).await?;
+        let replay_gain = client.command(commands::ReplayGainStatus).await?;
+
+        // The typed status response has no mixramp fields, read them from the raw one
+        let raw_status = client.raw_command(RawCommand::new("status")).await?;
+        let raw_field = |name: &str| {
+            raw_status
+                .fields()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        };
+
+        let replay_gain_mode = match replay_gain.mode {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+            ReplayGainMode::Auto => "auto",
+        };
+        let update = match status.update_job {
+            Some(job) => format!("running (job {})", job),
+            None => "idle".to_string(),
+        };
+
+        let fields = vec![
+            (
+                "Crossfade".to_string(),
+                format!("{}s", status.crossfade.as_secs()),
+            ),
+            (
+                "MixRamp threshold".to_string(),
+                raw_field("mixrampdb")
+                    .map(|db| format!("{} dB", db))
+                    .unwrap_or_else(|| "off".to_string()),
+            ),
+            (
+                "MixRamp delay".to_string(),
+                raw_field("mixrampdelay")
+                    .map(|delay| format!("{}s", delay))
+                    .unwrap_or_else(|| "off".to_string()),
+            ),
+            ("ReplayGain".to_string(), replay_gain_mode.to_string()),
+            (
+                "Consume".to_string(),
+                if status.consume { "on" } else { "off" }.to_string(),
+            ),
+            ("Database update".to_string(), update),
+        ];
+
+        Ok(Self { fields })
+    }
+}
+
+impl App {
+    /// Fetch the server's playback settings and open the MPD settings overlay
+    pub async fn open_mpd_settings(&mut self, client: &Client) {
+        match MpdSettings::fetch(client).await {
+            Ok(settings) => self.push_overlay(settings),
+            Err(e) => {
+                log::error!("Error fetching MPD settings: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Could not read MPD settings".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+}
+
+impl Overlay for MpdSettings {
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('M') => OverlayKey::Close,
+            _ => OverlayKey::Ignored,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_fields_popup(frame, "MPD Settings", &self.fields, config);
+    }
+}
@@ -0,0 +1,60 @@
+use mpd_client::{Client, commands};
+
+use crate::App;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::{MessageType, StatusMessage};
+
+/// Volume restored when unmuting a volume that was already 0 at startup
+const FALLBACK_UNMUTE_VOLUME: u8 = 50;
+
+impl App {
+    /// Mute by remembering the current volume and setting it to 0, or unmute by
+    /// restoring the remembered volume
+    pub async fn toggle_mute(&mut self, client: &Client) {
+        let current = self.mpd_status.as_ref().map_or(0, |status| status.volume);
+        let (target, muted_volume) = match self.muted_volume {
+            Some(volume) => (volume, None),
+            None if current > 0 => (0, Some(current)),
+            // Muted before we started (or by another client), nothing to restore
+            None => (FALLBACK_UNMUTE_VOLUME, None),
+        };
+
+        match client.command(commands::SetVolume(target)).await {
+            Ok(()) => {
+                self.muted_volume = muted_volume;
+                self.dirty.mark_status();
+            }
+            Err(e) => self.report_mpd_error("Error toggling mute", &e),
+        }
+    }
+
+    /// While muted, volume keys change the level restored on unmute instead of
+    /// unmuting. Returns false when not muted so the caller sets the volume as usual.
+    pub fn adjust_muted_volume(&mut self, action: &MPDAction) -> bool {
+        let Some(volume) = self.muted_volume else {
+            return false;
+        };
+
+        let step = match action {
+            MPDAction::VolumeUpFine | MPDAction::VolumeDownFine => {
+                self.config.mpd.volume_increment_fine
+            }
+            _ => self.config.mpd.volume_increment,
+        };
+        let volume = match action {
+            MPDAction::VolumeUp | MPDAction::VolumeUpFine => {
+                (volume as u32).saturating_add(step).min(100)
+            }
+            _ => (volume as u32).saturating_sub(step),
+        } as u8;
+
+        self.muted_volume = Some(volume);
+        self.set_status_message(StatusMessage {
+            text: format!("Muted, unmutes to {}%", volume),
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Success,
+        });
+        self.dirty.mark_status();
+        true
+    }
+}
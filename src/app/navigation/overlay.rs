@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::audio::AudioSink;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::navigation::search::DatabaseSearch;
+use crate::app::{Config, SongInfo};
+
+/// What an overlay did with a key press
+#[derive(Debug)]
+pub enum OverlayKey {
+    /// The overlay changed and needs redrawing
+    Handled,
+    /// The key means nothing to the overlay; it is swallowed all the same
+    /// (Esc closes the overlay instead)
+    Ignored,
+    /// The overlay is done and is popped off the stack
+    Close,
+    /// The overlay stays open and the app runs the command
+    Run(OverlayCommand),
+    /// The overlay is popped off the stack, then the app runs the command
+    CloseAndRun(OverlayCommand),
+}
+
+/// Work an overlay hands back to the app, for what needs the MPD client or app state
+#[derive(Debug)]
+pub enum OverlayCommand {
+    SetVolume(u8),
+    /// Start the sleep timer for the given minutes, or cancel it with None
+    SetSleepTimer(Option<u64>),
+    /// Move MPD's stream to the sink, or back to the default one with None
+    SetOutputSink(Option<AudioSink>),
+    SwitchPartition(String),
+    /// Run an action menu entry on the menu's song
+    MenuAction(MPDAction, SongInfo),
+    /// Answer to a confirmation: run the action or drop it
+    Confirm(MPDAction, bool),
+    /// Run the search's query; the search overlay is opened again with the results
+    Search(DatabaseSearch),
+    /// Append songs from the search results to the queue
+    AddSearchResults(Vec<PathBuf>),
+}
+
+/// Modal popup on the overlay stack: it sees every key while it is on top and is
+/// drawn over the rest of the UI
+pub trait Overlay: std::fmt::Debug {
+    /// Handle a key press. An Esc the overlay ignores pops it.
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey;
+
+    /// Draw the overlay on top of the frame
+    fn render(&self, frame: &mut Frame, config: &Config);
+}
+
+impl App {
+    /// Open `overlay` on top of the ones already open
+    pub fn push_overlay(&mut self, overlay: impl Overlay + 'static) {
+        self.overlays.push(Box::new(overlay));
+        self.dirty.mark_full_redraw();
+    }
+
+    /// Close the topmost overlay
+    pub fn pop_overlay(&mut self) {
+        if self.overlays.pop().is_some() {
+            self.dirty.mark_full_redraw();
+        }
+    }
+
+    /// Hand a key press to the topmost overlay. Returns false when no overlay is open
+    /// and the key is for the rest of the app.
+    pub async fn handle_overlay_key(&mut self, key: KeyEvent, client: &Client) -> bool {
+        let Some(overlay) = self.overlays.last_mut() else {
+            return false;
+        };
+
+        match overlay.handle_key(key) {
+            OverlayKey::Handled => self.dirty.mark_full_redraw(),
+            OverlayKey::Ignored if key.code == KeyCode::Esc => self.pop_overlay(),
+            OverlayKey::Ignored => {}
+            OverlayKey::Close => self.pop_overlay(),
+            OverlayKey::Run(command) => {
+                self.dirty.mark_full_redraw();
+                self.run_overlay_command(command, client).await;
+            }
+            OverlayKey::CloseAndRun(command) => {
+                self.pop_overlay();
+                self.run_overlay_command(command, client).await;
+            }
+        }
+        true
+    }
+
+    /// Carry out what an overlay asked for
+    async fn run_overlay_command(&mut self, command: OverlayCommand, client: &Client) {
+        match command {
+            OverlayCommand::SetVolume(volume) => self.set_volume(client, volume).await,
+            OverlayCommand::SetSleepTimer(minutes) => self.set_sleep_timer(client, minutes).await,
+            OverlayCommand::SetOutputSink(sink) => self.set_output_sink(sink).await,
+            OverlayCommand::SwitchPartition(partition) => {
+                self.enter_partition(client, &partition).await
+            }
+            OverlayCommand::MenuAction(action, target) => {
+                self.run_menu_action(action, target, client).await
+            }
+            OverlayCommand::Confirm(action, confirmed) => {
+                self.answer_confirmation(action, confirmed, client).await
+            }
+            OverlayCommand::Search(search) => self.run_database_search(client, search).await,
+            OverlayCommand::AddSearchResults(files) => {
+                self.add_search_results(client, &files).await
+            }
+        }
+    }
+}
@@ -146,6 +146,15 @@ impl App {
                             }
                         }
                     }
+                    MenuMode::Files => {
+                        let len = self.file_browser.entries.len();
+                        if len > 0 {
+                            let current = self.file_browser.list_state.selected().unwrap_or(0);
+                            // Wrap around to the bottom
+                            let new_index = if current > 0 { current - 1 } else { len - 1 };
+                            self.file_browser.list_state.select(Some(new_index));
+                        }
+                    }
                 }
             }
             MPDAction::NavigateDown => {
@@ -275,6 +284,15 @@ impl App {
                             }
                         }
                     }
+                    MenuMode::Files => {
+                        let len = self.file_browser.entries.len();
+                        if len > 0 {
+                            let current = self.file_browser.list_state.selected().unwrap_or(0);
+                            // Wrap around to the top
+                            let new_index = if current + 1 < len { current + 1 } else { 0 };
+                            self.file_browser.list_state.select(Some(new_index));
+                        }
+                    }
                 }
             }
             _ => {}
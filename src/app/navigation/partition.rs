@@ -0,0 +1,97 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::mpd_handler::{list_partitions, switch_partition};
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_select_popup;
+use crate::app::{Config, MessageType, StatusMessage};
+
+/// Partitions listed in the partition overlay and the selected one
+#[derive(Debug, Clone)]
+pub struct PartitionMenu {
+    pub partitions: Vec<String>,
+    pub selected: usize,
+}
+
+impl App {
+    /// List the server's partitions and open the partition overlay on the active one
+    pub async fn open_partition_popup(&mut self, client: &Client) {
+        match list_partitions(client).await {
+            Ok(partitions) => {
+                let active = self
+                    .mpd_status
+                    .as_ref()
+                    .and_then(|status| status.partition.as_deref());
+                let selected = partitions
+                    .iter()
+                    .position(|name| Some(name.as_str()) == active)
+                    .unwrap_or(0);
+                self.push_overlay(PartitionMenu {
+                    partitions,
+                    selected,
+                });
+            }
+            // Servers older than 0.22 don't know the command
+            Err(e) => self.report_mpd_error("Could not list partitions", &e),
+        }
+    }
+
+    /// Switch to `partition` and resync everything that belongs to a partition
+    pub async fn enter_partition(&mut self, client: &Client, partition: &str) {
+        if let Err(e) = switch_partition(client, partition).await {
+            self.report_mpd_error("Could not switch partition", &e);
+            return;
+        }
+
+        // The new partition has its own queue, player and outputs; the version
+        // counters of the old one say nothing about it
+        self.last_playlist_version = None;
+        self.last_song_id = None;
+        self.muted_volume = None;
+        self.force_update = true;
+        self.dirty.mark_queue();
+        self.dirty.mark_status();
+
+        self.set_status_message(StatusMessage {
+            text: format!("Partition: {}", partition),
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Success,
+        });
+    }
+}
+
+impl Overlay for PartitionMenu {
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        let entries = self.partitions.len().max(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + entries - 1) % entries;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % entries,
+            KeyCode::Enter => {
+                return match self.partitions.get(self.selected) {
+                    Some(partition) => {
+                        OverlayKey::CloseAndRun(OverlayCommand::SwitchPartition(partition.clone()))
+                    }
+                    None => OverlayKey::Close,
+                };
+            }
+            KeyCode::Char('q') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_select_popup(
+            frame,
+            " Partition ",
+            &self.partitions,
+            self.selected,
+            "Enter: switch  Esc: close",
+            config,
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::App;
+use crate::app::SongInfo;
+use crate::app::mpd_handler::MPDAction;
+use crate::app::navigation::search::parse_search_query;
+use crate::app::ui::LIST_HEIGHT;
+
+/// Query narrowing the queue view, and the queue indices it matches
+#[derive(Debug, Clone, Default)]
+pub struct QueueFilter {
+    pub query: String,
+    /// Whether keys go to the query line rather than the queue
+    pub editing: bool,
+    /// Queue indices of the matching songs, in queue order
+    pub matches: Vec<usize>,
+    /// Scroll offset within the matches, kept by the queue view between frames
+    pub offset: usize,
+}
+
+impl QueueFilter {
+    /// Recompute the matches of the query against `queue`
+    fn refresh(&mut self, queue: &[SongInfo]) {
+        let (tag, term) = parse_search_query(&self.query);
+        let term = term.to_lowercase();
+        self.matches = queue
+            .iter()
+            .enumerate()
+            .filter(|(_, song)| song_matches(song, tag, &term))
+            .map(|(index, _)| index)
+            .collect();
+    }
+
+    /// Lowercase term to highlight in the `tag` ("title", "artist" or "album") column,
+    /// None when the filter doesn't look at that tag or matches everything
+    pub fn highlight_term(&self, tag: &str) -> Option<String> {
+        let (filter_tag, term) = parse_search_query(&self.query);
+        (!term.is_empty() && (filter_tag == tag || filter_tag == "any"))
+            .then(|| term.to_lowercase())
+    }
+}
+
+/// Whether `song`'s `tag` ("title", "artist", "album" or "any") contains the lowercase
+/// `term`
+fn song_matches(song: &SongInfo, tag: &str, term: &str) -> bool {
+    let contains = |field: &str| field.to_lowercase().contains(term);
+    match tag {
+        "title" => contains(&song.title),
+        "artist" => song.artists.iter().any(|artist| contains(artist)),
+        "album" => contains(&song.album),
+        _ => {
+            contains(&song.title)
+                || song.artists.iter().any(|artist| contains(artist))
+                || contains(&song.album)
+        }
+    }
+}
+
+impl App {
+    /// Start typing a queue filter, or edit the current one
+    pub fn open_queue_filter(&mut self) {
+        self.queue_filter
+            .get_or_insert_with(Default::default)
+            .editing = true;
+        self.refresh_queue_filter();
+    }
+
+    /// Drop the filter and show the whole queue again
+    pub fn clear_queue_filter(&mut self) {
+        self.queue_filter = None;
+        self.dirty.mark_queue();
+    }
+
+    /// Match the filter against the current queue, e.g. after the queue changed.
+    /// Moves the selection to the first match when the selected song doesn't match.
+    pub fn refresh_queue_filter(&mut self) {
+        let Some(ref mut filter) = self.queue_filter else {
+            return;
+        };
+        filter.refresh(&self.queue);
+        filter.offset = 0;
+
+        let selected = self.queue_list_state.selected();
+        if !selected.is_some_and(|index| filter.matches.contains(&index))
+            && let Some(&first) = filter.matches.first()
+        {
+            self.queue_list_state.select(Some(first));
+            self.selected_queue_index = Some(first);
+        }
+        self.dirty.mark_queue();
+    }
+
+    /// Handle a key press while the queue filter is being typed.
+    /// Enter keeps the filter and returns to the queue, Esc drops it.
+    pub fn handle_queue_filter_key(&mut self, key: KeyEvent) {
+        let Some(ref mut filter) = self.queue_filter else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => filter.query.push(c),
+            KeyCode::Backspace => {
+                filter.query.pop();
+            }
+            KeyCode::Enter if filter.query.is_empty() => {
+                self.clear_queue_filter();
+                return;
+            }
+            KeyCode::Enter => {
+                filter.editing = false;
+                self.dirty.mark_queue();
+                return;
+            }
+            KeyCode::Esc => {
+                self.clear_queue_filter();
+                return;
+            }
+            _ => return,
+        }
+        self.refresh_queue_filter();
+    }
+
+    /// Move the queue selection between the filter's matches for the movement actions.
+    /// Returns false for actions that aren't movements, which then act as usual.
+    pub fn move_in_queue_filter(&mut self, action: &MPDAction) -> bool {
+        let Some(ref filter) = self.queue_filter else {
+            return false;
+        };
+        let len = filter.matches.len();
+        if len == 0 {
+            return matches!(
+                action,
+                MPDAction::QueueUp
+                    | MPDAction::QueueDown
+                    | MPDAction::ScrollUp
+                    | MPDAction::ScrollDown
+                    | MPDAction::PageUp
+                    | MPDAction::PageDown
+                    | MPDAction::GoToTop
+                    | MPDAction::GoToBottom
+            );
+        }
+
+        let page = LIST_HEIGHT.with(|height| height.get()).max(1);
+        let current = self
+            .queue_list_state
+            .selected()
+            .and_then(|index| filter.matches.iter().position(|&m| m >= index))
+            .unwrap_or(0);
+        let target = match action {
+            // Single steps wrap around like in the full queue
+            MPDAction::QueueUp => (current + len - 1) % len,
+            MPDAction::QueueDown => (current + 1) % len,
+            MPDAction::ScrollUp => current.saturating_sub(page / 2),
+            MPDAction::ScrollDown => (current + page / 2).min(len - 1),
+            MPDAction::PageUp => current.saturating_sub(page),
+            MPDAction::PageDown => (current + page).min(len - 1),
+            MPDAction::GoToTop => 0,
+            MPDAction::GoToBottom => len - 1,
+            _ => return false,
+        };
+
+        let index = filter.matches[target];
+        self.queue_list_state.select(Some(index));
+        self.selected_queue_index = Some(index);
+        self.dirty.mark_queue_selection();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn song(title: &str, artist: &str, album: &str) -> SongInfo {
+        SongInfo {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            artists: vec![artist.to_string()],
+            album: album.to_string(),
+            file_path: PathBuf::from(format!("{}.flac", title)),
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_keep_queue_indices() {
+        let queue = vec![
+            song("Blue Monday", "New Order", "Substance"),
+            song("Atmosphere", "Joy Division", "Substance"),
+            song("Ceremony", "New Order", "Movement"),
+        ];
+        let mut filter = QueueFilter {
+            query: "new order".to_string(),
+            ..Default::default()
+        };
+        filter.refresh(&queue);
+        assert_eq!(filter.matches, vec![0, 2]);
+
+        // A prefix limits the match to one tag, like the database search
+        filter.query = "album:movement".to_string();
+        filter.refresh(&queue);
+        assert_eq!(filter.matches, vec![2]);
+        filter.query = "title:substance".to_string();
+        filter.refresh(&queue);
+        assert!(filter.matches.is_empty());
+    }
+}
@@ -2,20 +2,26 @@ use crate::App;
 use crate::app::{
     MenuMode, PanelFocus,
     mpd_handler::MPDAction,
-    ui::{DisplayItem, compute_album_display_list},
+    ui::{DisplayItem, LIST_HEIGHT, compute_album_display_list},
 };
 use mpd_client::Client;
 
 impl App {
-    /// Handle scrolling by 15 items at a time
+    /// Handle scrolling by half a page (ScrollUp/Down) or a whole page (PageUp/Down),
+    /// sized from the height of the last rendered list
     pub async fn handle_scroll(&mut self, action: MPDAction, client: &Client) {
+        let page = LIST_HEIGHT.with(|height| height.get()).max(1);
+        let step = match action {
+            MPDAction::PageUp | MPDAction::PageDown => page,
+            _ => (page / 2).max(1),
+        };
         match self.menu_mode {
             MenuMode::Queue => {
                 if !self.queue.is_empty() {
                     let current = self.queue_list_state.selected().unwrap_or(0);
                     let new_index = match action {
-                        MPDAction::ScrollUp => {
-                            let potential = current.saturating_sub(15);
+                        MPDAction::ScrollUp | MPDAction::PageUp => {
+                            let potential = current.saturating_sub(step);
                             if potential == 0 && current == 0 {
                                 // Already at top, wrap to bottom
                                 self.queue.len().saturating_sub(1)
@@ -23,9 +29,9 @@ impl App {
                                 potential
                             }
                         }
-                        MPDAction::ScrollDown => {
+                        MPDAction::ScrollDown | MPDAction::PageDown => {
                             let potential =
-                                std::cmp::min(current + 15, self.queue.len().saturating_sub(1));
+                                std::cmp::min(current + step, self.queue.len().saturating_sub(1));
                             if potential == self.queue.len().saturating_sub(1)
                                 && current == self.queue.len().saturating_sub(1)
                             {
@@ -50,8 +56,8 @@ impl App {
                         {
                             let current = self.artist_list_state.selected().unwrap_or(0);
                             let new_index = match action {
-                                MPDAction::ScrollUp => {
-                                    let potential = current.saturating_sub(15);
+                                MPDAction::ScrollUp | MPDAction::PageUp => {
+                                    let potential = current.saturating_sub(step);
                                     if potential == 0 && current == 0 {
                                         // Already at top, wrap to bottom
                                         library.artists.len().saturating_sub(1)
@@ -59,9 +65,9 @@ impl App {
                                         potential
                                     }
                                 }
-                                MPDAction::ScrollDown => {
+                                MPDAction::ScrollDown | MPDAction::PageDown => {
                                     let potential = std::cmp::min(
-                                        current + 15,
+                                        current + step,
                                         library.artists.len().saturating_sub(1),
                                     );
                                     if potential == library.artists.len().saturating_sub(1)
@@ -99,8 +105,8 @@ impl App {
                             if !display_items.is_empty() {
                                 let current = self.album_display_list_state.selected().unwrap_or(0);
                                 let new_index = match action {
-                                    MPDAction::ScrollUp => {
-                                        let potential = current.saturating_sub(15);
+                                    MPDAction::ScrollUp | MPDAction::PageUp => {
+                                        let potential = current.saturating_sub(step);
                                         if potential == 0 && current == 0 {
                                             // Already at top, wrap to bottom
                                             display_items.len().saturating_sub(1)
@@ -108,9 +114,9 @@ impl App {
                                             potential
                                         }
                                     }
-                                    MPDAction::ScrollDown => {
+                                    MPDAction::ScrollDown | MPDAction::PageDown => {
                                         let potential = std::cmp::min(
-                                            current + 15,
+                                            current + step,
                                             display_items.len().saturating_sub(1),
                                         );
                                         if potential == display_items.len().saturating_sub(1)
@@ -157,17 +163,17 @@ impl App {
                         {
                             let current = self.all_albums_list_state.selected().unwrap_or(0);
                             let new_index = match action {
-                                MPDAction::ScrollUp => {
-                                    let potential = current.saturating_sub(15);
+                                MPDAction::ScrollUp | MPDAction::PageUp => {
+                                    let potential = current.saturating_sub(step);
                                     if potential == 0 && current == 0 {
                                         library.all_albums.len().saturating_sub(1)
                                     } else {
                                         potential
                                     }
                                 }
-                                MPDAction::ScrollDown => {
+                                MPDAction::ScrollDown | MPDAction::PageDown => {
                                     let potential = std::cmp::min(
-                                        current + 15,
+                                        current + step,
                                         library.all_albums.len().saturating_sub(1),
                                     );
                                     if potential == library.all_albums.len().saturating_sub(1)
@@ -193,17 +199,17 @@ impl App {
                         {
                             let current = self.album_tracks_list_state.selected().unwrap_or(0);
                             let new_index = match action {
-                                MPDAction::ScrollUp => {
-                                    let potential = current.saturating_sub(15);
+                                MPDAction::ScrollUp | MPDAction::PageUp => {
+                                    let potential = current.saturating_sub(step);
                                     if potential == 0 && current == 0 {
                                         album.tracks.len().saturating_sub(1)
                                     } else {
                                         potential
                                     }
                                 }
-                                MPDAction::ScrollDown => {
+                                MPDAction::ScrollDown | MPDAction::PageDown => {
                                     let potential = std::cmp::min(
-                                        current + 15,
+                                        current + step,
                                         album.tracks.len().saturating_sub(1),
                                     );
                                     if potential == album.tracks.len().saturating_sub(1)
@@ -224,23 +230,53 @@ impl App {
                     }
                 }
             }
+            MenuMode::Files => {
+                let len = self.file_browser.entries.len();
+                if len > 0 {
+                    let last = len - 1;
+                    let current = self.file_browser.list_state.selected().unwrap_or(0);
+                    let new_index = match action {
+                        MPDAction::ScrollUp | MPDAction::PageUp => {
+                            // Already at top, wrap to bottom
+                            if current == 0 {
+                                last
+                            } else {
+                                current.saturating_sub(step)
+                            }
+                        }
+                        MPDAction::ScrollDown | MPDAction::PageDown => {
+                            // Already at bottom, wrap to top
+                            if current == last {
+                                0
+                            } else {
+                                (current + step).min(last)
+                            }
+                        }
+                        _ => current,
+                    };
+                    self.file_browser.list_state.select(Some(new_index));
+                }
+            }
         }
         // Mark appropriate dirty flags for scrolling
         match self.menu_mode {
             MenuMode::Queue => self.dirty.mark_queue_selection(),
-            MenuMode::Artists | MenuMode::Albums => self.dirty.mark_library(),
+            MenuMode::Artists | MenuMode::Albums | MenuMode::Files => self.dirty.mark_library(),
         }
     }
 
     /// Handle jumping to the top or bottom of the current list
-    pub async fn handle_go_to_edge(&mut self, action: MPDAction, client: &Client) {
+    pub async fn handle_go_to_edge(
+        &mut self,
+        action: MPDAction,
+        count: Option<usize>,
+        client: &Client,
+    ) {
         match self.menu_mode {
             MenuMode::Queue => {
                 if !self.queue.is_empty() {
-                    let new_index = match action {
-                        MPDAction::GoToTop => 0,
-                        MPDAction::GoToBottom => self.queue.len().saturating_sub(1),
-                        _ => return,
+                    let Some(new_index) = edge_index(&action, count, self.queue.len()) else {
+                        return;
                     };
                     self.queue_list_state.select(Some(new_index));
                     self.selected_queue_index = self.queue_list_state.selected();
@@ -252,10 +288,9 @@ impl App {
                         if let Some(ref library) = self.library
                             && !library.artists.is_empty()
                         {
-                            let new_index = match action {
-                                MPDAction::GoToTop => 0,
-                                MPDAction::GoToBottom => library.artists.len().saturating_sub(1),
-                                _ => return,
+                            let Some(new_index) = edge_index(&action, count, library.artists.len())
+                            else {
+                                return;
                             };
                             self.artist_list_state.select(Some(new_index));
                             // Clear album selection when jumping in artists list
@@ -278,10 +313,10 @@ impl App {
                             let (display_items, _album_indices) =
                                 compute_album_display_list(&selected_artist, &self.expanded_albums);
                             if !display_items.is_empty() {
-                                let new_index = match action {
-                                    MPDAction::GoToTop => 0,
-                                    MPDAction::GoToBottom => display_items.len().saturating_sub(1),
-                                    _ => return,
+                                let Some(new_index) =
+                                    edge_index(&action, count, display_items.len())
+                                else {
+                                    return;
                                 };
                                 self.album_display_list_state.select(Some(new_index));
 
@@ -312,10 +347,10 @@ impl App {
                         if let Some(ref library) = self.library
                             && !library.all_albums.is_empty()
                         {
-                            let new_index = match action {
-                                MPDAction::GoToTop => 0,
-                                MPDAction::GoToBottom => library.all_albums.len().saturating_sub(1),
-                                _ => return,
+                            let Some(new_index) =
+                                edge_index(&action, count, library.all_albums.len())
+                            else {
+                                return;
                             };
                             self.all_albums_list_state.select(Some(new_index));
                             self.album_tracks_list_state.select(Some(0));
@@ -328,10 +363,9 @@ impl App {
                             && let Some((_, album)) = library.all_albums.get(selected_album_index)
                             && !album.tracks.is_empty()
                         {
-                            let new_index = match action {
-                                MPDAction::GoToTop => 0,
-                                MPDAction::GoToBottom => album.tracks.len().saturating_sub(1),
-                                _ => return,
+                            let Some(new_index) = edge_index(&action, count, album.tracks.len())
+                            else {
+                                return;
                             };
                             self.album_tracks_list_state.select(Some(new_index));
                         }
@@ -341,11 +375,34 @@ impl App {
                     }
                 }
             }
+            MenuMode::Files => {
+                let len = self.file_browser.entries.len();
+                if len > 0 {
+                    let Some(new_index) = edge_index(&action, count, len) else {
+                        return;
+                    };
+                    self.file_browser.list_state.select(Some(new_index));
+                }
+            }
         }
         // Mark appropriate dirty flags for go to edge
         match self.menu_mode {
             MenuMode::Queue => self.dirty.mark_queue_selection(),
-            MenuMode::Artists | MenuMode::Albums => self.dirty.mark_library(),
+            MenuMode::Artists | MenuMode::Albums | MenuMode::Files => self.dirty.mark_library(),
+        }
+    }
+}
+
+/// Row a go-to-edge action lands on in a non-empty list of `len` rows.
+/// With a count it goes to that (1-based) row instead, like vim's `5G`.
+fn edge_index(action: &MPDAction, count: Option<usize>, len: usize) -> Option<usize> {
+    let last = len.saturating_sub(1);
+    match (action, count) {
+        (MPDAction::GoToTop | MPDAction::GoToBottom, Some(row)) => {
+            Some(row.saturating_sub(1).min(last))
         }
+        (MPDAction::GoToTop, None) => Some(0),
+        (MPDAction::GoToBottom, None) => Some(last),
+        _ => None,
     }
 }
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_search_popup;
+use crate::app::{Config, MessageType, SongInfo, StatusMessage};
+
+/// Query and results of the database search overlay
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseSearch {
+    pub query: String,
+    /// Whether keys go to the query line rather than the results list
+    pub editing: bool,
+    pub results: Vec<SongInfo>,
+    pub selected: usize,
+}
+
+/// Split an optional "title:", "artist:" or "album:" prefix off `query`.
+/// Returns the MPD tag to search and the remaining term ("any" without a prefix).
+pub fn parse_search_query(query: &str) -> (&'static str, &str) {
+    if let Some((prefix, term)) = query.split_once(':') {
+        for tag in ["title", "artist", "album"] {
+            if prefix.trim().eq_ignore_ascii_case(tag) {
+                return (tag, term.trim());
+            }
+        }
+    }
+    ("any", query.trim())
+}
+
+impl App {
+    /// Open the database search overlay with an empty query
+    pub fn open_database_search(&mut self) {
+        self.push_overlay(DatabaseSearch {
+            editing: true,
+            ..Default::default()
+        });
+    }
+
+    /// Search MPD for the query of `search` and open it again with the results list
+    pub async fn run_database_search(&mut self, client: &Client, mut search: DatabaseSearch) {
+        let (tag, term) = parse_search_query(&search.query);
+        match SongInfo::search_database(client, tag, term, &self.tag_settings).await {
+            Ok(results) => {
+                log::info!("Search {} {:?}: {} results", tag, term, results.len());
+                search.results = results;
+                search.selected = 0;
+                search.editing = search.results.is_empty();
+            }
+            Err(e) => {
+                log::error!("Error searching database: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Search failed".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+        self.push_overlay(search);
+    }
+
+    /// Append `files` from the search results to the queue and report how many were added
+    pub async fn add_search_results(&mut self, client: &Client, files: &[PathBuf]) {
+        if files.is_empty() {
+            return;
+        }
+        let position = self.config.mpd.add_position();
+        self.enqueue_songs(client, files, false, position).await;
+        self.force_update = true;
+
+        let text = match files.len() {
+            1 => "Added 1 song".to_string(),
+            n => format!("Added {} songs", n),
+        };
+        self.set_status_message(StatusMessage {
+            text,
+            created_at: std::time::Instant::now(),
+            message_type: MessageType::Success,
+        });
+    }
+}
+
+impl Overlay for DatabaseSearch {
+    /// While editing, Enter runs the search; in the results list Enter adds the
+    /// selected song, A adds every result and / edits the query again
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        if self.editing {
+            match key.code {
+                KeyCode::Char(c) => self.query.push(c),
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Enter if parse_search_query(&self.query).1.is_empty() => {
+                    return OverlayKey::Ignored;
+                }
+                KeyCode::Enter => {
+                    return OverlayKey::CloseAndRun(OverlayCommand::Search(std::mem::take(self)));
+                }
+                // Back to the previous results, or close when there are none
+                KeyCode::Esc if !self.results.is_empty() => self.editing = false,
+                _ => return OverlayKey::Ignored,
+            }
+            return OverlayKey::Handled;
+        }
+
+        let last = self.results.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('/') => self.editing = true,
+            KeyCode::Enter | KeyCode::Char('a') => {
+                let files = self
+                    .results
+                    .get(self.selected)
+                    .map(|song| song.file_path.clone())
+                    .into_iter()
+                    .collect();
+                return OverlayKey::Run(OverlayCommand::AddSearchResults(files));
+            }
+            KeyCode::Char('A') => {
+                let files = self
+                    .results
+                    .iter()
+                    .map(|song| song.file_path.clone())
+                    .collect();
+                return OverlayKey::Run(OverlayCommand::AddSearchResults(files));
+            }
+            KeyCode::Char('q') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_search_popup(
+            frame,
+            &self.query,
+            self.editing,
+            &self.results,
+            self.selected,
+            config,
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use mpd_client::{Client, commands, commands::SingleMode, responses::Status};
+
+use crate::App;
+
+/// MPD's repeat and single options
+type PlaybackOptions = (bool, SingleMode);
+
+impl App {
+    /// Arm stop-after-current with MPD's single oneshot, or disarm it again. Repeat is
+    /// turned off while armed (with repeat on, single replays the song instead of
+    /// stopping), and disarming restores the repeat and single options arming replaced.
+    pub async fn toggle_stop_after_current(&mut self, client: &Client) {
+        let current = match &self.mpd_status {
+            Some(status) => (status.repeat, status.single),
+            None => match client.command(commands::Status).await {
+                Ok(status) => (status.repeat, status.single),
+                Err(e) => {
+                    self.report_mpd_error("StopAfterCurrent failed", &e);
+                    return;
+                }
+            },
+        };
+
+        let ((repeat, single), saved) = toggle_options(current, self.stop_after_current);
+        let result = client
+            .command_list((commands::SetRepeat(repeat), commands::SetSingle(single)))
+            .await;
+        match result {
+            Ok(_) => {
+                self.stop_after_current = saved;
+                self.dirty.mark_status();
+            }
+            Err(e) => self.report_mpd_error("StopAfterCurrent failed", &e),
+        }
+    }
+
+    /// Once MPD has stopped after the current song (it turns single oneshot off by
+    /// itself), put back the repeat and single options arming replaced
+    pub async fn finish_stop_after_current(&mut self, client: &Client, status: &Status) {
+        if status.single == SingleMode::Oneshot {
+            return;
+        }
+        // Single switched on meanwhile is a choice of its own, leave it be
+        let Some((repeat, single)) = self
+            .stop_after_current
+            .take()
+            .filter(|_| status.single == SingleMode::Disabled)
+        else {
+            return;
+        };
+
+        let result = client
+            .command_list((commands::SetRepeat(repeat), commands::SetSingle(single)))
+            .await;
+        if let Err(e) = result {
+            self.report_mpd_error("Error restoring repeat after stopping", &e);
+        }
+    }
+}
+
+/// Options to set for toggling stop-after-current from `current`, and the options to
+/// remember for restoring later (None once disarmed). `saved` is what arming remembered;
+/// armed by another client there is nothing to restore, so it is just switched off.
+fn toggle_options(
+    current: PlaybackOptions,
+    saved: Option<PlaybackOptions>,
+) -> (PlaybackOptions, Option<PlaybackOptions>) {
+    match current {
+        (repeat, SingleMode::Oneshot) => (saved.unwrap_or((repeat, SingleMode::Disabled)), None),
+        current => ((false, SingleMode::Oneshot), Some(current)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_options_restore_repeat_and_single() {
+        // Repeat one: both repeat and single are turned off for the stop...
+        let (armed, saved) = toggle_options((true, SingleMode::Enabled), None);
+        assert_eq!(armed, (false, SingleMode::Oneshot));
+        assert_eq!(saved, Some((true, SingleMode::Enabled)));
+
+        // ...and come back when it is toggled off again
+        let (disarmed, saved) = toggle_options(armed, saved);
+        assert_eq!(disarmed, (true, SingleMode::Enabled));
+        assert_eq!(saved, None);
+
+        // Armed elsewhere: nothing remembered, so only single is switched off
+        let (disarmed, _) = toggle_options((false, SingleMode::Oneshot), None);
+        assert_eq!(disarmed, (false, SingleMode::Disabled));
+    }
+}
@@ -0,0 +1,113 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::Client;
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::navigation::overlay::{Overlay, OverlayKey};
+use crate::app::ui::widgets::render_track_info_popup;
+use crate::app::{Config, MessageType, SongInfo, StatusMessage};
+
+/// Tags shown in the track info overlay and how far it is scrolled
+#[derive(Debug, Clone)]
+pub struct TrackInfo {
+    /// Missing-tag warnings for the song and its album, shown above the tags
+    pub warnings: Vec<String>,
+    pub fields: Vec<(String, String)>,
+    pub scroll: usize,
+}
+
+impl App {
+    /// Fetch all tags of the current song and open the track info overlay
+    pub async fn open_track_info(&mut self, client: &Client) {
+        let Some(song) = self.current_song.clone() else {
+            self.set_status_message(StatusMessage {
+                text: "Nothing playing".to_string(),
+                created_at: std::time::Instant::now(),
+                message_type: MessageType::Error,
+            });
+            return;
+        };
+        self.open_track_info_for(client, &song).await;
+    }
+
+    /// Fetch all tags of `song` and open the track info overlay
+    pub async fn open_track_info_for(&mut self, client: &Client, song: &SongInfo) {
+        let mut warnings: Vec<String> = song
+            .missing_tags(&self.tag_settings)
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        // Aggregate over the album when it is loaded in the library
+        let album = match (&self.library, song.album_key(&self.tag_settings)) {
+            (Some(library), Some((artist, album_name))) => library
+                .all_albums
+                .iter()
+                .find(|(a, album)| *a == artist && album.name == album_name)
+                .map(|(_, album)| album),
+            _ => None,
+        };
+        if let Some(album) = album
+            && album.poorly_tagged_tracks() > 0
+        {
+            warnings.push(format!(
+                "{} of {} tracks on this album have missing tags",
+                album.poorly_tagged_tracks(),
+                album.tracks.len()
+            ));
+        }
+
+        // Format summaries go above the tags
+        let mut summary = Vec::new();
+        if let Some(badge) = song.format_badge() {
+            summary.push(("Audio".to_string(), badge));
+        }
+        if let Some(album) = album {
+            if album.is_mixed_format() {
+                summary.push((
+                    "Album audio".to_string(),
+                    format!("mixed: {}", album.format_breakdown()),
+                ));
+            } else if let Some(badge) = album.format_badge() {
+                summary.push(("Album audio".to_string(), badge.to_string()));
+            }
+        }
+
+        match SongInfo::fetch_all_tags(client, &song.file_path).await {
+            Ok(fields) => {
+                summary.extend(fields);
+                self.push_overlay(TrackInfo {
+                    warnings,
+                    fields: summary,
+                    scroll: 0,
+                });
+            }
+            Err(e) => {
+                log::error!("Error fetching tags: {}", e);
+                self.set_status_message(StatusMessage {
+                    text: "Could not read tags".to_string(),
+                    created_at: std::time::Instant::now(),
+                    message_type: MessageType::Error,
+                });
+            }
+        }
+    }
+}
+
+impl Overlay for TrackInfo {
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        // The renderer clamps the scroll to the popup height
+        let last = (self.warnings.len() + self.fields.len()).saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::Char('q') | KeyCode::Char('i') => return OverlayKey::Close,
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_track_info_popup(frame, &self.warnings, &self.fields, self.scroll, config);
+    }
+}
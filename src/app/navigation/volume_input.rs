@@ -0,0 +1,61 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use mpd_client::{Client, commands};
+use ratatui::Frame;
+
+use crate::App;
+use crate::app::Config;
+use crate::app::navigation::overlay::{Overlay, OverlayCommand, OverlayKey};
+use crate::app::ui::widgets::render_input_popup;
+
+/// Digits typed so far in the volume input overlay
+#[derive(Debug, Clone, Default)]
+pub struct VolumeInput {
+    pub input: String,
+}
+
+impl App {
+    /// Open the volume input overlay with nothing typed yet
+    pub fn open_volume_input(&mut self) {
+        self.push_overlay(VolumeInput::default());
+    }
+
+    /// Set MPD's volume to `volume` from the volume input overlay
+    pub async fn set_volume(&mut self, client: &Client, volume: u8) {
+        match client.command(commands::SetVolume(volume)).await {
+            Ok(()) => {
+                log::info!("Volume set to {}", volume);
+                // An explicit level replaces the one remembered by mute
+                self.muted_volume = None;
+            }
+            Err(e) => self.report_mpd_error("Error setting volume", &e),
+        }
+        self.force_update = true;
+    }
+}
+
+impl Overlay for VolumeInput {
+    /// Digits are collected until Enter sets the volume
+    fn handle_key(&mut self, key: KeyEvent) -> OverlayKey {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() && self.input.len() < 3 => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            // Anything above 100 is clamped rather than rejected
+            KeyCode::Enter => {
+                return match self.input.parse::<u32>() {
+                    Ok(volume) => {
+                        OverlayKey::CloseAndRun(OverlayCommand::SetVolume(volume.min(100) as u8))
+                    }
+                    Err(_) => OverlayKey::Close,
+                };
+            }
+            _ => return OverlayKey::Ignored,
+        }
+        OverlayKey::Handled
+    }
+
+    fn render(&self, frame: &mut Frame, config: &Config) {
+        render_input_popup(frame, "Volume", "Set volume: ", &self.input, config);
+    }
+}
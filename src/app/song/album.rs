@@ -1,4 +1,5 @@
 use crate::app::SongInfo;
+use crate::app::song::TagSettings;
 
 #[derive(Debug, Clone)]
 pub struct Album {
@@ -6,20 +7,65 @@ pub struct Album {
     pub tracks: Vec<SongInfo>,
     /// Pre-computed total duration (computed once on construction)
     cached_total_duration: Option<std::time::Duration>,
+    /// Whether every track has a known duration (so the total is exact)
+    cached_duration_complete: bool,
+    /// Whether the tracks span more than one disc
+    cached_multi_disc: bool,
+    /// Number of tracks with missing key tags
+    cached_poorly_tagged: usize,
+    /// Track counts per format badge (see [`SongInfo::format_badge`]), most common first
+    cached_formats: Vec<(String, usize)>,
+    /// Badge of the most common format, with a trailing "*" when the formats are mixed
+    cached_format_badge: Option<String>,
 }
 
 impl Album {
     /// Create a new Album with pre-computed total duration
-    pub fn new(name: String, tracks: Vec<SongInfo>) -> Self {
+    pub fn new(name: String, tracks: Vec<SongInfo>, tags: &TagSettings) -> Self {
         let name = SongInfo::sanitize_string(&name);
         let cached_total_duration = Self::compute_total_duration(&tracks);
+        let cached_duration_complete = tracks.iter().all(|track| track.duration.is_some());
+        let cached_multi_disc = tracks
+            .iter()
+            .any(|track| track.disc_number != tracks[0].disc_number);
+        let cached_poorly_tagged = tracks
+            .iter()
+            .filter(|track| !track.missing_tags(tags).is_empty())
+            .count();
+        let cached_formats = Self::compute_formats(&tracks);
+        let cached_format_badge = cached_formats.first().map(|(badge, _)| {
+            if cached_formats.len() > 1 {
+                format!("{}*", badge)
+            } else {
+                badge.clone()
+            }
+        });
         Self {
             name,
             tracks,
             cached_total_duration,
+            cached_duration_complete,
+            cached_multi_disc,
+            cached_poorly_tagged,
+            cached_formats,
+            cached_format_badge,
         }
     }
 
+    /// Count the tracks of each format badge (used during construction).
+    /// Ties keep the order the formats first appear in.
+    fn compute_formats(tracks: &[SongInfo]) -> Vec<(String, usize)> {
+        let mut formats: Vec<(String, usize)> = Vec::new();
+        for badge in tracks.iter().filter_map(SongInfo::format_badge) {
+            match formats.iter_mut().find(|(known, _)| *known == badge) {
+                Some((_, count)) => *count += 1,
+                None => formats.push((badge, 1)),
+            }
+        }
+        formats.sort_by(|(_, a), (_, b)| b.cmp(a));
+        formats
+    }
+
     /// Compute total duration from tracks (used during construction)
     fn compute_total_duration(tracks: &[SongInfo]) -> Option<std::time::Duration> {
         let mut total_secs = 0u64;
@@ -43,4 +89,100 @@ impl Album {
     pub fn total_duration(&self) -> Option<std::time::Duration> {
         self.cached_total_duration
     }
+
+    /// Check if the tracks span more than one disc
+    pub fn is_multi_disc(&self) -> bool {
+        self.cached_multi_disc
+    }
+
+    /// Number of tracks with missing key tags (see [`SongInfo::missing_tags`])
+    pub fn poorly_tagged_tracks(&self) -> usize {
+        self.cached_poorly_tagged
+    }
+
+    /// Badge of the album's most common format, e.g. "FLAC 16/44", ending in "*" when
+    /// some tracks have another format. None when no track has a known format.
+    pub fn format_badge(&self) -> Option<&str> {
+        self.cached_format_badge.as_deref()
+    }
+
+    /// Whether the tracks come in more than one format
+    pub fn is_mixed_format(&self) -> bool {
+        self.cached_formats.len() > 1
+    }
+
+    /// Track counts per format, most common first, e.g. "10 FLAC 16/44, 2 MP3"
+    pub fn format_breakdown(&self) -> String {
+        self.cached_formats
+            .iter()
+            .map(|(badge, count)| format!("{} {}", count, badge))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Check if the total duration covers every track (false when some durations are unknown)
+    pub fn is_duration_complete(&self) -> bool {
+        self.cached_duration_complete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn track(file: &str, format: Option<&str>) -> SongInfo {
+        SongInfo {
+            title: file.to_string(),
+            artist: "Artist".to_string(),
+            artists: vec!["Artist".to_string()],
+            album: "Album".to_string(),
+            file_path: PathBuf::from(file),
+            format: format.map(String::from),
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_format_badges() {
+        assert_eq!(
+            track("a.flac", Some("44100:16:2"))
+                .format_badge()
+                .as_deref(),
+            Some("FLAC 16/44")
+        );
+        assert_eq!(
+            track("a.dsf", Some("dsd64:2")).format_badge().as_deref(),
+            Some("DSD64")
+        );
+        assert_eq!(
+            track("a.m4a", Some("44100:16:2")).format_badge().as_deref(),
+            Some("ALAC 16/44")
+        );
+        assert_eq!(
+            track("a.m4a", Some("44100:f:2")).format_badge().as_deref(),
+            Some("AAC")
+        );
+        assert_eq!(
+            track("a.mp3", Some("44100:24:2")).format_badge().as_deref(),
+            Some("MP3")
+        );
+        assert_eq!(track("a", None).format_badge(), None);
+    }
+
+    #[test]
+    fn test_mixed_format_album() {
+        let album = Album::new(
+            "Album".to_string(),
+            vec![
+                track("1.mp3", Some("44100:24:2")),
+                track("2.flac", Some("96000:24:2")),
+                track("3.flac", Some("96000:24:2")),
+            ],
+            &TagSettings::default(),
+        );
+        assert_eq!(album.format_badge(), Some("FLAC 24/96*"));
+        assert!(album.is_mixed_format());
+        assert_eq!(album.format_breakdown(), "2 FLAC 24/96, 1 MP3");
+    }
 }
@@ -1,34 +1,46 @@
+use std::sync::Arc;
+
 use crate::app::SongInfo;
-use crate::app::song::Album;
+use crate::app::song::{Album, TagSettings};
 
 #[derive(Debug, Clone)]
 pub struct Artist {
     pub name: String,
-    pub albums: Vec<Album>,
+    /// Shared with the library, so handing out an Artist never copies its tracks
+    pub albums: Vec<Arc<Album>>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) enum ArtistData {
     NotLoaded,
     Loading,
-    Loaded(Vec<Album>),
+    Loaded(Vec<Arc<Album>>),
 }
 
 /// Lazy-loaded artist: initially only has the name, albums are loaded on demand
 #[derive(Debug, Clone)]
 pub struct LazyArtist {
     pub name: String,
+    /// Tag values, as MPD lists them, of the artists grouped under this name
+    /// (just the name itself unless featured-artist credits are stripped)
+    pub tag_values: Vec<String>,
     /// Albums for this artist - tracks loading state to prevent concurrent loads
     pub albums: ArtistData,
+    /// Album count from a tag query, shown until the albums themselves are loaded
+    pub known_album_count: Option<usize>,
 }
 
 impl LazyArtist {
-    /// Create a new lazy artist with just the name
-    pub fn new(name: String) -> Self {
-        let name = SongInfo::sanitize_string(&name);
+    /// Create a new lazy artist with just the name, taken from the tag value `name`
+    pub fn new(name: String, tags: &TagSettings) -> Self {
+        let grouped = tags
+            .grouping_artist(&SongInfo::sanitize_string(&name))
+            .to_string();
         Self {
-            name,
+            name: grouped,
+            tag_values: vec![name],
             albums: ArtistData::NotLoaded,
+            known_album_count: None,
         }
     }
 
@@ -42,7 +54,16 @@ impl LazyArtist {
         matches!(self.albums, ArtistData::Loading)
     }
 
-    /// Convert to a regular Artist (returns empty albums if not loaded)
+    /// Number of albums, once they are loaded or counted
+    pub fn album_count(&self) -> Option<usize> {
+        match &self.albums {
+            ArtistData::Loaded(albums) => Some(albums.len()),
+            _ => self.known_album_count,
+        }
+    }
+
+    /// Convert to a regular Artist (returns empty albums if not loaded).
+    /// Cheap enough to call per frame: only the album handles are copied.
     pub fn to_artist(&self) -> Artist {
         let albums = match &self.albums {
             ArtistData::Loaded(albums) => albums.clone(),
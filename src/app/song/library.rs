@@ -1,13 +1,50 @@
 use crate::app::{
     SongInfo,
-    song::{Album, Artist, LazyArtist, artist::ArtistData},
+    config::mpd::GroupBy,
+    song::{Album, Artist, LazyArtist, TagSettings, artist::ArtistData},
 };
 use mpd_client::{
-    client::Client,
+    client::{Client, CommandError},
     commands,
     filter::{Filter, Operator},
     tag::Tag,
 };
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Phase of a running library load, reported to the UI while it waits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadProgress {
+    /// Fetching the album artist names
+    ListingArtists,
+    /// Fetching albums, `done` of `total` artists (total is 0 for a single bulk query)
+    LoadingAlbums { done: usize, total: usize },
+    /// Grouping the fetched songs into albums
+    BuildingAlbums,
+}
+
+impl std::fmt::Display for LoadProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadProgress::ListingArtists => write!(f, "Listing artists…"),
+            LoadProgress::LoadingAlbums { total: 0, .. } => write!(f, "Loading albums…"),
+            LoadProgress::LoadingAlbums { done, total } => {
+                write!(f, "Loading albums… ({}/{})", done, total)
+            }
+            LoadProgress::BuildingAlbums => write!(f, "Building albums…"),
+        }
+    }
+}
+
+/// Channel the library reports its [`LoadProgress`] on
+pub type LoadProgressSender = mpsc::UnboundedSender<LoadProgress>;
+
+/// Send `phase` if anyone is listening. A closed channel only means the UI stopped caring.
+fn report(progress: Option<&LoadProgressSender>, phase: LoadProgress) {
+    if let Some(tx) = progress {
+        let _ = tx.send(phase);
+    }
+}
 
 /// Lazy-loading library that only fetches artist data when needed
 #[derive(Debug, Clone)]
@@ -17,32 +54,44 @@ pub struct LazyLibrary {
     /// Flattened list of all albums sorted alphabetically by album name.
     /// This is populated incrementally as artists are loaded.
     /// Each entry is (artist_name, Album).
-    pub all_albums: Vec<(String, Album)>,
+    /// Albums are shared with the artists they belong to rather than copied.
+    pub all_albums: Vec<(String, Arc<Album>)>,
     /// Flag to track if all_albums is complete (all artists loaded)
     pub all_albums_complete: bool,
     /// Flag to track if all_albums is sorted
     pub all_albums_sorted: bool,
+    /// Tag the artists are listed by
+    pub group_by: GroupBy,
+    /// How songs are read and artists grouped
+    pub tags: Arc<TagSettings>,
 }
 
 impl LazyLibrary {
     /// Initialize the library by loading just the artist names.
     /// This is fast because it only fetches tag values, not full song metadata.
-    /// MPD command: list AlbumArtist
-    pub async fn init(client: &Client) -> color_eyre::Result<Self> {
+    /// MPD command: list AlbumArtist (or Artist, depending on `group_by`)
+    pub async fn init(
+        client: &Client,
+        group_by: GroupBy,
+        tags: Arc<TagSettings>,
+        progress: Option<&LoadProgressSender>,
+    ) -> color_eyre::Result<Self> {
         let start_time = std::time::Instant::now();
 
         log::info!("Initializing lazy library (loading artist names only)...");
+        report(progress, LoadProgress::ListingArtists);
 
-        // Get all unique album artists using the List command
-        let album_artists_list = match client.command(commands::List::new(Tag::AlbumArtist)).await {
+        // Get all unique (album) artists using the List command
+        let tag = group_by.tag();
+        let album_artists_list = match client.command(commands::List::new(tag.clone())).await {
             Ok(list) => list,
             Err(e) => {
-                log::error!("MPD List command failed for AlbumArtist tag: {}", e);
+                log::error!("MPD List command failed for {:?} tag: {}", tag, e);
                 log::error!("This usually indicates:");
                 log::error!("  - MPD database corruption or inconsistency");
                 log::error!("  - Permission issues with music directory");
                 log::error!("  - Network/protocol issues with MPD server");
-                log::error!("  - Missing or invalid AlbumArtist tags in music files");
+                log::error!("  - Missing or invalid {:?} tags in music files", tag);
                 return Err(color_eyre::eyre::eyre!(
                     "Failed to list album artists: {}",
                     e
@@ -50,15 +99,30 @@ impl LazyLibrary {
             }
         };
 
-        let mut artist_names: Vec<String> = album_artists_list
+        let artist_names: Vec<String> = album_artists_list
             .into_iter()
             .filter(|name| !name.is_empty())
             .collect();
 
-        // Sort alphabetically
-        artist_names.sort_by_key(|a| a.to_lowercase());
+        let mut artists: Vec<LazyArtist> = artist_names
+            .into_iter()
+            .map(|name| LazyArtist::new(name, &tags))
+            .collect();
 
-        let artists: Vec<LazyArtist> = artist_names.into_iter().map(LazyArtist::new).collect();
+        // Sort alphabetically, then merge the names that group together
+        artists.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        artists.dedup_by(|artist, kept| {
+            let same = artist.name == kept.name;
+            if same {
+                kept.tag_values.append(&mut artist.tag_values);
+            }
+            same
+        });
 
         let duration = start_time.elapsed();
         log::info!(
@@ -72,11 +136,13 @@ impl LazyLibrary {
             all_albums: Vec::new(),
             all_albums_complete: false,
             all_albums_sorted: false,
+            group_by,
+            tags,
         })
     }
 
     /// Load albums and songs for a specific artist by index.
-    /// MPD command: find "(AlbumArtist == 'artist_name')" sort Album
+    /// MPD command: find "(AlbumArtist == 'artist_name')" sort Album (Artist when grouping by artist)
     pub async fn load_artist(
         &mut self,
         client: &Client,
@@ -107,12 +173,18 @@ impl LazyLibrary {
 
         let start_time = std::time::Instant::now();
 
-        // Fetch all songs for this artist
-        let filter = Filter::new(Tag::AlbumArtist, Operator::Equal, artist_name.clone());
-        let find_cmd = commands::Find::new(filter).sort(Tag::Album);
+        // Fetch all songs for this artist, and for every credit grouped under it
+        let finds: Vec<commands::Find> = self.artists[artist_index]
+            .tag_values
+            .iter()
+            .map(|value| {
+                let filter = Filter::new(self.group_by.tag(), Operator::Equal, value.clone());
+                commands::Find::new(filter).sort(Tag::Album)
+            })
+            .collect();
 
-        let songs = match client.command(find_cmd).await {
-            Ok(songs) => songs,
+        let songs = match client.command_list(finds).await {
+            Ok(lists) => lists.into_iter().flatten(),
             Err(e) => {
                 // Revert to NotLoaded on error
                 self.artists[artist_index].albums = ArtistData::NotLoaded;
@@ -122,41 +194,28 @@ impl LazyLibrary {
                 log::error!("  - Missing or inaccessible music files");
                 log::error!("  - Permission issues with music directory");
                 log::error!("  - Network/protocol issues with MPD server");
-                return Err(color_eyre::eyre::eyre!(
-                    "Failed to find songs for artist '{}': {}",
-                    artist_name,
-                    e
-                ));
+                // Keeps the command error reachable for callers that tell
+                // connection loss apart from a bad artist
+                return Err(color_eyre::Report::new(e)
+                    .wrap_err(format!("Failed to find songs for artist '{}'", artist_name)));
             }
         };
 
-        // Group songs by album
-        let mut albums_map: std::collections::HashMap<String, Vec<SongInfo>> =
-            std::collections::HashMap::new();
-
+        // Group songs by album (all songs share this artist)
+        let mut albums_map: AlbumsMap = std::collections::HashMap::new();
+        // With several credits a song can be listed under more than one of them
+        let mut seen = std::collections::HashSet::new();
         for song in songs {
-            let song_info = SongInfo::from_song(&song);
-            let album_name = song_info.album.clone();
-            albums_map.entry(album_name).or_default().push(song_info);
+            if !seen.insert(song.url.clone()) {
+                continue;
+            }
+            let song_info = SongInfo::from_song(&song, &self.tags);
+            albums_map
+                .entry(song_info.album.clone())
+                .or_default()
+                .push(song_info);
         }
-
-        // Build album list
-        let mut albums: Vec<Album> = albums_map
-            .into_iter()
-            .map(|(album_name, mut tracks)| {
-                // Sort tracks by disc and track number
-                tracks.sort_by(|a, b| {
-                    a.disc_number
-                        .cmp(&b.disc_number)
-                        .then(a.track_number.cmp(&b.track_number))
-                        .then(a.title.cmp(&b.title))
-                });
-                Album::new(album_name, tracks)
-            })
-            .collect();
-
-        // Sort albums alphabetically
-        albums.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        let albums = build_albums(albums_map, &self.tags);
 
         let duration = start_time.elapsed();
         log::debug!(
@@ -174,7 +233,8 @@ impl LazyLibrary {
                 .iter()
                 .any(|(a_name, a)| a_name == &artist_name && a.name == album.name);
             if !exists {
-                self.all_albums.push((artist_name.clone(), album.clone()));
+                self.all_albums
+                    .push((artist_name.clone(), Arc::clone(album)));
             }
         }
 
@@ -213,12 +273,17 @@ impl LazyLibrary {
 
     /// Preload all albums for the Albums view.
     /// Uses a fast bulk approach: fetches all songs at once instead of per-artist.
-    pub async fn preload_all_albums(&mut self, client: &Client) -> color_eyre::Result<()> {
+    pub async fn preload_all_albums(
+        &mut self,
+        client: &Client,
+        progress: Option<&LoadProgressSender>,
+    ) -> color_eyre::Result<()> {
         if self.all_albums_complete {
             return Ok(());
         }
 
         log::info!("Preloading all albums for Albums view (bulk)...");
+        report(progress, LoadProgress::LoadingAlbums { done: 0, total: 0 });
         let start_time = std::time::Instant::now();
 
         // Fetch ALL songs in the library at once using a filter that matches every song
@@ -227,34 +292,35 @@ impl LazyLibrary {
         //   Filter::tag_exists(Tag::Other("file".into()))
         // (Conceptually similar to a raw MPD query like: find "(file != '')")
         let filter = Filter::tag_exists(Tag::Other("file".into()));
-        let all_songs = client
-            .command(commands::Find::new(filter))
-            .await
-            .map_err(|e| color_eyre::eyre::eyre!("Failed to find all songs: {}", e))?;
+        let all_songs = match client.command(commands::Find::new(filter)).await {
+            Ok(songs) => songs,
+            Err(CommandError::ConnectionClosed) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "Failed to find all songs: {}",
+                    CommandError::ConnectionClosed
+                ));
+            }
+            Err(e) => {
+                // Very large libraries can overflow or choke the single bulk response.
+                // Fall back to smaller per-artist queries instead of giving up.
+                log::warn!(
+                    "Bulk album preload failed ({}), falling back to per-artist loading",
+                    e
+                );
+                return self.preload_all_albums_by_artist(client, progress).await;
+            }
+        };
 
-        // Group by artist -> album -> songs
-        let mut artist_albums: std::collections::HashMap<
-            String,
-            std::collections::HashMap<String, Vec<SongInfo>>,
-        > = std::collections::HashMap::new();
-
-        for song in all_songs {
-            let song_info = SongInfo::from_song(&song);
-            // Use album artist for grouping (fall back to artist if not set)
-            let artist_name = song
-                .album_artists()
-                .first()
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| song_info.artist.clone());
-            let album_name = song_info.album.clone();
+        report(progress, LoadProgress::BuildingAlbums);
 
-            artist_albums
-                .entry(artist_name)
-                .or_default()
-                .entry(album_name)
-                .or_default()
-                .push(song_info);
-        }
+        // Group by artist -> album -> songs
+        let mut artist_albums = group_by_artist(
+            all_songs
+                .iter()
+                .map(|song| SongInfo::from_song(song, &self.tags)),
+            self.group_by,
+            &self.tags,
+        );
 
         // Update each artist's albums
         for artist in &mut self.artists {
@@ -264,24 +330,12 @@ impl LazyLibrary {
             }
 
             if let Some(albums_map) = artist_albums.remove(&artist.name) {
-                let mut albums: Vec<Album> = albums_map
-                    .into_iter()
-                    .map(|(album_name, mut tracks)| {
-                        tracks.sort_by(|a, b| {
-                            a.disc_number
-                                .cmp(&b.disc_number)
-                                .then(a.track_number.cmp(&b.track_number))
-                                .then(a.title.cmp(&b.title))
-                        });
-                        Album::new(album_name, tracks)
-                    })
-                    .collect();
-
-                albums.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+                let albums = build_albums(albums_map, &self.tags);
 
                 // Add to all_albums
                 for album in &albums {
-                    self.all_albums.push((artist.name.clone(), album.clone()));
+                    self.all_albums
+                        .push((artist.name.clone(), Arc::clone(album)));
                 }
 
                 artist.albums = ArtistData::Loaded(albums);
@@ -311,4 +365,243 @@ impl LazyLibrary {
 
         Ok(())
     }
+
+    /// Preload all albums by loading every artist individually.
+    /// More round trips than the bulk query, but each response stays small.
+    /// MPD command: find "(AlbumArtist == 'artist_name')" sort Album (per artist)
+    pub async fn preload_all_albums_by_artist(
+        &mut self,
+        client: &Client,
+        progress: Option<&LoadProgressSender>,
+    ) -> color_eyre::Result<()> {
+        if self.all_albums_complete {
+            return Ok(());
+        }
+
+        log::info!("Preloading all albums for Albums view (per artist)...");
+        let start_time = std::time::Instant::now();
+        let mut failed = 0;
+
+        let total = self.artists.len();
+        for artist_index in 0..total {
+            report(
+                progress,
+                LoadProgress::LoadingAlbums {
+                    done: artist_index,
+                    total,
+                },
+            );
+            match self.load_artist(client, artist_index).await {
+                Ok(()) => {}
+                // Every other artist would fail the same way
+                Err(e) if connection_lost(&e) => {
+                    log::error!("Lost the connection during chunked preload: {:#}", e);
+                    return Err(e);
+                }
+                Err(e) => {
+                    log::warn!("Skipping artist during chunked preload: {:#}", e);
+                    failed += 1;
+                }
+            }
+        }
+
+        self.ensure_albums_sorted();
+
+        let duration = start_time.elapsed();
+        if failed > 0 {
+            log::warn!(
+                "Chunked preload finished with {} failed artists: {} albums in {:?}",
+                failed,
+                self.all_albums.len(),
+                duration
+            );
+        } else {
+            log::info!(
+                "All albums preloaded (chunked): {} albums in {:?}",
+                self.all_albums.len(),
+                duration
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `error` comes from the connection to MPD being gone, rather than from
+/// the command itself
+fn connection_lost(error: &color_eyre::Report) -> bool {
+    matches!(
+        error.downcast_ref::<CommandError>(),
+        Some(CommandError::ConnectionClosed | CommandError::Protocol(_))
+    )
+}
+
+/// Songs of one artist, grouped by album name
+type AlbumsMap = std::collections::HashMap<String, Vec<SongInfo>>;
+
+/// Group songs by album artist (or artist), then album.
+///
+/// Albums are always keyed on (artist, album), so two artists' albums
+/// sharing a title ("Greatest Hits") stay separate. The album artist falls back
+/// to the track artist and uses the same sanitized form as [`LazyArtist`] names, with
+/// featured-artist credits stripped.
+/// Grouping by artist files a song under each of its artists, matching what
+/// `find Artist == name` returns for every one of them.
+fn group_by_artist(
+    songs: impl IntoIterator<Item = SongInfo>,
+    group_by: GroupBy,
+    tags: &TagSettings,
+) -> std::collections::HashMap<String, AlbumsMap> {
+    let mut artist_albums: std::collections::HashMap<String, AlbumsMap> =
+        std::collections::HashMap::new();
+
+    for song_info in songs {
+        let artist_names = match group_by {
+            GroupBy::AlbumArtist => vec![song_info.library_artist(group_by, tags).to_string()],
+            GroupBy::Artist if song_info.artists.is_empty() => {
+                vec![song_info.library_artist(group_by, tags).to_string()]
+            }
+            GroupBy::Artist => song_info
+                .artists
+                .iter()
+                .map(|artist| tags.grouping_artist(artist).to_string())
+                .collect::<std::collections::BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        };
+        for artist_name in artist_names {
+            artist_albums
+                .entry(artist_name)
+                .or_default()
+                .entry(song_info.album.clone())
+                .or_default()
+                .push(song_info.clone());
+        }
+    }
+
+    artist_albums
+}
+
+/// Build one artist's albums: tracks sorted by disc and track number, albums alphabetically.
+/// The file path is the final tiebreaker so untagged tracks (all defaults) keep a stable
+/// order between launches instead of following HashMap iteration order.
+fn build_albums(albums_map: AlbumsMap, tags: &TagSettings) -> Vec<Arc<Album>> {
+    let mut albums: Vec<Album> = albums_map
+        .into_iter()
+        .map(|(album_name, mut tracks)| {
+            tracks.sort_by(|a, b| {
+                a.disc_number
+                    .cmp(&b.disc_number)
+                    .then(a.track_number.cmp(&b.track_number))
+                    .then(a.title.cmp(&b.title))
+                    .then_with(|| a.file_path.cmp(&b.file_path))
+            });
+            Album::new(album_name, tracks, tags)
+        })
+        .collect();
+
+    albums.sort_by(|a, b| {
+        a.name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    albums.into_iter().map(Arc::new).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn song(album_artist: &str, album: &str, title: &str) -> SongInfo {
+        SongInfo {
+            title: title.to_string(),
+            artist: album_artist.to_string(),
+            artists: vec![album_artist.to_string()],
+            album: album.to_string(),
+            album_artist: Some(album_artist.to_string()),
+            file_path: PathBuf::from(format!("{}/{}/{}.flac", album_artist, album, title)),
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_shared_album_title_stays_separate_per_artist() {
+        let tags = TagSettings::default();
+        let mut grouped = group_by_artist(
+            [
+                song("Artist A", "Greatest Hits", "A1"),
+                song("Artist B", "Greatest Hits", "B1"),
+                song("Artist A", "Greatest Hits", "A2"),
+            ],
+            GroupBy::AlbumArtist,
+            &tags,
+        );
+
+        let a = build_albums(grouped.remove("Artist A").unwrap(), &tags);
+        let b = build_albums(grouped.remove("Artist B").unwrap(), &tags);
+        assert_eq!(a.len(), 1);
+        assert_eq!(b.len(), 1);
+        assert_eq!(a[0].tracks.len(), 2);
+        assert_eq!(b[0].tracks.len(), 1);
+        assert!(a[0].tracks.iter().all(|t| t.artist == "Artist A"));
+        assert_eq!(b[0].tracks[0].title, "B1");
+    }
+
+    #[test]
+    fn test_untagged_tracks_sort_by_file_path() {
+        let untagged = |path: &str| {
+            let mut song = song("Unknown Artist", "Unknown Album", "Unknown Title");
+            song.file_path = PathBuf::from(path);
+            song.track_number = 0;
+            song
+        };
+        let songs = vec![untagged("c.mp3"), untagged("a.mp3"), untagged("b.mp3")];
+
+        let tags = TagSettings::default();
+        let mut grouped = group_by_artist(songs, GroupBy::AlbumArtist, &tags);
+        let albums = build_albums(grouped.remove("Unknown Artist").unwrap(), &tags);
+        let paths: Vec<_> = albums[0].tracks.iter().map(|t| &t.file_path).collect();
+        assert_eq!(
+            paths,
+            [
+                &PathBuf::from("a.mp3"),
+                &PathBuf::from("b.mp3"),
+                &PathBuf::from("c.mp3")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grouping_by_artist_files_songs_under_each_artist() {
+        let mut duet = song("Artist A", "Duets", "Together");
+        duet.artists = vec!["Artist A".to_string(), "Artist B".to_string()];
+
+        let tags = TagSettings::default();
+        let grouped = group_by_artist([duet.clone()], GroupBy::Artist, &tags);
+        assert!(grouped.contains_key("Artist A"));
+        assert!(grouped.contains_key("Artist B"));
+
+        // By album artist the duet only shows up once
+        let grouped = group_by_artist([duet], GroupBy::AlbumArtist, &tags);
+        assert_eq!(grouped.len(), 1);
+    }
+
+    #[test]
+    fn test_connection_lost_only_for_a_dead_connection() {
+        let wrap = |e: CommandError| color_eyre::Report::new(e).wrap_err("Failed to find songs");
+        assert!(connection_lost(&wrap(CommandError::ConnectionClosed)));
+
+        let refused = CommandError::ErrorResponse {
+            error: mpd_client::protocol::response::Error {
+                code: 50,
+                command_index: 0,
+                current_command: Some("find".into()),
+                message: "No such song".into(),
+            },
+            succesful_frames: Vec::new(),
+        };
+        assert!(!connection_lost(&wrap(refused)));
+    }
 }
@@ -6,5 +6,5 @@ pub mod song_info;
 // Convenience re-exports
 pub use album::Album;
 pub use artist::{Artist, LazyArtist};
-pub use library::LazyLibrary;
-pub use song_info::SongInfo;
+pub use library::{LazyLibrary, LoadProgress, LoadProgressSender};
+pub use song_info::{SongInfo, TagSettings};
@@ -1,16 +1,108 @@
+use crate::app::config::Config;
+use crate::app::config::mpd::GroupBy;
+use crate::app::config::ui::TitleFallback;
 use mpd_client::{
     Client,
     client::CommandError,
     commands::SetBinaryLimit,
+    protocol::Command as RawCommand,
     responses::{PlayState, Song},
 };
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How tags are read and artists grouped, taken from the config once and passed to
+/// everything that turns MPD songs into [`SongInfo`]s or groups them
+#[derive(Debug, Clone)]
+pub struct TagSettings {
+    /// What is shown as the title of songs without a Title tag
+    title_fallback: TitleFallback,
+    /// Patterns that start a featured-artist credit ("feat.")
+    featured_patterns: Vec<String>,
+}
+
+impl Default for TagSettings {
+    fn default() -> Self {
+        Self {
+            title_fallback: TitleFallback::Unknown,
+            featured_patterns: Vec::new(),
+        }
+    }
+}
+
+impl TagSettings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            title_fallback: config.ui.title_fallback(),
+            featured_patterns: config.mpd.featured_artist_patterns.clone(),
+        }
+    }
+
+    /// Name an artist is grouped under in the library: `name` without a featured-artist
+    /// credit matching one of the configured patterns
+    pub fn grouping_artist<'a>(&self, name: &'a str) -> &'a str {
+        strip_featured(name, &self.featured_patterns)
+    }
+
+    /// Title for a song at `path` that has no Title tag, following the configured fallback
+    fn fallback_title(&self, path: &Path) -> String {
+        match self.title_fallback {
+            TitleFallback::Filename => {
+                title_from_path(path).unwrap_or_else(|| "Unknown Title".to_string())
+            }
+            TitleFallback::Unknown => "Unknown Title".to_string(),
+        }
+    }
+}
+
+/// `name` up to the earliest of `patterns` (ignoring ASCII case), e.g. "Artist" for
+/// "Artist feat. Guest" with " feat. ". A name that starts with a pattern is kept whole.
+fn strip_featured<'a>(name: &'a str, patterns: &[String]) -> &'a str {
+    let cut = patterns
+        .iter()
+        .filter(|pattern| !pattern.is_empty())
+        .filter_map(|pattern| {
+            name.as_bytes()
+                .windows(pattern.len())
+                .position(|window| window.eq_ignore_ascii_case(pattern.as_bytes()))
+        })
+        .min();
+    match cut.map(|cut| name[..cut].trim_end()) {
+        Some(stripped) if !stripped.is_empty() => stripped,
+        _ => name,
+    }
+}
+
+/// Readable title made from a file name: the extension and a leading track number
+/// ("03 - ", "1-03. ") are dropped and underscores become spaces.
+/// None when the path has no file name.
+pub fn title_from_path(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_string_lossy();
+    let stem = SongInfo::sanitize_string(&stem.replace('_', " "));
+    let stem = stem.trim();
+
+    if !stem.starts_with(|c: char| c.is_ascii_digit()) {
+        return (!stem.is_empty()).then(|| stem.to_string());
+    }
+    // Only drop the number when something is left, "1979.flac" keeps its name
+    let title = stem
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '-')
+        .trim_start_matches(['.', ' ', '-'])
+        .trim();
+    let title = if title.is_empty() { stem } else { title };
+    (!title.is_empty()).then(|| title.to_string())
+}
 
 #[derive(Debug, Clone)]
 pub struct SongInfo {
     pub title: String,
+    /// Primary (first) artist, used for sorting and grouping
     pub artist: String,
+    /// Every artist of the track, for display (collaborations, features)
+    pub artists: Vec<String>,
     pub album: String,
+    pub album_artist: Option<String>,
     pub file_path: PathBuf,
     pub format: Option<String>,
     pub play_state: Option<PlayState>,
@@ -19,6 +111,77 @@ pub struct SongInfo {
     pub duration: Option<std::time::Duration>,
     pub disc_number: u64,
     pub track_number: u64,
+    /// ReplayGain tags of the file, read for the current song only (None = not read)
+    pub replay_gain: Option<ReplayGain>,
+}
+
+/// Empty song on the first disc and track, for tests to fill in the fields they need
+#[cfg(test)]
+impl Default for SongInfo {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            artist: String::new(),
+            artists: Vec::new(),
+            album: String::new(),
+            album_artist: None,
+            file_path: PathBuf::new(),
+            format: None,
+            play_state: None,
+            progress: None,
+            elapsed: None,
+            duration: None,
+            disc_number: 1,
+            track_number: 1,
+            replay_gain: None,
+        }
+    }
+}
+
+/// ReplayGain values from a file's tags, in dB
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplayGain {
+    pub track_gain: Option<f32>,
+    pub album_gain: Option<f32>,
+}
+
+impl ReplayGain {
+    /// Read the gains from raw file comments such as `REPLAYGAIN_TRACK_GAIN: -6.48 dB`
+    pub fn from_comments<'a>(comments: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut gain = Self::default();
+        for (key, value) in comments {
+            if key.eq_ignore_ascii_case("REPLAYGAIN_TRACK_GAIN") {
+                gain.track_gain = gain.track_gain.or(parse_gain(value));
+            } else if key.eq_ignore_ascii_case("REPLAYGAIN_ALBUM_GAIN") {
+                gain.album_gain = gain.album_gain.or(parse_gain(value));
+            }
+        }
+        gain
+    }
+
+    /// Short description for the format line, "untagged" without any gain tag
+    pub fn label(&self) -> String {
+        let gain = |db: f32| format!("{:+.1} dB", db);
+        match (self.track_gain, self.album_gain) {
+            (None, None) => "untagged".to_string(),
+            (Some(track), None) => format!("track {}", gain(track)),
+            (None, Some(album)) => format!("album {}", gain(album)),
+            (Some(track), Some(album)) => {
+                format!("track {} album {}", gain(track), gain(album))
+            }
+        }
+    }
+}
+
+/// Gain in dB from a tag value such as "-6.48 dB" or "+1.2"
+fn parse_gain(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let number = value
+        .strip_suffix("dB")
+        .or_else(|| value.strip_suffix("db"))
+        .or_else(|| value.strip_suffix("DB"))
+        .unwrap_or(value);
+    number.trim().parse().ok()
 }
 
 impl SongInfo {
@@ -47,15 +210,19 @@ impl SongInfo {
         result
     }
 
-    pub fn from_song(song: &Song) -> Self {
+    pub fn from_song(song: &Song, tags: &TagSettings) -> Self {
         let title = song
             .title()
             .map(Self::sanitize_string)
-            .unwrap_or_else(|| "Unknown Title".to_string());
-        let artist = song
+            .unwrap_or_else(|| tags.fallback_title(song.file_path()));
+        let artists: Vec<String> = song
             .artists()
-            .first()
+            .iter()
             .map(|s| Self::sanitize_string(s))
+            .collect();
+        let artist = artists
+            .first()
+            .cloned()
             .unwrap_or_else(|| "Unknown Artist".to_string());
 
         let album = song
@@ -63,6 +230,11 @@ impl SongInfo {
             .map(Self::sanitize_string)
             .unwrap_or_else(|| "Unknown Album".to_string());
 
+        let album_artist = song
+            .album_artists()
+            .first()
+            .map(|s| Self::sanitize_string(s));
+
         let file_path = song.file_path().to_path_buf();
         let format = song.format.clone();
         let duration = song.duration;
@@ -71,7 +243,9 @@ impl SongInfo {
         Self {
             title,
             artist,
+            artists,
             album,
+            album_artist,
             file_path,
             format,
             play_state: None,
@@ -80,8 +254,161 @@ impl SongInfo {
             duration,
             disc_number,
             track_number,
+            replay_gain: None,
+        }
+    }
+
+    /// Build songs from a raw song list response, where each `file` field starts a new song.
+    /// Tags are read the same way as [`SongInfo::from_song`].
+    pub fn from_fields<'a>(
+        fields: impl IntoIterator<Item = (&'a str, &'a str)>,
+        tags: &TagSettings,
+    ) -> Vec<Self> {
+        let mut songs: Vec<Self> = Vec::new();
+
+        for (key, value) in fields {
+            if key == "file" {
+                songs.push(Self {
+                    title: "Unknown Title".to_string(),
+                    artist: "Unknown Artist".to_string(),
+                    artists: Vec::new(),
+                    album: "Unknown Album".to_string(),
+                    album_artist: None,
+                    file_path: PathBuf::from(value),
+                    format: None,
+                    play_state: None,
+                    progress: None,
+                    elapsed: None,
+                    duration: None,
+                    disc_number: 0,
+                    track_number: 0,
+                    replay_gain: None,
+                });
+                continue;
+            }
+            let Some(song) = songs.last_mut() else {
+                continue;
+            };
+
+            // Only the first value of single-valued tags counts, like Song::title()
+            match key {
+                "Title" if song.title == "Unknown Title" => {
+                    song.title = Self::sanitize_string(value)
+                }
+                "Artist" => song.artists.push(Self::sanitize_string(value)),
+                "Album" if song.album == "Unknown Album" => {
+                    song.album = Self::sanitize_string(value)
+                }
+                "AlbumArtist" if song.album_artist.is_none() => {
+                    song.album_artist = Some(Self::sanitize_string(value))
+                }
+                "Format" => song.format = Some(value.to_string()),
+                "duration" => song.duration = value.parse().ok().map(Duration::from_secs_f64),
+                "Track" => song.track_number = leading_number(value),
+                "Disc" => song.disc_number = leading_number(value),
+                _ => {}
+            }
+        }
+
+        for song in &mut songs {
+            if let Some(artist) = song.artists.first() {
+                song.artist = artist.clone();
+            }
+            if song.title == "Unknown Title" {
+                song.title = tags.fallback_title(&song.file_path);
+            }
         }
+        songs
     }
+
+    /// Search the whole database for songs whose `tag` contains `query`, ignoring case.
+    /// `tag` is an MPD tag name such as "title", or "any" to match every tag.
+    ///
+    /// MPD command: search (mpd_client has no typed wrapper, and find is case sensitive)
+    pub async fn search_database(
+        client: &Client,
+        tag: &str,
+        query: &str,
+        tags: &TagSettings,
+    ) -> Result<Vec<Self>, CommandError> {
+        let frame = client
+            .raw_command(RawCommand::new("search").argument(tag).argument(query))
+            .await?;
+        Ok(Self::from_fields(frame.fields(), tags))
+    }
+
+    /// Album artist and album name identifying the album this song belongs to.
+    /// The artist is grouped like library artists, so the key matches library albums.
+    /// None for songs without an album tag (singles).
+    pub fn album_key(&self, tags: &TagSettings) -> Option<(String, String)> {
+        if self.album == "Unknown Album" {
+            return None;
+        }
+        let artist = tags.grouping_artist(self.album_artist.as_ref().unwrap_or(&self.artist));
+        Some((artist.to_string(), self.album.clone()))
+    }
+
+    /// Name of the library artist this song is listed under when grouping by `group_by`
+    pub fn library_artist(&self, group_by: GroupBy, tags: &TagSettings) -> &str {
+        tags.grouping_artist(match group_by {
+            GroupBy::AlbumArtist => self.album_artist.as_ref().unwrap_or(&self.artist),
+            GroupBy::Artist => &self.artist,
+        })
+    }
+
+    /// All artists joined with `separator`, or just the primary artist for single-artist tracks
+    pub fn display_artists(&self, separator: &str) -> Cow<'_, str> {
+        if self.artists.len() > 1 {
+            Cow::Owned(self.artists.join(separator))
+        } else {
+            Cow::Borrowed(&self.artist)
+        }
+    }
+
+    /// Key tags that were missing from the file and replaced by defaults
+    pub fn missing_tags(&self, tags: &TagSettings) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        // A title made from the file name is a default too
+        if self.title == "Unknown Title" || self.title == tags.fallback_title(&self.file_path) {
+            missing.push("no Title tag");
+        }
+        if self.artist == "Unknown Artist" {
+            missing.push("no Artist tag");
+        }
+        if self.album == "Unknown Album" {
+            missing.push("no Album tag");
+        }
+        if self.album_artist.is_none() {
+            missing.push("no AlbumArtist tag");
+        }
+        if self.track_number == 0 {
+            missing.push("no track number");
+        }
+        missing
+    }
+
+    /// Track number as shown in album tracklists: "1-03" on multi-disc albums, "03" otherwise
+    pub fn track_label(&self, multi_disc: bool) -> String {
+        match (self.track_number, multi_disc) {
+            (0, false) => "--".to_string(),
+            (0, true) => format!("{}---", self.disc_number),
+            (track, false) => format!("{:02}", track),
+            (track, true) => format!("{}-{:02}", self.disc_number, track),
+        }
+    }
+
+    /// Convert a file path to the URI sent to MPD.
+    /// Paths that are not valid UTF-8 are converted lossily (MPD only speaks UTF-8),
+    /// which is logged since the result may no longer name the file on disk and
+    /// must not be used for filesystem lookups.
+    pub fn uri_for_path(path: &Path) -> Cow<'_, str> {
+        let uri = path.to_string_lossy();
+        if let Cow::Owned(ref lossy) = uri {
+            log::warn!("Path is not valid UTF-8, using lossy URI {:?}", lossy);
+        }
+        uri
+    }
+
     pub async fn set_max_art_size(client: &Client, size_bytes: usize) -> Result<(), CommandError> {
         client.command(SetBinaryLimit(size_bytes)).await
     }
@@ -91,8 +418,8 @@ impl SongInfo {
     /// background loading via spawn_cover_art_loader for better responsiveness.
     #[allow(dead_code)]
     pub async fn load_cover(&self, client: &Client) -> Option<Vec<u8>> {
-        let uri = self.file_path.to_str()?;
-        let art_data_result = client.album_art(uri).await.ok()?;
+        let uri = Self::uri_for_path(&self.file_path);
+        let art_data_result = client.album_art(&uri).await.ok()?;
 
         let (raw_data, _mime_type_option) = art_data_result?;
 
@@ -122,4 +449,239 @@ impl SongInfo {
             .as_ref()
             .and_then(|f| f.split(':').next()?.parse().ok())
     }
+
+    /// Short codec and resolution label such as "FLAC 16/44", "DSD64" or "MP3",
+    /// from the file extension and the MPD format string.
+    ///
+    /// Lossy codecs get no resolution, their decoders report float samples rather
+    /// than a bit depth. M4A files are told apart the same way: ALAC decodes to
+    /// integer samples, AAC to float. None for files without an extension.
+    pub fn format_badge(&self) -> Option<String> {
+        let extension = self.file_path.extension()?.to_str()?.to_lowercase();
+        let mut parts = self.format.as_deref().unwrap_or("").split(':');
+        let (rate, bits) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        // Bit depth and sample rate in kHz, e.g. "24/96"
+        let resolution = match (bits.parse::<u32>(), rate.parse::<u32>()) {
+            (Ok(bits), Ok(rate)) => Some(format!("{}/{}", bits, rate / 1000)),
+            _ => None,
+        };
+
+        let codec = match extension.as_str() {
+            // MPD reports DSD as e.g. "dsd64:2"
+            "dsf" | "dff" if rate.starts_with("dsd") => return Some(rate.to_uppercase()),
+            "dsf" | "dff" => return Some("DSD".to_string()),
+            "m4a" | "mp4" if resolution.is_some() => "ALAC".to_string(),
+            "m4a" | "mp4" => return Some("AAC".to_string()),
+            "flac" | "wav" | "aif" | "aiff" | "ape" | "wv" | "alac" => extension.to_uppercase(),
+            _ => return Some(extension.to_uppercase()),
+        };
+        Some(match resolution {
+            Some(resolution) => format!("{} {}", codec, resolution),
+            None => codec,
+        })
+    }
+
+    /// Read the ReplayGain tags of the file at `path` from its raw comments.
+    ///
+    /// MPD command: readcomments
+    pub async fn fetch_replay_gain(
+        client: &Client,
+        path: &Path,
+    ) -> Result<ReplayGain, CommandError> {
+        let uri = Self::uri_for_path(path);
+        let frame = client
+            .raw_command(RawCommand::new("readcomments").argument(uri.as_ref()))
+            .await?;
+        Ok(ReplayGain::from_comments(frame.fields()))
+    }
+
+    /// Fetch every tag MPD knows for `path`, for the track info overlay.
+    ///
+    /// MPD command: lsinfo (enabled tag types) followed by readcomments (raw file
+    /// comments such as LABEL or ISRC). Comments that duplicate a tag are dropped.
+    pub async fn fetch_all_tags(
+        client: &Client,
+        path: &Path,
+    ) -> Result<Vec<(String, String)>, CommandError> {
+        let uri = Self::uri_for_path(path);
+        let tags = client
+            .raw_command(RawCommand::new("lsinfo").argument(uri.as_ref()))
+            .await?;
+
+        // Not every file type (or MPD build) supports comments, tags alone are still useful
+        let comments = match client
+            .raw_command(RawCommand::new("readcomments").argument(uri.as_ref()))
+            .await
+        {
+            Ok(frame) => frame
+                .fields()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            Err(e) => {
+                log::debug!("readcomments failed for {}: {}", uri, e);
+                Vec::new()
+            }
+        };
+
+        let tags = tags
+            .fields()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Ok(merge_tags(tags, comments))
+    }
+}
+
+/// Number at the start of a tag value such as "3/12", 0 when there is none
+fn leading_number(value: &str) -> u64 {
+    value
+        .split('/')
+        .next()
+        .and_then(|n| n.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Append the `comments` whose key (case-insensitively) is not already among the `tags`
+fn merge_tags(
+    mut tags: Vec<(String, String)>,
+    comments: Vec<(String, String)>,
+) -> Vec<(String, String)> {
+    for (key, value) in comments {
+        if !tags.iter().any(|(k, _)| k.eq_ignore_ascii_case(&key)) {
+            tags.push((key, value));
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_for_utf8_path_is_borrowed() {
+        let path = PathBuf::from("Artist/Album/01 Track.flac");
+        let uri = SongInfo::uri_for_path(&path);
+        assert!(matches!(uri, Cow::Borrowed(_)));
+        assert_eq!(uri, "Artist/Album/01 Track.flac");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_uri_for_non_utf8_path_is_lossy() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        // "caf\xe9.mp3" as written by a Latin-1 system
+        let path = Path::new(OsStr::from_bytes(b"Artist/caf\xe9.mp3"));
+        let uri = SongInfo::uri_for_path(path);
+        assert!(matches!(uri, Cow::Owned(_)));
+        assert_eq!(uri, "Artist/caf\u{FFFD}.mp3");
+    }
+
+    #[test]
+    fn test_merge_tags_skips_duplicate_comments() {
+        let tags = vec![
+            ("Title".to_string(), "Song".to_string()),
+            ("Composer".to_string(), "Someone".to_string()),
+        ];
+        let comments = vec![
+            ("TITLE".to_string(), "Song".to_string()),
+            ("LABEL".to_string(), "Records".to_string()),
+        ];
+        let merged = merge_tags(tags, comments);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[2], ("LABEL".to_string(), "Records".to_string()));
+    }
+
+    #[test]
+    fn test_display_artists_joins_collaborations() {
+        let mut song = SongInfo {
+            title: "Song".to_string(),
+            artist: "A".to_string(),
+            artists: vec!["A".to_string()],
+            album: "Album".to_string(),
+            file_path: PathBuf::from("a.flac"),
+            ..SongInfo::default()
+        };
+        assert!(matches!(song.display_artists(", "), Cow::Borrowed("A")));
+
+        song.artists.push("B".to_string());
+        assert_eq!(song.display_artists(" & "), "A & B");
+    }
+
+    #[test]
+    fn test_from_fields_splits_songs_on_file() {
+        let fields = [
+            ("file", "a/01.flac"),
+            ("Title", "One"),
+            ("Artist", "A"),
+            ("Artist", "B"),
+            ("Track", "1/10"),
+            ("duration", "61.5"),
+            ("file", "b/02.flac"),
+            ("Album", "Two"),
+        ];
+        let songs = SongInfo::from_fields(fields, &TagSettings::default());
+        assert_eq!(songs.len(), 2);
+        assert_eq!(songs[0].title, "One");
+        assert_eq!(songs[0].artist, "A");
+        assert_eq!(songs[0].artists, vec!["A", "B"]);
+        assert_eq!(songs[0].track_number, 1);
+        assert_eq!(songs[0].duration, Some(Duration::from_millis(61500)));
+        assert_eq!(songs[1].file_path, PathBuf::from("b/02.flac"));
+        assert_eq!(songs[1].title, "Unknown Title");
+        assert_eq!(songs[1].album, "Two");
+    }
+
+    #[test]
+    fn test_replay_gain_from_comments() {
+        let gain = ReplayGain::from_comments([
+            ("replaygain_track_gain", "-6.48 dB"),
+            ("REPLAYGAIN_ALBUM_GAIN", "+1.20 dB"),
+            ("REPLAYGAIN_TRACK_PEAK", "0.98"),
+        ]);
+        assert_eq!(gain.track_gain, Some(-6.48));
+        assert_eq!(gain.album_gain, Some(1.2));
+        assert_eq!(gain.label(), "track -6.5 dB album +1.2 dB");
+
+        let untagged =
+            ReplayGain::from_comments([("ENCODER", "LAME"), ("REPLAYGAIN_TRACK_GAIN", "n/a")]);
+        assert_eq!(untagged, ReplayGain::default());
+        assert_eq!(untagged.label(), "untagged");
+    }
+
+    #[test]
+    fn test_strip_featured_keeps_main_artist() {
+        let patterns = vec![
+            " feat. ".to_string(),
+            " ft. ".to_string(),
+            " (feat.".to_string(),
+        ];
+        let strip = |name| strip_featured(name, &patterns);
+        assert_eq!(strip("Artist feat. Guest"), "Artist");
+        assert_eq!(strip("Artist FT. Guest & Other"), "Artist");
+        assert_eq!(strip("Artist (feat. Guest)"), "Artist");
+        assert_eq!(strip("Daft Punk"), "Daft Punk");
+        // The earliest credit wins when several match
+        assert_eq!(strip("A ft. B feat. C"), "A");
+        assert_eq!(strip(" feat. Guest"), " feat. Guest");
+    }
+
+    #[test]
+    fn test_title_from_path_drops_track_number() {
+        let title = |path: &str| title_from_path(Path::new(path));
+        assert_eq!(
+            title("Artist/Album/03 - Some_Song.flac").as_deref(),
+            Some("Some Song")
+        );
+        assert_eq!(title("1-03. Intro.mp3").as_deref(), Some("Intro"));
+        assert_eq!(
+            title("07_Closing_Time.ogg").as_deref(),
+            Some("Closing Time")
+        );
+        assert_eq!(title("Untitled.flac").as_deref(), Some("Untitled"));
+        // A name that is only a number stays as it is
+        assert_eq!(title("Album/1979.flac").as_deref(), Some("1979"));
+        assert_eq!(title(""), None);
+    }
 }
@@ -3,6 +3,9 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::DefaultTerminal;
+use ratatui_image::picker::{Picker, ProtocolType};
+
+use crate::app::ui::ImageSupport;
 
 /// Initialize the terminal for the application
 pub fn init_terminal() -> color_eyre::Result<DefaultTerminal> {
@@ -23,3 +26,37 @@ pub fn restore_terminal() -> color_eyre::Result<()> {
     disable_raw_mode()?;
     Ok(())
 }
+
+/// Detect how the terminal can display images.
+/// Dumb terminals are never queried; if querying fails we fall back to halfblocks.
+/// Must be called after entering the alternate screen but before reading terminal events.
+pub fn detect_image_support() -> (Picker, ImageSupport) {
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" {
+        log::info!(
+            "Terminal '{}' has no image support, using text placeholder",
+            term
+        );
+        return (Picker::from_fontsize((10, 20)), ImageSupport::Unsupported);
+    }
+
+    let picker = match Picker::from_query_stdio() {
+        Ok(picker) => picker,
+        Err(e) => {
+            log::warn!("Failed to query terminal image support: {}", e);
+            Picker::from_fontsize((10, 20))
+        }
+    };
+
+    let support = match picker.protocol_type() {
+        ProtocolType::Halfblocks => ImageSupport::Halfblocks,
+        _ => ImageSupport::Native,
+    };
+    log::info!(
+        "Image protocol: {:?} (TERM={})",
+        picker.protocol_type(),
+        term
+    );
+
+    (picker, support)
+}
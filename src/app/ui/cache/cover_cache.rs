@@ -5,34 +5,79 @@
 //! to provide instant cover art display when tracks change.
 
 use crate::app::SongInfo;
+use crate::app::song::TagSettings;
+use image::DynamicImage;
+use mpd_client::responses::Status;
 use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{RwLock, watch};
 
 /// Maximum number of cached cover art entries
 const MAX_CACHE_ENTRIES: usize = 20;
 
-/// Number of queue items to prefetch ahead/behind
-const PREFETCH_AHEAD: usize = 3;
-const PREFETCH_BEHIND: usize = 1;
+/// Maximum size of the decoded covers in the cache. Only reached with large covers
+/// (e.g. `max_dimension = 0`), the least recently used ones go first.
+const MAX_CACHE_BYTES: usize = 128 * 1024 * 1024;
 
-/// Cached cover art data
+/// Time left in a track at which the next track's cover is fetched again
+const NEAR_END_PREFETCH: Duration = Duration::from_secs(10);
+
+/// Key a cover is cached under
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CoverKey {
+    /// Cover of a single file
+    File(PathBuf),
+    /// Cover shared by every track of an album (album artist, album name)
+    Album(String, String),
+}
+
+impl CoverKey {
+    /// Key for a song's cover: its album when `by_album` is set and the song has
+    /// an album tag, otherwise its file
+    pub fn for_song(song: &SongInfo, by_album: bool, tags: &TagSettings) -> Self {
+        match song.album_key(tags) {
+            Some((artist, album)) if by_album => CoverKey::Album(artist, album),
+            _ => CoverKey::File(song.file_path.clone()),
+        }
+    }
+}
+
+/// Cached cover art
 #[derive(Debug, Clone)]
 pub struct CachedCover {
-    /// Raw image bytes (None means no cover art available)
-    pub data: Option<Vec<u8>>,
+    /// Decoded (and scaled down) cover, so a cache hit costs no decoding.
+    /// None means the song has no usable cover art.
+    pub image: Option<DynamicImage>,
+}
+
+/// What a loader should do about a key, see [`CoverArtCache::lookup`]
+#[derive(Debug)]
+pub enum CoverLookup {
+    /// The cover is cached (None = the song has no usable cover art)
+    Cached(Option<DynamicImage>),
+    /// Another task is fetching the cover. The receiver's `changed()` returns once
+    /// that fetch has been cached or given up, after which the key is looked up again.
+    Pending(watch::Receiver<()>),
+    /// Nobody has the cover yet. The caller is now marked as fetching it and must
+    /// [`insert`](CoverArtCache::insert) the result or
+    /// [`unmark_pending`](CoverArtCache::unmark_pending) the key.
+    Fetch,
 }
 
 /// Thread-safe cover art cache with LRU eviction
 #[derive(Debug)]
 pub struct CoverArtCache {
-    /// Map from file path to cached cover data
-    entries: HashMap<PathBuf, CachedCover>,
+    /// Map from cover key to cached cover data
+    entries: HashMap<CoverKey, CachedCover>,
     /// LRU order (front = oldest, back = most recent)
-    lru_order: VecDeque<PathBuf>,
-    /// Paths currently being fetched (to avoid duplicate requests)
-    pending: std::collections::HashSet<PathBuf>,
+    lru_order: VecDeque<CoverKey>,
+    /// Size of the decoded images in `entries`
+    bytes: usize,
+    /// Keys currently being fetched (to avoid duplicate requests). Dropping a sender
+    /// wakes the tasks waiting on that fetch.
+    pending: HashMap<CoverKey, watch::Sender<()>>,
     /// Cache statistics
     hits: u64,
     misses: u64,
@@ -44,14 +89,15 @@ impl CoverArtCache {
         Self {
             entries: HashMap::with_capacity(MAX_CACHE_ENTRIES),
             lru_order: VecDeque::with_capacity(MAX_CACHE_ENTRIES),
-            pending: std::collections::HashSet::new(),
+            bytes: 0,
+            pending: HashMap::new(),
             hits: 0,
             misses: 0,
         }
     }
 
-    /// Get cached cover art for a file path
-    pub fn get(&mut self, path: &PathBuf) -> Option<&CachedCover> {
+    /// Get cached cover art for a key
+    pub fn get(&mut self, path: &CoverKey) -> Option<&CachedCover> {
         if self.entries.contains_key(path) {
             self.hits += 1;
             // Move to back of LRU (most recently used)
@@ -64,47 +110,59 @@ impl CoverArtCache {
         }
     }
 
-    /// Check if a path is cached (without updating LRU or stats)
-    pub fn contains(&self, path: &PathBuf) -> bool {
+    /// Check if a key is cached (without updating LRU or stats)
+    pub fn contains(&self, path: &CoverKey) -> bool {
         self.entries.contains_key(path)
     }
 
-    /// Insert cover art into the cache
-    pub fn insert(&mut self, path: PathBuf, data: Option<Vec<u8>>) {
+    /// Insert cover art into the cache, evicting the least recently used covers to stay
+    /// within [`MAX_CACHE_ENTRIES`] and [`MAX_CACHE_BYTES`]
+    pub fn insert(&mut self, path: CoverKey, image: Option<DynamicImage>) {
         // Remove from pending
         self.pending.remove(&path);
 
-        // If already cached, just update and refresh LRU
-        if self.entries.contains_key(&path) {
+        // A cover cached before is replaced
+        if let Some(old) = self.entries.remove(&path) {
+            self.bytes -= image_bytes(&old.image);
             self.lru_order.retain(|p| p != &path);
-            self.lru_order.push_back(path.clone());
-            self.entries.insert(path, CachedCover { data });
-            return;
         }
 
-        // Evict oldest if at capacity
-        while self.entries.len() >= MAX_CACHE_ENTRIES {
-            if let Some(oldest) = self.lru_order.pop_front() {
-                self.entries.remove(&oldest);
-                log::debug!("Evicted cover art cache entry: {:?}", oldest);
-            } else {
+        // Evict oldest while over either limit; a single cover above the byte
+        // budget is still cached, on its own
+        let size = image_bytes(&image);
+        while self.entries.len() >= MAX_CACHE_ENTRIES || self.bytes + size > MAX_CACHE_BYTES {
+            let Some(oldest) = self.lru_order.pop_front() else {
                 break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.bytes -= image_bytes(&evicted.image);
             }
+            log::debug!("Evicted cover art cache entry: {:?}", oldest);
         }
 
         // Insert new entry
+        self.bytes += size;
         self.lru_order.push_back(path.clone());
-        self.entries.insert(path, CachedCover { data });
+        self.entries.insert(path, CachedCover { image });
     }
 
-    /// Mark a path as currently being fetched
-    pub fn mark_pending(&mut self, path: PathBuf) {
-        self.pending.insert(path);
+    /// Look up `path`: its cached cover, the fetch already running for it, or
+    /// (marking it pending) the go-ahead to fetch it
+    pub fn lookup(&mut self, path: &CoverKey) -> CoverLookup {
+        if let Some(cached) = self.get(path) {
+            return CoverLookup::Cached(cached.image.clone());
+        }
+        if let Some(fetch) = self.pending.get(path) {
+            return CoverLookup::Pending(fetch.subscribe());
+        }
+        self.pending.insert(path.clone(), watch::channel(()).0);
+        CoverLookup::Fetch
     }
 
-    /// Check if a path is pending fetch
-    pub fn is_pending(&self, path: &PathBuf) -> bool {
-        self.pending.contains(path)
+    /// Forget that a key is being fetched, when the fetch was given up on.
+    /// Tasks waiting on it look the key up again and fetch it themselves.
+    pub fn unmark_pending(&mut self, path: &CoverKey) {
+        self.pending.remove(path);
     }
 
     /// Get cache statistics
@@ -133,10 +191,16 @@ impl CoverArtCache {
     pub fn clear(&mut self) {
         self.entries.clear();
         self.lru_order.clear();
+        self.bytes = 0;
         self.pending.clear();
     }
 }
 
+/// Memory taken by a decoded cover
+fn image_bytes(image: &Option<DynamicImage>) -> usize {
+    image.as_ref().map_or(0, |image| image.as_bytes().len())
+}
+
 impl Default for CoverArtCache {
     fn default() -> Self {
         Self::new()
@@ -151,52 +215,153 @@ pub fn new_shared_cache() -> SharedCoverCache {
     Arc::new(RwLock::new(CoverArtCache::new()))
 }
 
-/// Determine which queue items should be prefetched based on current position
-pub fn get_prefetch_targets(queue: &[SongInfo], current_index: Option<usize>) -> Vec<PathBuf> {
+/// Determine which queue items should be prefetched based on current position: up to
+/// `count` on either side, as many as the cache holds next to the current cover.
+/// With `consume` on, played songs leave the queue and there is nothing to go back
+/// to, so both sides' share goes to the songs ahead.
+pub fn get_prefetch_targets(
+    queue: &[SongInfo],
+    current_index: Option<usize>,
+    count: usize,
+    consume: bool,
+) -> Vec<&SongInfo> {
     let mut targets = Vec::new();
 
     let Some(current_idx) = current_index else {
         return targets;
     };
+    let count = count.min((MAX_CACHE_ENTRIES - 1) / 2);
+    let (ahead, behind) = if consume {
+        (count * 2, 0)
+    } else {
+        (count, count)
+    };
 
     // Prefetch ahead
-    for i in 1..=PREFETCH_AHEAD {
+    for i in 1..=ahead {
         let idx = current_idx.saturating_add(i);
         if idx < queue.len() {
-            targets.push(queue[idx].file_path.clone());
+            targets.push(&queue[idx]);
         }
     }
 
     // Prefetch behind (for going back)
-    for i in 1..=PREFETCH_BEHIND {
+    for i in 1..=behind {
         if let Some(idx) = current_idx.checked_sub(i) {
-            targets.push(queue[idx].file_path.clone());
+            targets.push(&queue[idx]);
         }
     }
 
     targets
 }
 
-/// Find the current song's index in the queue
-pub fn find_current_index(queue: &[SongInfo], current_song: &Option<SongInfo>) -> Option<usize> {
+/// Check if playback is close enough to the end of the track to warm the next cover.
+/// Tracks without a known length (streams) never are.
+pub fn is_near_track_end(elapsed: Option<Duration>, duration: Option<Duration>) -> bool {
+    match (elapsed, duration) {
+        (Some(elapsed), Some(duration)) if !duration.is_zero() => {
+            duration.saturating_sub(elapsed) <= NEAR_END_PREFETCH
+        }
+        _ => false,
+    }
+}
+
+/// Find the current song's index in the queue: the position MPD reports when it holds
+/// the current song, else the first entry of the same file. Positions shift whenever
+/// songs before the current one are removed (as in consume mode), and a file can be
+/// queued more than once, so the position is only trusted when it matches.
+pub fn find_current_index(
+    queue: &[SongInfo],
+    status: Option<&Status>,
+    current_song: &Option<SongInfo>,
+) -> Option<usize> {
     let current = current_song.as_ref()?;
-    queue.iter().position(|s| s.file_path == current.file_path)
+    status
+        .and_then(|status| status.current_song)
+        .map(|(position, _)| position.0)
+        .filter(|&index| {
+            queue
+                .get(index)
+                .is_some_and(|song| song.file_path == current.file_path)
+        })
+        .or_else(|| queue.iter().position(|s| s.file_path == current.file_path))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn song(file: &str) -> SongInfo {
+        SongInfo {
+            title: file.to_string(),
+            artist: "Artist".to_string(),
+            artists: vec!["Artist".to_string()],
+            album: "Album".to_string(),
+            file_path: PathBuf::from(file),
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_near_track_end() {
+        let secs = Duration::from_secs;
+        assert!(!is_near_track_end(Some(secs(100)), Some(secs(200))));
+        assert!(is_near_track_end(Some(secs(190)), Some(secs(200))));
+        assert!(is_near_track_end(Some(secs(205)), Some(secs(200))));
+
+        // Streams have no length, so there is no end to be near
+        assert!(!is_near_track_end(Some(secs(5)), None));
+        assert!(!is_near_track_end(Some(secs(5)), Some(Duration::ZERO)));
+        assert!(!is_near_track_end(None, Some(secs(5))));
+    }
+
+    #[test]
+    fn test_prefetch_targets_window() {
+        let queue: Vec<SongInfo> = (0..30).map(|i| song(&format!("{}.flac", i))).collect();
+        let files = |targets: Vec<&SongInfo>| -> Vec<String> {
+            targets
+                .iter()
+                .map(|song| song.file_path.display().to_string())
+                .collect()
+        };
+
+        // Ahead first, then behind, clipped at the ends of the queue
+        assert_eq!(
+            files(get_prefetch_targets(&queue, Some(1), 2, false)),
+            vec!["2.flac", "3.flac", "0.flac"]
+        );
+        assert!(get_prefetch_targets(&queue, Some(1), 0, false).is_empty());
+        assert!(get_prefetch_targets(&queue, None, 2, false).is_empty());
+
+        // Never more than fit in the cache beside the current cover
+        assert_eq!(get_prefetch_targets(&queue, Some(15), 100, false).len(), 18);
+
+        // Consumed songs don't come back, so the songs ahead get the whole share
+        assert_eq!(
+            files(get_prefetch_targets(&queue, Some(1), 2, true)),
+            vec!["2.flac", "3.flac", "4.flac", "5.flac"]
+        );
+        assert_eq!(get_prefetch_targets(&queue, Some(5), 100, true).len(), 18);
+    }
+
+    fn cover(width: u32) -> Option<DynamicImage> {
+        Some(DynamicImage::new_rgb8(width, 1))
+    }
+
+    fn width(cached: &CachedCover) -> Option<u32> {
+        cached.image.as_ref().map(DynamicImage::width)
+    }
+
     #[test]
     fn test_cache_insert_and_get() {
         let mut cache = CoverArtCache::new();
-        let path = PathBuf::from("/music/song.mp3");
+        let path = CoverKey::File(PathBuf::from("/music/song.mp3"));
 
-        cache.insert(path.clone(), Some(vec![1, 2, 3]));
+        cache.insert(path.clone(), cover(3));
 
         let cached = cache.get(&path);
         assert!(cached.is_some());
-        assert_eq!(cached.unwrap().data, Some(vec![1, 2, 3]));
+        assert_eq!(width(cached.unwrap()), Some(3));
     }
 
     #[test]
@@ -205,46 +370,119 @@ mod tests {
 
         // Fill cache beyond capacity
         for i in 0..(MAX_CACHE_ENTRIES + 5) {
-            let path = PathBuf::from(format!("/music/song{}.mp3", i));
-            cache.insert(path, Some(vec![i as u8]));
+            let path = CoverKey::File(PathBuf::from(format!("/music/song{}.mp3", i)));
+            cache.insert(path, cover(i as u32 + 1));
         }
 
         // Should have evicted oldest entries
         assert_eq!(cache.entries.len(), MAX_CACHE_ENTRIES);
 
         // First entries should be evicted
-        assert!(!cache.contains(&PathBuf::from("/music/song0.mp3")));
-        assert!(!cache.contains(&PathBuf::from("/music/song4.mp3")));
+        assert!(!cache.contains(&CoverKey::File(PathBuf::from("/music/song0.mp3"))));
+        assert!(!cache.contains(&CoverKey::File(PathBuf::from("/music/song4.mp3"))));
 
         // Last entries should still be present
         let last_idx = MAX_CACHE_ENTRIES + 4;
-        assert!(cache.contains(&PathBuf::from(format!("/music/song{}.mp3", last_idx))));
+        assert!(cache.contains(&CoverKey::File(PathBuf::from(format!(
+            "/music/song{}.mp3",
+            last_idx
+        )))));
+    }
+
+    #[test]
+    fn test_cache_stays_within_byte_budget() {
+        let mut cache = CoverArtCache::new();
+        let key = |i: usize| CoverKey::File(PathBuf::from(format!("/music/big{}.flac", i)));
+        // 3000x3000 RGB, about 27 MB each: only four fit
+        for i in 0..6 {
+            cache.insert(key(i), Some(DynamicImage::new_rgb8(3000, 3000)));
+        }
+        assert!(cache.bytes <= MAX_CACHE_BYTES);
+        assert_eq!(cache.entries.len(), 4);
+        assert!(!cache.contains(&key(1)));
+        assert!(cache.contains(&key(5)));
+
+        // Replacing a cover accounts for the old one
+        cache.insert(key(5), None);
+        assert_eq!(cache.bytes, 3 * 3000 * 3000 * 3);
     }
 
     #[test]
     fn test_cache_none_data() {
         let mut cache = CoverArtCache::new();
-        let path = PathBuf::from("/music/no_cover.mp3");
+        let path = CoverKey::File(PathBuf::from("/music/no_cover.mp3"));
 
         // Should be able to cache "no cover" result
         cache.insert(path.clone(), None);
 
         let cached = cache.get(&path);
         assert!(cached.is_some());
-        assert_eq!(cached.unwrap().data, None);
+        assert!(cached.unwrap().image.is_none());
     }
 
     #[test]
     fn test_pending_tracking() {
         let mut cache = CoverArtCache::new();
-        let path = PathBuf::from("/music/song.mp3");
+        let path = CoverKey::File(PathBuf::from("/music/song.mp3"));
 
-        assert!(!cache.is_pending(&path));
-        cache.mark_pending(path.clone());
-        assert!(cache.is_pending(&path));
+        // The first lookup takes on the fetch, later ones wait for it
+        assert!(matches!(cache.lookup(&path), CoverLookup::Fetch));
+        assert!(matches!(cache.lookup(&path), CoverLookup::Pending(_)));
 
         // Insert clears pending
-        cache.insert(path.clone(), Some(vec![]));
-        assert!(!cache.is_pending(&path));
+        cache.insert(path.clone(), cover(1));
+        assert!(matches!(cache.lookup(&path), CoverLookup::Cached(Some(_))));
+
+        // So does giving up on the fetch, without caching anything
+        let other = CoverKey::File(PathBuf::from("/music/other.mp3"));
+        assert!(matches!(cache.lookup(&other), CoverLookup::Fetch));
+        cache.unmark_pending(&other);
+        assert!(!cache.contains(&other));
+        assert!(matches!(cache.lookup(&other), CoverLookup::Fetch));
+    }
+
+    #[test]
+    fn test_tracks_of_an_album_wait_for_the_pending_fetch() {
+        let mut cache = CoverArtCache::new();
+        let tags = TagSettings::default();
+        let first = CoverKey::for_song(&song("album/01.flac"), true, &tags);
+        let second = CoverKey::for_song(&song("album/02.flac"), true, &tags);
+        assert_eq!(first, second);
+
+        assert!(matches!(cache.lookup(&first), CoverLookup::Fetch));
+        let CoverLookup::Pending(done) = cache.lookup(&second) else {
+            panic!("second track should wait for the album's fetch");
+        };
+        assert!(done.has_changed().is_ok());
+
+        // The waiting track is woken once the cover is cached, and then finds it
+        cache.insert(first, cover(5));
+        assert!(done.has_changed().is_err());
+        assert!(matches!(
+            cache.lookup(&second),
+            CoverLookup::Cached(Some(image)) if image.width() == 5
+        ));
+
+        // A fetch that is given up on wakes the waiters too, and the next one fetches
+        let key = CoverKey::Album("Artist".to_string(), "Other".to_string());
+        assert!(matches!(cache.lookup(&key), CoverLookup::Fetch));
+        let CoverLookup::Pending(done) = cache.lookup(&key) else {
+            panic!("fetch should be pending");
+        };
+        cache.unmark_pending(&key);
+        assert!(done.has_changed().is_err());
+        assert!(matches!(cache.lookup(&key), CoverLookup::Fetch));
+    }
+
+    #[test]
+    fn test_album_key_shared_between_tracks() {
+        let mut cache = CoverArtCache::new();
+        let key = CoverKey::Album("Artist".to_string(), "Album".to_string());
+
+        cache.insert(key.clone(), cover(7));
+
+        // Any track of the album resolves to the same entry
+        assert_eq!(cache.get(&key).and_then(width), Some(7));
+        assert!(!cache.contains(&CoverKey::File(PathBuf::from("/music/album/01.flac"))));
     }
 }
@@ -226,6 +226,24 @@ impl DirtyFlags {
             || self.status_message.get()
     }
 
+    /// Check if the progress bar is the only dirty region (a plain clock tick)
+    #[inline]
+    pub fn is_progress_only(&self) -> bool {
+        self.progress.get()
+            && !(self.force_full.get()
+                || self.terminal_size.get()
+                || self.queue.get()
+                || self.queue_selection.get()
+                || self.current_song.get()
+                || self.status.get()
+                || self.cover_art.get()
+                || self.library.get()
+                || self.menu_mode.get()
+                || self.panel_focus.get()
+                || self.key_sequence.get()
+                || self.status_message.get())
+    }
+
     /// Check if a full redraw is needed (terminal resize, mode change, etc.)
     #[inline]
     pub fn needs_full_redraw(&self) -> bool {
@@ -322,4 +340,19 @@ mod tests {
         assert!(flags.is_status_dirty());
         assert!(flags.is_cover_art_dirty());
     }
+
+    #[test]
+    fn test_progress_only() {
+        let flags = DirtyFlags::new();
+        assert!(!flags.is_progress_only());
+        flags.clear_all();
+        assert!(!flags.is_progress_only());
+
+        flags.mark_progress();
+        assert!(flags.is_progress_only());
+
+        // Anything else changing at the same time needs the whole frame
+        flags.mark_queue_selection();
+        assert!(!flags.is_progress_only());
+    }
 }
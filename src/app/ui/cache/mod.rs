@@ -1,8 +1,10 @@
 pub mod cover_cache;
 pub mod dirty;
+pub mod queued;
 pub mod render_cache;
 pub mod width_cache;
 
 pub use dirty::DirtyFlags;
+pub use queued::QueuedSet;
 pub use render_cache::RenderCache;
 pub use width_cache::WidthCache;
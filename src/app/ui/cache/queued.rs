@@ -0,0 +1,115 @@
+//! Lookup of which library items are already in the MPD queue.
+//!
+//! Rebuilt once per queue update so the library views can mark queued
+//! artists, albums and tracks without scanning the queue for every row.
+
+use crate::app::SongInfo;
+use crate::app::config::mpd::GroupBy;
+use crate::app::song::TagSettings;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Files and library artists currently present in the queue
+#[derive(Debug, Default)]
+pub struct QueuedSet {
+    files: HashSet<PathBuf>,
+    artists: HashSet<String>,
+}
+
+impl QueuedSet {
+    /// Rebuild the lookup from the current queue, with artists named the way the
+    /// library groups them by `group_by`
+    pub fn rebuild(&mut self, queue: &[SongInfo], group_by: GroupBy, tags: &TagSettings) {
+        self.files.clear();
+        self.artists.clear();
+        for song in queue {
+            self.files.insert(song.file_path.clone());
+            self.artists
+                .insert(song.library_artist(group_by, tags).to_string());
+        }
+    }
+
+    /// Whether this file is in the queue
+    pub fn contains_file(&self, file_path: &Path) -> bool {
+        self.files.contains(file_path)
+    }
+
+    /// Whether any of these tracks is in the queue
+    pub fn contains_any(&self, tracks: &[SongInfo]) -> bool {
+        !self.files.is_empty() && tracks.iter().any(|t| self.contains_file(&t.file_path))
+    }
+
+    /// Whether any song listed under this library artist is in the queue.
+    /// Matched by name so it also works for artists whose albums aren't loaded yet.
+    pub fn contains_artist(&self, name: &str) -> bool {
+        self.artists.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(path: &str, album_artist: Option<&str>) -> SongInfo {
+        SongInfo {
+            artist: "Track Artist".to_string(),
+            artists: vec!["Track Artist".to_string()],
+            album_artist: album_artist.map(str::to_string),
+            file_path: PathBuf::from(path),
+            ..SongInfo::default()
+        }
+    }
+
+    fn rebuild(queued: &mut QueuedSet, queue: &[SongInfo], group_by: GroupBy) {
+        queued.rebuild(queue, group_by, &TagSettings::default());
+    }
+
+    #[test]
+    fn test_rebuild_replaces_previous_queue() {
+        let mut queued = QueuedSet::default();
+        rebuild(
+            &mut queued,
+            &[song("a.flac", Some("Artist A"))],
+            GroupBy::AlbumArtist,
+        );
+        assert!(queued.contains_file(Path::new("a.flac")));
+        assert!(queued.contains_artist("Artist A"));
+
+        rebuild(
+            &mut queued,
+            &[song("b.flac", Some("Artist B"))],
+            GroupBy::AlbumArtist,
+        );
+        assert!(!queued.contains_file(Path::new("a.flac")));
+        assert!(!queued.contains_artist("Artist A"));
+        assert!(queued.contains_file(Path::new("b.flac")));
+    }
+
+    #[test]
+    fn test_artists_follow_library_grouping() {
+        let mut queued = QueuedSet::default();
+        let queue = [song("a.flac", Some("Various Artists"))];
+
+        // Listed under the album artist, or under the track artist when grouping by artist
+        rebuild(&mut queued, &queue, GroupBy::AlbumArtist);
+        assert!(queued.contains_artist("Various Artists"));
+        assert!(!queued.contains_artist("Track Artist"));
+        rebuild(&mut queued, &queue, GroupBy::Artist);
+        assert!(queued.contains_artist("Track Artist"));
+        assert!(!queued.contains_artist("Various Artists"));
+
+        // Without an AlbumArtist tag the library lists the song under its artist
+        rebuild(&mut queued, &[song("b.flac", None)], GroupBy::AlbumArtist);
+        assert!(queued.contains_artist("Track Artist"));
+    }
+
+    #[test]
+    fn test_contains_any() {
+        let mut queued = QueuedSet::default();
+        let tracks = [song("a.flac", None), song("b.flac", None)];
+        assert!(!queued.contains_any(&tracks));
+
+        rebuild(&mut queued, &[song("b.flac", None)], GroupBy::AlbumArtist);
+        assert!(queued.contains_any(&tracks));
+    }
+}
@@ -4,6 +4,10 @@
 //! but rarely change (e.g., formatted durations, progress bars, fillers).
 
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::app::config::ui::FmtOpts;
+use crate::app::ui::rendering::utils::format_duration;
 
 /// Maximum width for pre-generated filler strings
 const MAX_FILLER_WIDTH: usize = 256;
@@ -149,10 +153,8 @@ impl FileTypeCache {
 /// Cache for formatted duration strings
 #[derive(Debug, Default)]
 pub struct DurationCache {
-    /// Short format: seconds -> "M:SS" or "MM:SS"
-    short: HashMap<u64, String>,
-    /// Long format: seconds -> "H:MM:SS"
-    long: HashMap<u64, String>,
+    /// Configurable format: (seconds, options) -> see `format_duration`
+    formatted: HashMap<(u64, FmtOpts), String>,
     /// With prefix spaces: seconds -> "  M:SS"
     prefixed: HashMap<u64, String>,
 }
@@ -163,29 +165,13 @@ impl DurationCache {
         Self::default()
     }
 
-    /// Get short format duration (M:SS or MM:SS)
-    /// Used for song durations in queue, track lists, etc.
-    pub fn format_short(&mut self, secs: u64) -> &str {
-        self.short.entry(secs).or_insert_with(|| {
-            let m = secs / 60;
-            let s = secs % 60;
-            format!("{}:{:02}", m, s)
-        })
-    }
-
-    /// Get long format duration (H:MM:SS or M:SS)
-    /// Used for album total durations
-    pub fn format_long(&mut self, secs: u64) -> &str {
-        self.long.entry(secs).or_insert_with(|| {
-            let h = secs / 3600;
-            let m = (secs % 3600) / 60;
-            let s = secs % 60;
-            if h > 0 {
-                format!("{}:{}:{:02}", h, m, s)
-            } else {
-                format!("{}:{:02}", m, s)
-            }
-        })
+    /// Get a duration in the configured format (see `UiConfig::time_format`)
+    /// Used for every duration in the UI: queue, track lists, progress and runtimes
+    pub fn format(&mut self, duration: Duration, opts: FmtOpts) -> &str {
+        let secs = duration.as_secs();
+        self.formatted
+            .entry((secs, opts))
+            .or_insert_with(|| format_duration(Duration::from_secs(secs), opts))
     }
 
     /// Get duration with prefix spaces (  M:SS)
@@ -202,13 +188,13 @@ impl DurationCache {
     /// Get the number of cached entries
     #[allow(dead_code)]
     pub fn len(&self) -> usize {
-        self.short.len() + self.long.len() + self.prefixed.len()
+        self.formatted.len() + self.prefixed.len()
     }
 
     /// Check if cache is empty
     #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
-        self.short.is_empty() && self.long.is_empty() && self.prefixed.is_empty()
+        self.formatted.is_empty() && self.prefixed.is_empty()
     }
 }
 
@@ -303,6 +289,7 @@ impl RenderCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::config::ui::HourDisplay;
 
     #[test]
     fn test_filler_cache() {
@@ -322,15 +309,17 @@ mod tests {
     fn test_duration_cache() {
         let mut cache = DurationCache::new();
 
-        assert_eq!(cache.format_short(0), "0:00");
-        assert_eq!(cache.format_short(65), "1:05");
-        assert_eq!(cache.format_short(3661), "61:01");
-
-        assert_eq!(cache.format_long(65), "1:05");
-        assert_eq!(cache.format_long(3661), "1:1:01");
-        assert_eq!(cache.format_long(7322), "2:2:02");
-
         assert_eq!(cache.format_prefixed(65), "  1:05");
+
+        let padded = FmtOpts {
+            hours: HourDisplay::Always,
+            pad: true,
+        };
+        assert_eq!(cache.format(Duration::from_secs(65), padded), "00:01:05");
+        assert_eq!(
+            cache.format(Duration::from_millis(65_900), padded),
+            "00:01:05"
+        );
     }
 
     #[test]
@@ -382,16 +371,21 @@ mod tests {
     fn test_cache_reuse() {
         let mut cache = DurationCache::new();
 
+        let opts = FmtOpts {
+            hours: HourDisplay::Auto,
+            pad: false,
+        };
+
         // First access computes
-        let _ = cache.format_short(120);
-        assert_eq!(cache.short.len(), 1);
+        let _ = cache.format(Duration::from_secs(120), opts);
+        assert_eq!(cache.formatted.len(), 1);
 
         // Second access should reuse
-        let _ = cache.format_short(120);
-        assert_eq!(cache.short.len(), 1);
+        let _ = cache.format(Duration::from_secs(120), opts);
+        assert_eq!(cache.formatted.len(), 1);
 
         // Different value adds to cache
-        let _ = cache.format_short(180);
-        assert_eq!(cache.short.len(), 2);
+        let _ = cache.format(Duration::from_secs(180), opts);
+        assert_eq!(cache.formatted.len(), 2);
     }
 }
@@ -4,12 +4,15 @@ pub mod views;
 pub mod widgets;
 
 pub use cache::DirtyFlags;
+pub use cache::QueuedSet;
 pub use cache::RenderCache;
 pub use cache::WidthCache;
-pub use rendering::{AlbumDisplayCache, DisplayItem, Protocol, compute_album_display_list};
+pub use rendering::{
+    AlbumDisplayCache, DisplayItem, ImageSupport, Protocol, compute_album_display_list,
+};
 pub use views::{MenuMode, PanelFocus};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 thread_local! {
     /// Global width cache for Unicode string width calculations
@@ -23,4 +26,7 @@ thread_local! {
     /// Global album display list cache
     /// Caches computed display lists to avoid recomputation each frame
     pub static ALBUM_DISPLAY_CACHE: RefCell<AlbumDisplayCache> = RefCell::new(AlbumDisplayCache::new());
+
+    /// Rows visible in the last rendered list, the step for page-wise navigation
+    pub static LIST_HEIGHT: Cell<usize> = const { Cell::new(0) };
 }
@@ -1,5 +1,7 @@
 pub mod renderer;
 pub mod utils;
 
-pub use renderer::render;
-pub use utils::{AlbumDisplayCache, DisplayItem, Protocol, compute_album_display_list};
+pub use renderer::{render, render_loading_screen, render_progress_only};
+pub use utils::{
+    AlbumDisplayCache, DisplayItem, ImageSupport, Protocol, compute_album_display_list,
+};
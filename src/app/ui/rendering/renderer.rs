@@ -1,5 +1,7 @@
+use chrono::Timelike;
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Alignment, Rect},
     style::{Style, Stylize},
     text::{Line, Span},
@@ -9,14 +11,29 @@ use ratatui::{
 use crate::app::Config;
 use crate::app::KeyBinds;
 use crate::app::MessageType;
-use crate::app::ui::Protocol;
+use crate::app::Toast;
+use crate::app::binds_handler::key_display;
+use crate::app::main_loop::SleepTimer;
+use crate::app::navigation::file_browser::FileBrowser;
+use crate::app::navigation::overlay::Overlay;
+use crate::app::navigation::queue_filter::QueueFilter;
+use crate::app::song::LoadProgress;
+use crate::app::ui::rendering::utils;
 use crate::app::ui::views::{
-    albums::render_albums_mode, artists::render_artists_mode, queue::render_queue_mode,
+    albums::render_albums_mode, artists::render_artists_mode, files::render_files_mode,
+    now_playing::render_now_playing_mode, queue::render_queue_mode,
 };
+use crate::app::ui::widgets::{create_left_box_bottom, render_toast};
+use crate::app::ui::{Protocol, QueuedSet};
 use crate::app::{LazyLibrary, SongInfo};
 use crate::app::{MenuMode, PanelFocus};
 use unicode_width::UnicodeWidthStr;
 
+/// Smallest terminal width the normal UI is drawn at
+const MIN_TERMINAL_WIDTH: u16 = 60;
+/// Smallest terminal height the normal UI is drawn at
+const MIN_TERMINAL_HEIGHT: u16 = 15;
+
 /// Render status 0n top-right corner (key sequence or status message)
 /// Returns true if something was rendered (so loading indicator can skip)
 fn render_top_right_status(
@@ -55,6 +72,7 @@ fn get_status_message_text(msg: &crate::app::StatusMessage) -> Option<String> {
                 _ => "Updating...",
             }
         }
+        MessageType::Success if !msg.text.is_empty() => &msg.text,
         MessageType::Success => "Updated!  ",
         MessageType::Error => &msg.text,
     };
@@ -64,48 +82,16 @@ fn get_status_message_text(msg: &crate::app::StatusMessage) -> Option<String> {
 /// Get the current key sequence as displayable text
 fn get_key_sequence_text(key_binds: &KeyBinds) -> Option<String> {
     let sequence = key_binds.get_current_sequence();
-    if sequence.is_empty() {
+    let count = key_binds.pending_count();
+    if sequence.is_empty() && count.is_none() {
         return None;
     }
 
-    // Convert key sequence to display string
-    let sequence_text: String = sequence
-        .iter()
-        .map(|(modifiers, key_code)| {
-            let key_str = match key_code {
-                crossterm::event::KeyCode::Char(c) => c.to_string(),
-                crossterm::event::KeyCode::Esc => "Esc".to_string(),
-                crossterm::event::KeyCode::Enter => "Enter".to_string(),
-                crossterm::event::KeyCode::Backspace => "Backspace".to_string(),
-                crossterm::event::KeyCode::Tab => "Tab".to_string(),
-                crossterm::event::KeyCode::Delete => "Delete".to_string(),
-                crossterm::event::KeyCode::Insert => "Insert".to_string(),
-                crossterm::event::KeyCode::Home => "Home".to_string(),
-                crossterm::event::KeyCode::End => "End".to_string(),
-                crossterm::event::KeyCode::PageUp => "PageUp".to_string(),
-                crossterm::event::KeyCode::PageDown => "PageDown".to_string(),
-                crossterm::event::KeyCode::Up => "↑".to_string(),
-                crossterm::event::KeyCode::Down => "↓".to_string(),
-                crossterm::event::KeyCode::Left => "←".to_string(),
-                crossterm::event::KeyCode::Right => "→".to_string(),
-                crossterm::event::KeyCode::F(n) => format!("F{}", n),
-                _ => format!("{:?}", key_code),
-            };
-
-            // Add modifier prefixes
-            let mut result = String::new();
-            if modifiers.contains(crossterm::event::KeyModifiers::CONTROL) {
-                result.push_str("Ctrl+");
-            }
-            if modifiers.contains(crossterm::event::KeyModifiers::ALT) {
-                result.push_str("Alt+");
-            }
-            if modifiers.contains(crossterm::event::KeyModifiers::SHIFT) {
-                result.push_str("Shift+");
-            }
-            result.push_str(&key_str);
-            result
-        })
+    // A pending count is shown in front of the keys, like vim does
+    let sequence_text: String = count
+        .map(|count| count.to_string())
+        .into_iter()
+        .chain(sequence.into_iter().map(key_display))
         .collect::<Vec<_>>()
         .join(" → ");
     Some(sequence_text)
@@ -248,7 +234,272 @@ fn render_config_warnings_popup(frame: &mut Frame, warnings: &[String], config:
     frame.render_widget(popup_text, popup_area);
 }
 
+/// Split the key hint line off the bottom of `area` when hints are turned on,
+/// returning the area left for the view and the hint line
+fn split_key_hints(area: Rect, config: &Config) -> (Rect, Option<Rect>) {
+    if !config.ui.key_hints || area.height == 0 {
+        return (area, None);
+    }
+    let view = Rect {
+        height: area.height - 1,
+        ..area
+    };
+    let hints = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+    (view, Some(hints))
+}
+
+/// Render the key hints of the current view, dropping the ones that don't fit
+fn render_key_hints(frame: &mut Frame, hints: &[(String, &str)], area: Rect, config: &Config) {
+    let mut spans = vec![Span::raw(" ")];
+    let mut width = 1;
+    for (key, label) in hints {
+        let hint_width = key.width() + label.width() + 4;
+        if width + hint_width > area.width as usize {
+            break;
+        }
+        width += hint_width;
+        spans.push(Span::styled(
+            key.clone(),
+            Style::default().fg(config.colors.top_accent_color()),
+        ));
+        spans.push(Span::styled(
+            format!(": {}  ", label),
+            Style::default().fg(config.colors.track_duration_color()),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Render a centered notice asking for a bigger terminal
+fn render_terminal_too_small(frame: &mut Frame, area: Rect, config: &Config) {
+    let text = format!(
+        "Terminal too small (need {}x{})",
+        MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+
+    let message_area = Rect {
+        x: area.x,
+        y: area.y + area.height / 2,
+        width: area.width,
+        height: area.height.min(1),
+    };
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.song_title_color()),
+        ))
+        .alignment(Alignment::Center),
+        message_area,
+    );
+}
+
+/// Spinner frames of the loading screen, advanced by the caller
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Render the startup loading screen: a spinner and the current library load phase
+pub fn render_loading_screen(
+    frame: &mut Frame,
+    phase: &LoadProgress,
+    spinner_frame: usize,
+    config: &Config,
+) {
+    let area = frame.area();
+    let spinner = SPINNER_FRAMES[spinner_frame % SPINNER_FRAMES.len()];
+
+    let message_area = Rect {
+        x: area.x,
+        y: area.y + area.height / 2,
+        width: area.width,
+        height: area.height.min(1),
+    };
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", spinner),
+                Style::default().fg(config.colors.top_accent_color()),
+            ),
+            Span::styled(
+                phase.to_string(),
+                Style::default().fg(config.colors.song_title_color()),
+            ),
+        ]))
+        .alignment(Alignment::Center),
+        message_area,
+    );
+}
+
+/// Sleep timer countdown as shown on the bottom row
+fn sleep_timer_text(timer: &SleepTimer, config: &Config) -> String {
+    format!(
+        " 󰒲 {} ",
+        utils::format_duration(timer.remaining(), config.ui.time_format())
+    )
+}
+
+/// Render the remaining sleep timer time right-aligned on the bottom row
+fn render_sleep_timer_countdown(
+    frame: &mut Frame,
+    timer: &SleepTimer,
+    area: Rect,
+    config: &Config,
+) {
+    let text = sleep_timer_text(timer, config);
+    let text_width = text.width() as u16;
+
+    if area.width < text_width + 4 || area.height == 0 {
+        return;
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.top_accent_color()),
+        )),
+        Rect {
+            x: area.x + area.width - text_width - 2,
+            y: area.y + area.height - 1,
+            width: text_width,
+            height: 1,
+        },
+    );
+}
+
+/// Render the current time right-aligned on the bottom row, left of the sleep timer
+/// countdown when there is one
+fn render_clock(frame: &mut Frame, sleep_timer: &Option<SleepTimer>, area: Rect, config: &Config) {
+    let Some(format) = config.ui.clock_format() else {
+        return;
+    };
+    let now = chrono::Local::now();
+    let text = format!(
+        " 󰥔 {} ",
+        utils::format_clock(now.hour(), now.minute(), format)
+    );
+    let text_width = text.width() as u16;
+    let timer_width = sleep_timer
+        .as_ref()
+        .map_or(0, |timer| sleep_timer_text(timer, config).width() as u16);
+
+    if area.width < text_width + timer_width + 4 || area.height == 0 {
+        return;
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.top_accent_color()),
+        )),
+        Rect {
+            x: area.x + area.width - timer_width - text_width - 2,
+            y: area.y + area.height - 1,
+            width: text_width,
+            height: 1,
+        },
+    );
+}
+
+/// Render the active partition left-aligned on the bottom row, returning its width
+fn render_partition_indicator(
+    frame: &mut Frame,
+    partition: &str,
+    area: Rect,
+    config: &Config,
+) -> u16 {
+    let text = format!(" 󰓃 {} ", partition);
+    let text_width = text.width() as u16;
+
+    if area.width < text_width + 4 || area.height == 0 {
+        return 0;
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.top_accent_color()),
+        )),
+        Rect {
+            x: area.x + 2,
+            y: area.y + area.height - 1,
+            width: text_width,
+            height: 1,
+        },
+    );
+    text_width
+}
+
+/// Point out on the bottom row, right of the `left` cells of the indicators before it,
+/// that consume mode is on. The queue view explains that played songs are removed.
+/// Returns the width taken, 0 when it doesn't fit.
+fn render_consume_indicator(
+    frame: &mut Frame,
+    left: u16,
+    area: Rect,
+    queue_view: bool,
+    config: &Config,
+) -> u16 {
+    let text = if queue_view {
+        "  consume: played songs leave the queue "
+    } else {
+        "  consume "
+    };
+    let text_width = text.width() as u16;
+
+    if area.width < left + text_width + 4 || area.height == 0 {
+        return 0;
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.top_accent_color()),
+        )),
+        Rect {
+            x: area.x + 2 + left,
+            y: area.y + area.height - 1,
+            width: text_width,
+            height: 1,
+        },
+    );
+    text_width
+}
+
+/// Point out on the bottom row, right of the `left` cells of the indicators before it,
+/// that the library no longer matches MPD's database
+fn render_library_stale_indicator(frame: &mut Frame, left: u16, area: Rect, config: &Config) {
+    let text = match config.binds.refresh.first() {
+        Some(key) => format!(" 󰑓 library changed, {} to refresh ", key),
+        None => " 󰑓 library changed ".to_string(),
+    };
+    let text_width = text.width() as u16;
+
+    if area.width < left + text_width + 4 || area.height == 0 {
+        return;
+    }
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            text,
+            Style::default().fg(config.colors.top_accent_color()),
+        )),
+        Rect {
+            x: area.x + 2 + left,
+            y: area.y + area.height - 1,
+            width: text_width,
+            height: 1,
+        },
+    );
+}
+
 /// Renders the user interface.
+///
+/// Returns the progress bar's area when nothing covers it, so the next clock tick can
+/// redraw just that region with [`render_progress_only`].
 #[allow(clippy::too_many_arguments)]
 pub fn render(
     frame: &mut Frame<'_>,
@@ -256,6 +507,7 @@ pub fn render(
     current_song: &Option<SongInfo>,
     queue: &[SongInfo],
     queue_list_state: &mut ListState,
+    queue_filter: Option<&mut QueueFilter>,
     config: &Config,
     menu_mode: &MenuMode,
     library: &Option<LazyLibrary>,
@@ -266,15 +518,38 @@ pub fn render(
     album_tracks_list_state: &mut ListState,
     panel_focus: &PanelFocus,
     expanded_albums: &std::collections::HashSet<(String, String)>,
+    file_browser: &mut FileBrowser,
+    queued: &QueuedSet,
     mpd_status: &Option<mpd_client::responses::Status>,
     key_binds: &KeyBinds,
     bit_perfect_enabled: bool,
+    muted: bool,
+    now_playing_view: bool,
+    library_stale: bool,
     show_config_warnings_popup: bool,
     config_warnings: &[String],
     status_message: &Option<crate::app::StatusMessage>,
-) {
+    sleep_timer: &Option<SleepTimer>,
+    overlays: &[Box<dyn Overlay>],
+    toast: Option<&Toast>,
+) -> Option<Rect> {
     let area = frame.area();
 
+    // The multi-panel layout falls apart below this size, show a notice instead
+    if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+        render_terminal_too_small(frame, area, config);
+        return None;
+    }
+
+    let (area, hints_area) = split_key_hints(area, config);
+    if let Some(hints_area) = hints_area {
+        let hints = key_binds.key_hints(menu_mode, panel_focus, now_playing_view);
+        render_key_hints(frame, &hints, hints_area, config);
+    }
+
+    // Skip image rendering while a popup is covering the screen
+    let skip_image_render = show_config_warnings_popup || !overlays.is_empty();
+
     // Extract play_state, progress, and format from current_song
     let (play_state, progress, elapsed, duration, format) = if let Some(song) = current_song {
         (
@@ -288,9 +563,23 @@ pub fn render(
         (None, None, None, None, None)
     };
 
-    match menu_mode {
-        MenuMode::Queue => {
-            render_queue_mode(
+    let progress_area = if now_playing_view {
+        render_now_playing_mode(
+            frame,
+            protocol,
+            area,
+            current_song,
+            config,
+            &play_state,
+            progress,
+            elapsed,
+            duration,
+            mpd_status,
+            skip_image_render,
+        )
+    } else {
+        match menu_mode {
+            MenuMode::Queue => render_queue_mode(
                 frame,
                 protocol,
                 area,
@@ -298,6 +587,7 @@ pub fn render(
                 current_song,
                 queue,
                 queue_list_state,
+                queue_filter,
                 config,
                 &play_state,
                 progress,
@@ -306,11 +596,10 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
-            );
-        }
-        MenuMode::Artists => {
-            render_artists_mode(
+                muted,
+                skip_image_render,
+            ),
+            MenuMode::Artists => render_artists_mode(
                 frame,
                 protocol,
                 area,
@@ -323,6 +612,7 @@ pub fn render(
                 album_display_list_state,
                 panel_focus,
                 expanded_albums,
+                queued,
                 &play_state,
                 progress,
                 elapsed,
@@ -330,11 +620,10 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
-            );
-        }
-        MenuMode::Albums => {
-            render_albums_mode(
+                muted,
+                skip_image_render,
+            ),
+            MenuMode::Albums => render_albums_mode(
                 frame,
                 protocol,
                 area,
@@ -346,6 +635,7 @@ pub fn render(
                 album_tracks_list_state,
                 panel_focus,
                 expanded_albums,
+                queued,
                 &play_state,
                 progress,
                 elapsed,
@@ -353,10 +643,30 @@ pub fn render(
                 mpd_status,
                 menu_mode,
                 bit_perfect_enabled,
-                show_config_warnings_popup,
-            );
+                muted,
+                skip_image_render,
+            ),
+            MenuMode::Files => render_files_mode(
+                frame,
+                protocol,
+                area,
+                &format,
+                current_song,
+                config,
+                file_browser,
+                queued,
+                &play_state,
+                progress,
+                elapsed,
+                duration,
+                mpd_status,
+                menu_mode,
+                bit_perfect_enabled,
+                muted,
+                skip_image_render,
+            ),
         }
-    }
+    };
 
     // Render key sequence status overlay
     render_top_right_status(frame, key_binds, status_message, area, config);
@@ -365,4 +675,210 @@ pub fn render(
     if show_config_warnings_popup && !config_warnings.is_empty() {
         render_config_warnings_popup(frame, config_warnings, config);
     }
+
+    // Render sleep timer countdown and the clock in the bottom border
+    if let Some(timer) = sleep_timer {
+        render_sleep_timer_countdown(frame, timer, area, config);
+    }
+    render_clock(frame, sleep_timer, area, config);
+
+    // Render the active partition in the bottom border, unless it is the default one
+    let mut indicator_width = 0;
+    if let Some(partition) = mpd_status
+        .as_ref()
+        .and_then(|status| status.partition.as_deref())
+        .filter(|partition| *partition != "default")
+    {
+        indicator_width = render_partition_indicator(frame, partition, area, config);
+    }
+    if mpd_status.as_ref().is_some_and(|status| status.consume) {
+        indicator_width += render_consume_indicator(
+            frame,
+            indicator_width,
+            area,
+            *menu_mode == MenuMode::Queue,
+            config,
+        );
+    }
+    if library_stale {
+        render_library_stale_indicator(frame, indicator_width, area, config);
+    }
+
+    // Render the overlay stack, bottom to top
+    for overlay in overlays {
+        overlay.render(frame, config);
+    }
+
+    // Render the last error on top of everything else
+    if let Some(toast) = toast {
+        render_toast(frame, &toast.text, config);
+    }
+
+    // Popups may cover the progress bar, redrawing it alone would paint over them
+    (!skip_image_render).then_some(progress_area)
+}
+
+/// Redraw only what changes on a clock tick (the progress bar, the sleep timer
+/// countdown and the clock) on top of `previous`, the last fully rendered frame
+pub fn render_progress_only(
+    frame: &mut Frame<'_>,
+    previous: &Buffer,
+    progress_area: Rect,
+    current_song: &Option<SongInfo>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    sleep_timer: &Option<SleepTimer>,
+    config: &Config,
+) {
+    frame.buffer_mut().clone_from(previous);
+
+    // Clear first so a shorter time string leaves nothing of the old one behind
+    frame.render_widget(Clear, progress_area);
+    let song = current_song.as_ref();
+    let play_state = song.and_then(|song| song.play_state);
+    let progress_bar = create_left_box_bottom(
+        &play_state,
+        song.and_then(|song| song.progress),
+        song.and_then(|song| song.elapsed),
+        song.and_then(|song| song.duration),
+        mpd_status.as_ref(),
+        config,
+    );
+    frame.render_widget(progress_bar, progress_area);
+
+    // Same bottom row as the full render, above the key hints
+    let (area, _) = split_key_hints(frame.area(), config);
+    if let Some(timer) = sleep_timer {
+        render_sleep_timer_countdown(frame, timer, area, config);
+    }
+    render_clock(frame, sleep_timer, area, config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ui::ImageSupport;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn song(i: usize) -> SongInfo {
+        SongInfo {
+            title: format!("Track {}", i),
+            artist: format!("Artist {}", i % 50),
+            artists: vec![format!("Artist {}", i % 50)],
+            album: format!("Album {}", i / 12),
+            file_path: PathBuf::from(format!("music/{}.flac", i)),
+            format: Some("44100:16:2".to_string()),
+            play_state: Some(mpd_client::responses::PlayState::Playing),
+            progress: Some(0.5),
+            elapsed: Some(Duration::from_secs(100)),
+            duration: Some(Duration::from_secs(200)),
+            track_number: (i % 12) as u64 + 1,
+            ..SongInfo::default()
+        }
+    }
+
+    #[test]
+    #[ignore] // Performance benchmarks are non-deterministic; run with --ignored
+    fn bench_full_vs_progress_only_redraw() {
+        use std::time::Instant;
+
+        let queue: Vec<SongInfo> = (0..5000).map(song).collect();
+        let current_song = Some(song(0));
+        let config = Config::default();
+        let (global_map, queue_map, artists_map, albums_map, files_map, sequential_bindings) =
+            config.binds.build_enhanced_key_maps();
+        let key_binds = KeyBinds::new_with_sequential(
+            global_map,
+            queue_map,
+            artists_map,
+            albums_map,
+            files_map,
+            sequential_bindings,
+        );
+        let mut protocol = Protocol {
+            image: None,
+            support: ImageSupport::Unsupported,
+            picker: None,
+            fill_source: None,
+        };
+        let mut states: [ListState; 6] = Default::default();
+        states[0].select(Some(2500));
+        let queued = QueuedSet::default();
+        let expanded = std::collections::HashSet::new();
+        let mut file_browser = FileBrowser::default();
+        let mut terminal = Terminal::new(TestBackend::new(160, 48)).unwrap();
+
+        let mut full_frame = || {
+            let mut progress_area = None;
+            let [queue_state, artists, albums, display, all_albums, tracks] = &mut states;
+            let completed = terminal
+                .draw(|frame| {
+                    progress_area = render(
+                        frame,
+                        &mut protocol,
+                        &current_song,
+                        &queue,
+                        queue_state,
+                        None,
+                        &config,
+                        &MenuMode::Queue,
+                        &None,
+                        artists,
+                        albums,
+                        display,
+                        all_albums,
+                        tracks,
+                        &PanelFocus::Artists,
+                        &expanded,
+                        &mut file_browser,
+                        &queued,
+                        &None,
+                        &key_binds,
+                        false,
+                        false,
+                        false,
+                        false,
+                        false,
+                        &[],
+                        &None,
+                        &None,
+                        &[],
+                        None,
+                    )
+                })
+                .unwrap();
+            (completed.buffer.clone(), progress_area.unwrap())
+        };
+
+        let start = Instant::now();
+        let (mut buffer, progress_area) = full_frame();
+        for _ in 1..100 {
+            buffer = full_frame().0;
+        }
+        let full_duration = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..100 {
+            let completed = terminal
+                .draw(|frame| {
+                    render_progress_only(
+                        frame,
+                        &buffer,
+                        progress_area,
+                        &current_song,
+                        &None,
+                        &None,
+                        &config,
+                    )
+                })
+                .unwrap();
+            buffer = completed.buffer.clone();
+        }
+        let partial_duration = start.elapsed();
+
+        println!("\nRedraw of a 5000 track queue view, 100 ticks:");
+        println!("  Full:          {:?}", full_duration);
+        println!("  Progress only: {:?}", partial_duration);
+    }
 }
@@ -1,7 +1,16 @@
-use crate::app::song::Artist;
+use crate::app::config::cover::CoverPosition;
+use crate::app::config::ui::{
+    ClockFormat, ColumnWidth, FmtOpts, HourDisplay, QueueColumn, QueueColumnKind,
+};
+use crate::app::song::{Album, Artist};
 use crate::app::ui::cache::width_cache::WidthCache;
+use crate::app::ui::{LIST_HEIGHT, RENDER_CACHE};
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
-use unicode_width::UnicodeWidthChar;
+use ratatui::style::Style;
+use ratatui::text::Span;
+use ratatui::widgets::ListState;
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Truncate a string to fit within the given display width, handling Unicode properly
 pub fn truncate_by_width(s: &str, max_width: usize) -> String {
@@ -77,6 +86,122 @@ pub fn left_align_cached(cache: &mut WidthCache, s: &str, width: usize) -> Strin
     format!("{}{}", s, " ".repeat(padding))
 }
 
+/// Format a duration as "M:SS" or "H:MM:SS" according to `opts`.
+/// Every duration shown in the UI goes through here so they all look alike.
+pub fn format_duration(duration: std::time::Duration, opts: FmtOpts) -> String {
+    let secs = duration.as_secs();
+    let show_hours = match opts.hours {
+        HourDisplay::Never => false,
+        HourDisplay::Auto => secs >= 3600,
+        HourDisplay::Always => true,
+    };
+    let lead_width = if opts.pad { 2 } else { 1 };
+
+    if show_hours {
+        format!(
+            "{:0lead_width$}:{:02}:{:02}",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )
+    } else {
+        format!("{:0lead_width$}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// An album's total runtime in the `opts` format, with a trailing "+" when some track
+/// durations are unknown
+fn album_total_duration(album: &Album, opts: FmtOpts) -> String {
+    RENDER_CACHE.with(|cache| match album.total_duration() {
+        Some(duration) => {
+            let mut cache = cache.borrow_mut();
+            let formatted = cache.durations.format(duration, opts);
+            if album.is_duration_complete() {
+                formatted.to_owned()
+            } else {
+                format!("{}+", formatted)
+            }
+        }
+        None => "-:--".to_owned(),
+    })
+}
+
+/// An album row's right-hand details: the runtime, prefixed with a "!" badge when
+/// some tracks are missing key tags, and preceded by the format badge when
+/// `with_format` is set
+pub fn album_row_details(album: &Album, opts: FmtOpts, with_format: bool) -> String {
+    let mut duration = album_total_duration(album, opts);
+    if album.poorly_tagged_tracks() > 0 {
+        duration = format!("! {}", duration);
+    }
+    match album.format_badge() {
+        Some(badge) if with_format => format!("{}  {}", badge, duration),
+        _ => duration,
+    }
+}
+
+/// Format a time of day for the clock. Both formats keep the same width all day,
+/// so a partial redraw never leaves a digit of the previous time behind.
+pub fn format_clock(hour: u32, minute: u32, format: ClockFormat) -> String {
+    match format {
+        ClockFormat::H24 => format!("{:02}:{:02}", hour, minute),
+        ClockFormat::H12 => {
+            let suffix = if hour < 12 { "AM" } else { "PM" };
+            let hour = match hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            format!("{:2}:{:02} {}", hour, minute, suffix)
+        }
+    }
+}
+
+/// Separator drawn between two adjacent queue columns. Position strings carry
+/// their own ". " and text columns are set apart by a border.
+pub fn column_separator(left: QueueColumnKind, right: QueueColumnKind) -> &'static str {
+    if left == QueueColumnKind::Position {
+        ""
+    } else if left.is_text() && right.is_text() {
+        " ║ "
+    } else {
+        " "
+    }
+}
+
+/// Width of each queue column in a row `inner_width` cells wide. `natural` is the
+/// width an auto-sized number or duration column needs; auto-sized text columns
+/// split whatever is left equally.
+pub fn queue_column_widths(
+    columns: &[QueueColumn],
+    inner_width: usize,
+    natural: impl Fn(QueueColumnKind) -> usize,
+) -> Vec<usize> {
+    let separators: usize = columns
+        .windows(2)
+        .map(|pair| column_separator(pair[0].kind, pair[1].kind).width())
+        .sum();
+    let available = inner_width.saturating_sub(separators);
+
+    let widths: Vec<Option<usize>> = columns
+        .iter()
+        .map(|column| match column.width {
+            ColumnWidth::Cells(cells) => Some(cells),
+            ColumnWidth::Percent(percent) => Some(available * percent / 100),
+            ColumnWidth::Auto if !column.kind.is_text() => Some(natural(column.kind)),
+            ColumnWidth::Auto => None,
+        })
+        .collect();
+
+    let used: usize = widths.iter().flatten().sum();
+    let shared = widths.iter().filter(|width| width.is_none()).count();
+    let share = available.saturating_sub(used) / shared.max(1);
+
+    widths
+        .into_iter()
+        .map(|width| width.unwrap_or(share))
+        .collect()
+}
+
 /// Helper function to center a rect within another rect
 pub fn center_area(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
     let [area] = Layout::horizontal([horizontal])
@@ -95,14 +220,226 @@ pub fn center_image(image_dimensions: Rect, available_area: Rect) -> Rect {
     }
 }
 
+/// Terminal width below which the library views show a single panel at a time
+pub const NARROW_LAYOUT_WIDTH: u16 = 100;
+
+/// Check if the area is too narrow for the side-by-side library panels
+pub fn is_narrow_layout(area: Rect) -> bool {
+    area.width < NARROW_LAYOUT_WIDTH
+}
+
+/// Width of the trailing column that marks library items already in the queue
+pub const QUEUED_MARKER_WIDTH: usize = 2;
+
+/// Marker shown in the queued column
+pub fn queued_marker(queued: bool) -> &'static str {
+    if queued { " •" } else { "  " }
+}
+
+/// Byte range of the first occurrence of the lowercase `term` in `text`, ignoring case
+pub fn find_match(text: &str, term: &str) -> Option<Range<usize>> {
+    if term.is_empty() {
+        return None;
+    }
+    // Compared char by char, lowercasing can change the byte length of `text`
+    text.char_indices().find_map(|(start, _)| {
+        let mut term_chars = term.chars();
+        for (offset, ch) in text[start..].char_indices() {
+            for lower in ch.to_lowercase() {
+                if term_chars.next() != Some(lower) {
+                    return None;
+                }
+            }
+            if term_chars.as_str().is_empty() {
+                return Some(start..start + offset + ch.len_utf8());
+            }
+        }
+        None
+    })
+}
+
+/// Spans of `text` in `style`, with the first match of the lowercase `term` (if any)
+/// in `match_style`
+pub fn highlight_match(
+    text: String,
+    term: Option<&str>,
+    style: Style,
+    match_style: Style,
+) -> Vec<Span<'static>> {
+    let Some(range) = term.and_then(|term| find_match(&text, term)) else {
+        return vec![Span::styled(text, style)];
+    };
+    vec![
+        Span::styled(text[..range.start].to_string(), style),
+        Span::styled(text[range.clone()].to_string(), match_style),
+        Span::styled(text[range.end..].to_string(), style),
+    ]
+}
+
+/// Rows of a `len`-item list visible in the bordered panel `area`, scrolled like
+/// ratatui's List (the offset only moves once the selection leaves the window).
+/// Stores the offset in `list_state` and returns the window with a state to render
+/// a List built from just those rows, so building rows scales with the viewport.
+/// The height is also recorded in `LIST_HEIGHT` for page-wise navigation.
+pub fn visible_window(
+    list_state: &mut ListState,
+    len: usize,
+    area: Rect,
+) -> (Range<usize>, ListState) {
+    let height = area.height.saturating_sub(2) as usize;
+    LIST_HEIGHT.with(|list_height| list_height.set(height));
+
+    let mut offset = list_state.offset().min(len.saturating_sub(height));
+    if let Some(selected) = list_state.selected().filter(|_| len > 0) {
+        let selected = selected.min(len - 1);
+        if selected < offset {
+            offset = selected;
+        } else if height > 0 && selected >= offset + height {
+            offset = selected + 1 - height;
+        }
+    }
+    *list_state.offset_mut() = offset;
+
+    let window = offset..(offset + height).min(len);
+    let selected = list_state
+        .selected()
+        .filter(|selected| window.contains(selected))
+        .map(|selected| selected - offset);
+    (window, ListState::default().with_selected(selected))
+}
+
+/// Limit an area to the given maximum size (0 = unlimited), keeping its origin
+pub fn limit_area(area: Rect, max_width: u16, max_height: u16) -> Rect {
+    Rect {
+        width: if max_width > 0 {
+            area.width.min(max_width)
+        } else {
+            area.width
+        },
+        height: if max_height > 0 {
+            area.height.min(max_height)
+        } else {
+            area.height
+        },
+        ..area
+    }
+}
+
+/// Place an image within the available area according to the configured position
+pub fn position_image(
+    image_dimensions: Rect,
+    available_area: Rect,
+    position: CoverPosition,
+) -> Rect {
+    let centered = center_image(image_dimensions, available_area);
+    let free_width = available_area.width.saturating_sub(image_dimensions.width);
+    let free_height = available_area
+        .height
+        .saturating_sub(image_dimensions.height);
+    match position {
+        CoverPosition::Center => centered,
+        CoverPosition::Top => Rect {
+            y: available_area.y,
+            ..centered
+        },
+        CoverPosition::Bottom => Rect {
+            y: available_area.y + free_height,
+            ..centered
+        },
+        CoverPosition::Left => Rect {
+            x: available_area.x,
+            ..centered
+        },
+        CoverPosition::Right => Rect {
+            x: available_area.x + free_width,
+            ..centered
+        },
+    }
+}
+
+/// Image output capability of the terminal, detected once at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSupport {
+    /// A graphics protocol (Kitty, Sixel, iTerm2) is available
+    Native,
+    /// No graphics protocol, covers are approximated with halfblock characters
+    Halfblocks,
+    /// The terminal cannot draw images at all, only a text placeholder is shown
+    Unsupported,
+}
+
 pub struct Protocol {
     pub image: Option<ratatui_image::protocol::StatefulProtocol>,
+    pub support: ImageSupport,
+    /// Builds the image protocols (None where covers are never loaded)
+    pub picker: Option<ratatui_image::picker::Picker>,
+    /// Decoded cover and the area (in cells) it was last cropped for, kept for "fill"
+    /// scaling so the cover can be cropped again when the area changes
+    pub fill_source: Option<(image::DynamicImage, Rect)>,
+}
+
+impl Protocol {
+    /// Show `cover`, or nothing for None. With `fill` the decoded image is kept for
+    /// [`Protocol::crop_to_fill`].
+    pub fn set_cover(&mut self, cover: Option<image::DynamicImage>, fill: bool) {
+        self.fill_source = cover
+            .as_ref()
+            .filter(|_| fill)
+            .map(|cover| (cover.clone(), Rect::default()));
+        self.image = cover
+            .zip(self.picker.as_ref())
+            .map(|(cover, picker)| picker.new_resize_protocol(cover));
+    }
+
+    /// Crop the kept cover to the shape of `area` unless it already was
+    pub fn crop_to_fill(&mut self, area: Rect) {
+        let (Some(picker), Some((cover, cropped_for))) = (&self.picker, &mut self.fill_source)
+        else {
+            return;
+        };
+        if *cropped_for == area || area.is_empty() {
+            return;
+        }
+
+        let (font_width, font_height) = picker.font_size();
+        let (x, y, width, height) = fill_crop(
+            (cover.width(), cover.height()),
+            (
+                u32::from(area.width) * u32::from(font_width),
+                u32::from(area.height) * u32::from(font_height),
+            ),
+        );
+        self.image = Some(picker.new_resize_protocol(cover.crop_imm(x, y, width, height)));
+        *cropped_for = area;
+    }
+}
+
+/// Centered part of an image of `image` pixels with the aspect ratio of `target` pixels,
+/// as (x, y, width, height). Scaled up to `target`, it covers it without distortion.
+pub fn fill_crop(image: (u32, u32), target: (u32, u32)) -> (u32, u32, u32, u32) {
+    let ((image_width, image_height), (target_width, target_height)) = (image, target);
+    if image_width == 0 || image_height == 0 || target_width == 0 || target_height == 0 {
+        return (0, 0, image_width, image_height);
+    }
+
+    // Compare image_width / image_height with target_width / target_height without floats
+    let image_ratio = u64::from(image_width) * u64::from(target_height);
+    let target_ratio = u64::from(target_width) * u64::from(image_height);
+    if image_ratio > target_ratio {
+        // Wider than the target: trim the sides
+        let width = (target_ratio / u64::from(target_height)).max(1) as u32;
+        ((image_width - width) / 2, 0, width, image_height)
+    } else {
+        // Taller than the target: trim top and bottom
+        let height = (image_ratio / u64::from(target_width)).max(1) as u32;
+        (0, (image_height - height) / 2, image_width, height)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum DisplayItem {
     Album(String),                                                 // album name
-    Song(String, Option<std::time::Duration>, std::path::PathBuf), // song title, duration, and file path
+    Song(String, Option<std::time::Duration>, std::path::PathBuf), // track label and title, duration, and file path
 }
 
 /// Cache for computed album display lists
@@ -197,10 +534,11 @@ pub fn compute_album_display_list(
 
         // If expanded, add songs
         if is_expanded {
+            let multi_disc = album.is_multi_disc();
             for song in &album.tracks {
                 album_indices.push(None); // Songs don't map to album indices
                 display_items.push(DisplayItem::Song(
-                    song.title.clone(),
+                    format!("{} {}", song.track_label(multi_disc), song.title),
                     song.duration,
                     song.file_path.clone(),
                 ));
@@ -210,3 +548,182 @@ pub fn compute_album_display_list(
 
     (display_items, album_indices)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_crop_trims_the_overflowing_sides() {
+        // A wide cover in a square area loses its left and right edges
+        assert_eq!(fill_crop((200, 100), (50, 50)), (50, 0, 100, 100));
+        // A tall cover in a wide area loses its top and bottom
+        assert_eq!(fill_crop((100, 300), (200, 100)), (0, 125, 100, 50));
+        // Matching shapes are left alone
+        assert_eq!(fill_crop((600, 600), (80, 80)), (0, 0, 600, 600));
+    }
+
+    #[test]
+    fn format_clock_keeps_its_width() {
+        assert_eq!(format_clock(9, 5, ClockFormat::H24), "09:05");
+        assert_eq!(format_clock(0, 30, ClockFormat::H12), "12:30 AM");
+        assert_eq!(format_clock(9, 5, ClockFormat::H12), " 9:05 AM");
+        assert_eq!(format_clock(12, 0, ClockFormat::H12), "12:00 PM");
+        assert_eq!(format_clock(23, 59, ClockFormat::H12), "11:59 PM");
+    }
+
+    fn panel(rows: u16) -> Rect {
+        // Two extra rows for the borders
+        Rect::new(0, 0, 40, rows + 2)
+    }
+
+    fn opts(hours: HourDisplay, pad: bool) -> FmtOpts {
+        FmtOpts { hours, pad }
+    }
+
+    #[test]
+    fn test_format_duration_zero() {
+        let zero = std::time::Duration::ZERO;
+        assert_eq!(
+            format_duration(zero, opts(HourDisplay::Auto, false)),
+            "0:00"
+        );
+        assert_eq!(
+            format_duration(zero, opts(HourDisplay::Auto, true)),
+            "00:00"
+        );
+        assert_eq!(
+            format_duration(zero, opts(HourDisplay::Always, false)),
+            "0:00:00"
+        );
+        assert_eq!(
+            format_duration(zero, opts(HourDisplay::Always, true)),
+            "00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_hour_boundary() {
+        let secs = std::time::Duration::from_secs;
+        let auto = opts(HourDisplay::Auto, false);
+        assert_eq!(format_duration(secs(3599), auto), "59:59");
+        assert_eq!(format_duration(secs(3600), auto), "1:00:00");
+        assert_eq!(
+            format_duration(secs(3661), opts(HourDisplay::Auto, true)),
+            "01:01:01"
+        );
+
+        // Without hours the minutes keep counting
+        assert_eq!(
+            format_duration(secs(3600), opts(HourDisplay::Never, false)),
+            "60:00"
+        );
+        assert_eq!(
+            format_duration(secs(185), opts(HourDisplay::Never, true)),
+            "03:05"
+        );
+        assert_eq!(
+            format_duration(secs(185), opts(HourDisplay::Always, false)),
+            "0:03:05"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_pads_minutes_after_hours() {
+        // The old album runtime format left the minutes unpadded after the hours
+        // ("1:1:01"), which reads as a different time
+        let secs = std::time::Duration::from_secs;
+        let auto = opts(HourDisplay::Auto, false);
+        assert_eq!(format_duration(secs(3661), auto), "1:01:01");
+        assert_eq!(format_duration(secs(7322), auto), "2:02:02");
+    }
+
+    #[test]
+    fn test_format_duration_ignores_fractions() {
+        let duration = std::time::Duration::from_millis(59_999);
+        assert_eq!(
+            format_duration(duration, opts(HourDisplay::Auto, false)),
+            "0:59"
+        );
+    }
+
+    fn columns(specs: &[&str]) -> Vec<QueueColumn> {
+        specs
+            .iter()
+            .map(|spec| QueueColumn::parse(spec).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_queue_column_widths_share_leftover_between_text_columns() {
+        // "1. " needs 3 cells, "M:SS" 4; separators: "" + " ║ " * 2 + " "
+        let columns = columns(&["position", "title", "artist", "album", "duration"]);
+        let widths = queue_column_widths(&columns, 100, |kind| match kind {
+            QueueColumnKind::Position => 3,
+            _ => 4,
+        });
+        assert_eq!(widths, vec![3, 28, 28, 28, 4]);
+    }
+
+    #[test]
+    fn test_queue_column_widths_respect_fixed_and_percent() {
+        // 50 cells minus two " ║ " leaves 44: 40% of that is 17, artist is fixed at 10
+        let columns = columns(&["title:40%", "artist:10", "album"]);
+        let widths = queue_column_widths(&columns, 50, |_| 0);
+        assert_eq!(widths, vec![17, 10, 17]);
+    }
+
+    #[test]
+    fn test_queue_column_parse_rejects_bad_specs() {
+        assert!(QueueColumn::parse("genre").is_none());
+        assert!(QueueColumn::parse("title:wide").is_none());
+        assert!(QueueColumn::parse("title:150%").is_none());
+        assert_eq!(
+            QueueColumn::parse("Duration:6"),
+            Some(QueueColumn {
+                kind: QueueColumnKind::Duration,
+                width: ColumnWidth::Cells(6),
+            })
+        );
+    }
+
+    #[test]
+    fn test_visible_window_follows_selection() {
+        let mut state = ListState::default().with_selected(Some(0));
+        let (window, window_state) = visible_window(&mut state, 100, panel(10));
+        assert_eq!(window, 0..10);
+        assert_eq!(window_state.selected(), Some(0));
+
+        // Moving below the window scrolls just far enough to show the selection
+        state.select(Some(15));
+        let (window, window_state) = visible_window(&mut state, 100, panel(10));
+        assert_eq!(window, 6..16);
+        assert_eq!(window_state.selected(), Some(9));
+        assert_eq!(state.offset(), 6);
+
+        // Moving up inside the window keeps the offset
+        state.select(Some(8));
+        let (window, _) = visible_window(&mut state, 100, panel(10));
+        assert_eq!(window, 6..16);
+    }
+
+    #[test]
+    fn test_find_match_ignores_case() {
+        assert_eq!(find_match("Hello World", "world"), Some(6..11));
+        assert_eq!(find_match("Hello World", "xyz"), None);
+        assert_eq!(find_match("Hello", ""), None);
+        // Byte ranges stay on the original text when lowercasing changes lengths
+        assert_eq!(find_match("İstanbul Ünü", "ünü"), Some(10..15));
+    }
+
+    #[test]
+    fn test_visible_window_clamps_to_list() {
+        let mut state = ListState::default().with_offset(50);
+        let (window, window_state) = visible_window(&mut state, 5, panel(10));
+        assert_eq!(window, 0..5);
+        assert_eq!(window_state.selected(), None);
+
+        let (window, _) = visible_window(&mut state, 0, panel(10));
+        assert!(window.is_empty());
+    }
+}
@@ -8,10 +8,13 @@ use ratatui::{
 
 use crate::app::Config;
 use crate::app::ui::widgets::{
-    create_empty_box, create_format_widget, create_left_box_bottom, create_song_widget,
-    create_top_box, render_image_widget,
+    create_empty_box, create_format_widget, create_left_box_bottom, create_message_box,
+    create_song_widget, create_top_box, empty_library_lines, library_unavailable_lines,
+    render_image_widget, render_list_scrollbar,
+};
+use crate::app::ui::{
+    MenuMode, PanelFocus, QueuedSet, RENDER_CACHE, WIDTH_CACHE, rendering::utils,
 };
-use crate::app::ui::{MenuMode, PanelFocus, RENDER_CACHE, WIDTH_CACHE, rendering::utils};
 use crate::app::{LazyLibrary, SongInfo};
 use unicode_width::UnicodeWidthStr;
 
@@ -28,6 +31,7 @@ pub fn render_albums_mode(
     album_tracks_list_state: &mut ListState,
     panel_focus: &PanelFocus,
     _expanded_albums: &std::collections::HashSet<(String, String)>,
+    queued: &QueuedSet,
     play_state: &Option<mpd_client::responses::PlayState>,
     progress: Option<f64>,
     elapsed: Option<std::time::Duration>,
@@ -35,8 +39,9 @@ pub fn render_albums_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    muted: bool,
     skip_image_render: bool,
-) {
+) -> Rect {
     // Same layout as tracks mode but for albums
     // Split area vertically: top section, middle section, bottom section
     let main_vertical_chunks = Layout::vertical([
@@ -46,29 +51,44 @@ pub fn render_albums_mode(
     ])
     .split(area);
 
+    // On narrow terminals only the focused panel is shown (drill-down navigation),
+    // with the song info moved under it and no cover art
+    let narrow = utils::is_narrow_layout(area);
+
     // Split bottom section horizontally: left boxes, right content
-    let bottom_horizontal_chunks = Layout::horizontal([
-        Constraint::Percentage(50), // Left boxes take 50% of width
-        Constraint::Percentage(50), // Right content takes 50% of width
-    ])
+    let bottom_horizontal_chunks = Layout::horizontal(if narrow {
+        [Constraint::Percentage(100), Constraint::Length(0)]
+    } else {
+        [
+            Constraint::Percentage(50), // Left boxes take 50% of width
+            Constraint::Percentage(50), // Right content takes 50% of width
+        ]
+    })
     .split(main_vertical_chunks[2]);
 
-    // Split left side into two side-by-side boxes and progress bar
+    // Split left side into the boxes, song info (narrow only) and progress bar
     let left_vertical_chunks = Layout::vertical([
-        Constraint::Percentage(100), // Two boxes take most of space
+        Constraint::Percentage(100), // Boxes take most of the space
+        Constraint::Length(if narrow { 4 } else { 0 }), // Song info when narrow
         Constraint::Length(3),       // Progress bar takes 3 lines
     ])
     .split(bottom_horizontal_chunks[0]);
 
     // Split the top part into two side-by-side boxes
-    let left_horizontal_chunks = Layout::horizontal([
-        Constraint::Percentage(50), // Albums list takes 50% of left space
-        Constraint::Percentage(50), // Album tracks take 50% of left space
-    ])
+    let left_horizontal_chunks = Layout::horizontal(if !narrow {
+        [
+            Constraint::Percentage(50), // Albums list takes 50% of left space
+            Constraint::Percentage(50), // Album tracks take 50% of left space
+        ]
+    } else if panel_focus == &PanelFocus::AlbumTracks {
+        [Constraint::Length(0), Constraint::Percentage(100)]
+    } else {
+        [Constraint::Percentage(100), Constraint::Length(0)]
+    })
     .split(left_vertical_chunks[0]);
 
     // Render format info widget at top
-    let format_widget = create_format_widget(format, current_song, config);
+    let format_widget = create_format_widget(format, current_song, mpd_status.as_ref(), config);
     frame.render_widget(format_widget, main_vertical_chunks[0]);
 
     // Render middle box that spans both splits
@@ -78,26 +98,67 @@ pub fn render_albums_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        muted,
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
-    // Render albums list
-    if let Some(library) = library {
-        let albums_list: Vec<ratatui::widgets::ListItem> = library
-            .all_albums
+    // Render albums list, or explain why there is none
+    if let Some(library) = library
+        .as_ref()
+        .filter(|library| library.all_albums.is_empty())
+    {
+        // An empty list is either still loading or the whole database is empty
+        let lines = if library.artists.is_empty() {
+            empty_library_lines(config)
+        } else {
+            vec![Line::from("Loading albums…")]
+        };
+        let albums_box = create_message_box("Albums", lines, config);
+        frame.render_widget(albums_box, left_horizontal_chunks[0]);
+        let tracks_box = create_empty_box("Tracks", config);
+        frame.render_widget(tracks_box, left_horizontal_chunks[1]);
+    } else if let Some(library) = library {
+        let (window, mut window_state) = utils::visible_window(
+            all_albums_list_state,
+            library.all_albums.len(),
+            left_horizontal_chunks[0],
+        );
+        let albums_list: Vec<ratatui::widgets::ListItem> = library.all_albums[window]
             .iter()
             .map(|(artist_name, album)| {
-                // Calculate available width for album name
-                let available_width = left_horizontal_chunks[0].width.saturating_sub(4) as usize;
+                let duration_str = utils::album_row_details(
+                    album,
+                    config.ui.time_format(),
+                    config.ui.album_format_badges,
+                );
+
+                // Calculate available width for album name, keeping the runtime right-aligned
+                let available_width = (left_horizontal_chunks[0].width.saturating_sub(4) as usize)
+                    .saturating_sub(utils::QUEUED_MARKER_WIDTH);
+                let max_text_width = available_width.saturating_sub(duration_str.width() + 2);
 
                 // Create display text with album name and artist
                 let display_text = format!("{} - {}", album.name, artist_name);
                 let truncated_text = WIDTH_CACHE.with(|cache| {
                     let mut cache = cache.borrow_mut();
-                    utils::truncate_by_width_cached(&mut cache, &display_text, available_width)
+                    utils::truncate_by_width_cached(&mut cache, &display_text, max_text_width)
                 });
 
-                ratatui::widgets::ListItem::new(vec![Line::from(truncated_text)])
+                let filler_width = max_text_width.saturating_sub(truncated_text.width());
+                let filler = RENDER_CACHE
+                    .with(|cache| cache.borrow().fillers.spaces(filler_width).to_owned());
+
+                ratatui::widgets::ListItem::new(vec![Line::from(vec![
+                    Span::raw(format!("{}{}  ", truncated_text, filler)),
+                    Span::styled(
+                        duration_str,
+                        Style::default().fg(config.colors.track_duration_color()),
+                    ),
+                    Span::styled(
+                        utils::queued_marker(queued.contains_any(&album.tracks)),
+                        config.colors.track_duration_color(),
+                    ),
+                ])])
             })
             .collect();
 
@@ -123,7 +184,14 @@ pub fn render_albums_mode(
         frame.render_stateful_widget(
             albums_list_widget,
             left_horizontal_chunks[0],
-            all_albums_list_state, // Using all_albums_list_state for album list navigation in Albums mode
+            &mut window_state, // Window of all_albums_list_state, which drives album list navigation in Albums mode
+        );
+        render_list_scrollbar(
+            frame,
+            left_horizontal_chunks[0],
+            library.all_albums.len(),
+            all_albums_list_state,
+            config,
         );
 
         // Show tracks for selected album
@@ -131,6 +199,7 @@ pub fn render_albums_mode(
             if let Some((_artist_name, selected_album)) =
                 library.all_albums.get(selected_album_index)
             {
+                let multi_disc = selected_album.is_multi_disc();
                 let tracks_list: Vec<ratatui::widgets::ListItem> = selected_album
                     .tracks
                     .iter()
@@ -138,36 +207,37 @@ pub fn render_albums_mode(
                         let track_duration_str = RENDER_CACHE.with(|cache| match track.duration {
                             Some(duration) => {
                                 let mut cache = cache.borrow_mut();
-                                format!("  {}", cache.durations.format_short(duration.as_secs()))
+                                format!(
+                                    "  {}",
+                                    cache.durations.format(duration, config.ui.time_format())
+                                )
                             }
                             None => "  --:--".to_owned(),
                         });
 
-                        let available_width =
-                            left_horizontal_chunks[1].width.saturating_sub(4) as usize;
+                        let available_width = (left_horizontal_chunks[1].width.saturating_sub(4)
+                            as usize)
+                            .saturating_sub(utils::QUEUED_MARKER_WIDTH);
                         let track_duration_width = track_duration_str.width();
                         let max_track_title_width =
                             available_width.saturating_sub(track_duration_width + 3);
 
                         // Truncate track title if needed to keep duration aligned
+                        let track_title =
+                            format!("{} {}", track.track_label(multi_disc), track.title);
                         let truncated_track_title = WIDTH_CACHE.with(|cache| {
                             let mut cache = cache.borrow_mut();
                             utils::truncate_by_width_cached(
                                 &mut cache,
-                                &track.title,
+                                &track_title,
                                 max_track_title_width,
                             )
                         });
 
                         let filler_width =
                             max_track_title_width.saturating_sub(truncated_track_title.width());
-                        let filler = RENDER_CACHE.with(|cache| {
-                            cache
-                                .borrow()
-                                .fillers
-                                .spaces(filler_width.max(0))
-                                .to_owned()
-                        });
+                        let filler = RENDER_CACHE
+                            .with(|cache| cache.borrow().fillers.spaces(filler_width).to_owned());
 
                         let track_text = format!("   {}{}", truncated_track_title, filler,);
                         let mut spans = vec![Span::styled(
@@ -178,6 +248,10 @@ pub fn render_albums_mode(
                             track_duration_str.clone(),
                             Style::default().fg(config.colors.track_duration_color()),
                         ));
+                        spans.push(Span::styled(
+                            utils::queued_marker(queued.contains_file(&track.file_path)),
+                            config.colors.track_duration_color(),
+                        ));
                         ratatui::widgets::ListItem::new(vec![Line::from(spans)])
                     })
                     .collect();
@@ -213,6 +287,13 @@ pub fn render_albums_mode(
                     left_horizontal_chunks[1],
                     album_tracks_list_state, // Using album_tracks_list_state for tracks navigation in Albums mode
                 );
+                render_list_scrollbar(
+                    frame,
+                    left_horizontal_chunks[1],
+                    selected_album.tracks.len(),
+                    album_tracks_list_state,
+                    config,
+                );
             } else {
                 let tracks_box = create_empty_box("Tracks", config);
                 frame.render_widget(tracks_box, left_horizontal_chunks[1]);
@@ -222,15 +303,23 @@ pub fn render_albums_mode(
             frame.render_widget(tracks_box, left_horizontal_chunks[1]);
         }
     } else {
-        let albums_box = create_empty_box("Albums", config);
+        let albums_box = create_message_box("Albums", library_unavailable_lines(), config);
         frame.render_widget(albums_box, left_horizontal_chunks[0]);
         let tracks_box = create_empty_box("Tracks", config);
         frame.render_widget(tracks_box, left_horizontal_chunks[1]);
     }
 
     // Render progress bar under the two boxes
-    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
-    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+    let progress_widget = create_left_box_bottom(
+        play_state,
+        progress,
+        elapsed,
+        duration,
+        mpd_status.as_ref(),
+        config,
+    );
+    frame.render_widget(progress_widget, left_vertical_chunks[2]);
+    let progress_area = left_vertical_chunks[2];
 
     // Split the right area vertically: image on top, song info at bottom
     let right_vertical_chunks = Layout::vertical([
@@ -242,9 +331,22 @@ pub fn render_albums_mode(
     let image_area = right_vertical_chunks[0];
 
     // Render image or placeholder
-    render_image_widget(frame, protocol, image_area, skip_image_render);
+    render_image_widget(
+        frame,
+        protocol,
+        image_area,
+        &config.cover,
+        config.colors.cover_border_color(),
+        skip_image_render || narrow,
+    );
 
     // Render the song information
     let song_widget = create_song_widget(current_song, config);
-    frame.render_widget(song_widget, right_vertical_chunks[1]);
+    if narrow {
+        frame.render_widget(song_widget, left_vertical_chunks[1]);
+    } else {
+        frame.render_widget(song_widget, right_vertical_chunks[1]);
+    }
+
+    progress_area
 }
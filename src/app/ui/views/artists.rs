@@ -1,11 +1,12 @@
 use crate::app::{
     Config, LazyLibrary, ListState, MenuMode, PanelFocus, SongInfo,
     ui::{
-        ALBUM_DISPLAY_CACHE, DisplayItem, Protocol, RENDER_CACHE, WIDTH_CACHE,
+        ALBUM_DISPLAY_CACHE, DisplayItem, Protocol, QueuedSet, RENDER_CACHE, WIDTH_CACHE,
         rendering::utils,
         widgets::{
-            create_empty_box, create_format_widget, create_left_box_bottom, create_song_widget,
-            create_top_box, render_image_widget,
+            create_empty_box, create_format_widget, create_left_box_bottom, create_message_box,
+            create_song_widget, create_top_box, empty_library_lines, library_unavailable_lines,
+            render_image_widget, render_list_scrollbar,
         },
     },
 };
@@ -34,6 +35,7 @@ pub fn render_artists_mode(
     album_display_list_state: &mut ListState,
     panel_focus: &PanelFocus,
     expanded_albums: &std::collections::HashSet<(String, String)>,
+    queued: &QueuedSet,
     play_state: &Option<mpd_client::responses::PlayState>,
     progress: Option<f64>,
     elapsed: Option<std::time::Duration>,
@@ -41,8 +43,9 @@ pub fn render_artists_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    muted: bool,
     skip_image_render: bool,
-) {
+) -> Rect {
     // Same as original layout, but replace queue box with 2 side-by-side boxes
     // Split area vertically: top section, middle section, bottom section
     let main_vertical_chunks = Layout::vertical([
@@ -52,29 +55,44 @@ pub fn render_artists_mode(
     ])
     .split(area);
 
+    // On narrow terminals only the focused panel is shown (drill-down navigation),
+    // with the song info moved under it and no cover art
+    let narrow = utils::is_narrow_layout(area);
+
     // Split bottom section horizontally: left boxes, right content
-    let bottom_horizontal_chunks = Layout::horizontal([
-        Constraint::Percentage(50), // Left boxes take 50% of width
-        Constraint::Percentage(50), // Right content takes 50% of width
-    ])
+    let bottom_horizontal_chunks = Layout::horizontal(if narrow {
+        [Constraint::Percentage(100), Constraint::Length(0)]
+    } else {
+        [
+            Constraint::Percentage(50), // Left boxes take 50% of width
+            Constraint::Percentage(50), // Right content takes 50% of width
+        ]
+    })
     .split(main_vertical_chunks[2]);
 
-    // Split left side into two side-by-side boxes and progress bar
+    // Split left side into the boxes, song info (narrow only) and progress bar
     let left_vertical_chunks = Layout::vertical([
-        Constraint::Percentage(100), // Two boxes take most of the space
+        Constraint::Percentage(100), // Boxes take most of the space
+        Constraint::Length(if narrow { 4 } else { 0 }), // Song info when narrow
         Constraint::Length(3),       // Progress bar takes 3 lines
     ])
     .split(bottom_horizontal_chunks[0]);
 
     // Split the top part into two side-by-side boxes
-    let left_horizontal_chunks = Layout::horizontal([
-        Constraint::Percentage(35), // Artists box takes 35% of left space
-        Constraint::Percentage(65), // Tracks box takes 65% of left space
-    ])
+    let left_horizontal_chunks = Layout::horizontal(if !narrow {
+        [
+            Constraint::Percentage(35), // Artists box takes 35% of left space
+            Constraint::Percentage(65), // Tracks box takes 65% of left space
+        ]
+    } else if panel_focus == &PanelFocus::Albums {
+        [Constraint::Length(0), Constraint::Percentage(100)]
+    } else {
+        [Constraint::Percentage(100), Constraint::Length(0)]
+    })
     .split(left_vertical_chunks[0]);
 
     // Render format info widget at top
-    let format_widget = create_format_widget(format, current_song, config);
+    let format_widget = create_format_widget(format, current_song, mpd_status.as_ref(), config);
     frame.render_widget(format_widget, main_vertical_chunks[0]);
 
     // Render middle box that spans both splits
@@ -84,22 +102,57 @@ pub fn render_artists_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        muted,
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
-    // Render artists list
-    if let Some(library) = library {
-        let artists_list: Vec<ratatui::widgets::ListItem> = library
-            .artists
+    // The playing song's artist and album are set in bold italics, like its queue row,
+    // so it can be found while browsing elsewhere
+    let playing_artist = library
+        .as_ref()
+        .zip(current_song.as_ref())
+        .map(|(library, song)| song.library_artist(library.group_by, &library.tags));
+    let playing_style = Style::default().bold().italic();
+
+    // Render artists list, or explain why there is none
+    if library
+        .as_ref()
+        .is_some_and(|library| library.artists.is_empty())
+    {
+        let artists_box = create_message_box("Artists", empty_library_lines(config), config);
+        frame.render_widget(artists_box, left_horizontal_chunks[0]);
+    } else if let Some(library) = library {
+        let (window, mut window_state) = utils::visible_window(
+            artist_list_state,
+            library.artists.len(),
+            left_horizontal_chunks[0],
+        );
+        let artists_list: Vec<ratatui::widgets::ListItem> = library.artists[window]
             .iter()
             .map(|artist| {
-                // Calculate available width for artist name (subtract borders and padding)
-                let available_width = left_horizontal_chunks[0].width.saturating_sub(4) as usize;
+                // Artists that aren't loaded yet show no count rather than a wrong one
+                let count = artist
+                    .album_count()
+                    .map(|count| format!(" ({})", count))
+                    .unwrap_or_default();
+                // Calculate available width for artist name (subtract borders, padding,
+                // count and marker)
+                let available_width = (left_horizontal_chunks[0].width.saturating_sub(4) as usize)
+                    .saturating_sub(utils::QUEUED_MARKER_WIDTH + count.len());
                 let truncated_name = WIDTH_CACHE.with(|cache| {
                     let mut cache = cache.borrow_mut();
                     utils::truncate_by_width_cached(&mut cache, &artist.name, available_width)
                 });
-                ratatui::widgets::ListItem::new(vec![Line::from(truncated_name)])
+                let marker = utils::queued_marker(queued.contains_artist(&artist.name));
+                let mut line = Line::from(vec![
+                    Span::raw(truncated_name),
+                    Span::styled(count, config.colors.border_title_color()),
+                    Span::styled(marker, config.colors.track_duration_color()),
+                ]);
+                if playing_artist == Some(artist.name.as_str()) {
+                    line = line.patch_style(playing_style);
+                }
+                ratatui::widgets::ListItem::new(vec![line])
             })
             .collect();
 
@@ -125,10 +178,17 @@ pub fn render_artists_mode(
         frame.render_stateful_widget(
             artists_list_widget,
             left_horizontal_chunks[0],
+            &mut window_state,
+        );
+        render_list_scrollbar(
+            frame,
+            left_horizontal_chunks[0],
+            library.artists.len(),
             artist_list_state,
+            config,
         );
     } else {
-        let artists_box = create_empty_box("Artists", config);
+        let artists_box = create_message_box("Artists", library_unavailable_lines(), config);
         frame.render_widget(artists_box, left_horizontal_chunks[0]);
     }
 
@@ -152,6 +212,12 @@ pub fn render_artists_mode(
                 items.to_vec()
             });
 
+            let playing_album = current_song
+                .as_ref()
+                .filter(|_| playing_artist == Some(selected_artist.name.as_str()))
+                .map(|song| song.album.as_str());
+            let playing_file = current_song.as_ref().map(|song| &song.file_path);
+
             let albums_list: Vec<ratatui::widgets::ListItem> = display_items
                 .iter()
                 .map(|item| {
@@ -165,18 +231,16 @@ pub fn render_artists_mode(
                                 .unwrap();
 
                             // Format total duration using cache
-                            let duration_str =
-                                RENDER_CACHE.with(|cache| match album.total_duration() {
-                                    Some(duration) => {
-                                        let mut cache = cache.borrow_mut();
-                                        cache.durations.format_long(duration.as_secs()).to_owned()
-                                    }
-                                    None => "--:--".to_owned(),
-                                });
+                            let duration_str = utils::album_row_details(
+                                album,
+                                config.ui.time_format(),
+                                config.ui.album_format_badges,
+                            );
 
                             // Calculate available width for filler (subtract album name width and duration width + spaces)
-                            let available_width =
-                                left_horizontal_chunks[1].width.saturating_sub(4) as usize; // 4 for borders/padding
+                            let available_width = (left_horizontal_chunks[1].width.saturating_sub(4)
+                                as usize) // 4 for borders/padding
+                                .saturating_sub(utils::QUEUED_MARKER_WIDTH);
                             let duration_width = duration_str.width();
                             let max_album_name_width =
                                 available_width.saturating_sub(duration_width + 4); // 6 for " " before/after and "     " between name and duration
@@ -211,25 +275,34 @@ pub fn render_artists_mode(
                             let display_text =
                                 format!(" {}{}   {}", truncated_album_name, filler, duration_str);
 
+                            let marker = utils::queued_marker(queued.contains_any(&album.tracks));
+                            let mut album_style = Style::default().fg(config.colors.album_color());
+                            if playing_album == Some(album_name.as_str()) {
+                                album_style = album_style.patch(playing_style);
+                            }
                             ratatui::widgets::ListItem::new(vec![
-                                Line::from(display_text)
-                                    .style(Style::default().fg(config.colors.album_color())),
+                                Line::from(vec![
+                                    Span::raw(display_text),
+                                    Span::styled(marker, config.colors.track_duration_color()),
+                                ])
+                                .style(album_style),
                             ])
                         }
-                        DisplayItem::Song(song_title, duration, _file_path) => {
+                        DisplayItem::Song(song_title, duration, file_path) => {
                             let song_duration_str = RENDER_CACHE.with(|cache| match duration {
                                 Some(duration) => {
                                     let mut cache = cache.borrow_mut();
                                     format!(
                                         "  {}",
-                                        cache.durations.format_short(duration.as_secs())
+                                        cache.durations.format(*duration, config.ui.time_format())
                                     )
                                 }
                                 None => "  --:--".to_owned(),
                             });
 
                             let available_width =
-                                left_horizontal_chunks[1].width.saturating_sub(4) as usize;
+                                (left_horizontal_chunks[1].width.saturating_sub(4) as usize)
+                                    .saturating_sub(utils::QUEUED_MARKER_WIDTH);
                             let song_duration_width = song_duration_str.width();
                             let max_song_title_width =
                                 available_width.saturating_sub(song_duration_width + 3); // 3 for "   " prefix
@@ -247,11 +320,7 @@ pub fn render_artists_mode(
                             let filler_width =
                                 max_song_title_width.saturating_sub(truncated_song_title.width());
                             let filler = RENDER_CACHE.with(|cache| {
-                                cache
-                                    .borrow()
-                                    .fillers
-                                    .spaces(filler_width.max(0))
-                                    .to_owned()
+                                cache.borrow().fillers.spaces(filler_width).to_owned()
                             });
 
                             let song_text = format!("   {}{}", truncated_song_title, filler,);
@@ -263,7 +332,15 @@ pub fn render_artists_mode(
                                 song_duration_str.clone(),
                                 Style::default().fg(config.colors.track_duration_color()),
                             ));
-                            ratatui::widgets::ListItem::new(vec![Line::from(spans)])
+                            spans.push(Span::styled(
+                                utils::queued_marker(queued.contains_file(file_path)),
+                                config.colors.track_duration_color(),
+                            ));
+                            let mut line = Line::from(spans);
+                            if playing_file == Some(file_path) {
+                                line = line.patch_style(playing_style);
+                            }
+                            ratatui::widgets::ListItem::new(vec![line])
                         }
                     }
                 })
@@ -300,6 +377,13 @@ pub fn render_artists_mode(
                 left_horizontal_chunks[1],
                 album_display_list_state,
             );
+            render_list_scrollbar(
+                frame,
+                left_horizontal_chunks[1],
+                display_items.len(),
+                album_display_list_state,
+                config,
+            );
         } else {
             let tracks_box = create_empty_box("Albums", config);
             frame.render_widget(tracks_box, left_horizontal_chunks[1]);
@@ -310,8 +394,16 @@ pub fn render_artists_mode(
     }
 
     // Render progress bar under the two empty boxes
-    let progress_widget = create_left_box_bottom(play_state, progress, elapsed, duration, config);
-    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+    let progress_widget = create_left_box_bottom(
+        play_state,
+        progress,
+        elapsed,
+        duration,
+        mpd_status.as_ref(),
+        config,
+    );
+    frame.render_widget(progress_widget, left_vertical_chunks[2]);
+    let progress_area = left_vertical_chunks[2];
 
     // Split the right area vertically: image on top, song info at bottom
     let right_vertical_chunks = Layout::vertical([
@@ -323,9 +415,22 @@ pub fn render_artists_mode(
     let image_area = right_vertical_chunks[0];
 
     // Render image or placeholder
-    render_image_widget(frame, protocol, image_area, skip_image_render);
+    render_image_widget(
+        frame,
+        protocol,
+        image_area,
+        &config.cover,
+        config.colors.cover_border_color(),
+        skip_image_render || narrow,
+    );
 
     // Render the song information
     let song_widget = create_song_widget(current_song, config);
-    frame.render_widget(song_widget, right_vertical_chunks[1]);
+    if narrow {
+        frame.render_widget(song_widget, left_vertical_chunks[1]);
+    } else {
+        frame.render_widget(song_widget, right_vertical_chunks[1]);
+    }
+
+    progress_area
 }
@@ -0,0 +1,202 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+use crate::app::SongInfo;
+use crate::app::navigation::file_browser::{FileBrowser, FileEntry};
+use crate::app::ui::widgets::{
+    create_format_widget, create_left_box_bottom, create_message_box, create_song_widget,
+    create_top_box, render_image_widget, render_list_scrollbar,
+};
+use crate::app::ui::{MenuMode, QueuedSet, RENDER_CACHE, WIDTH_CACHE, rendering::utils};
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_files_mode(
+    frame: &mut Frame<'_>,
+    protocol: &mut crate::app::ui::Protocol,
+    area: Rect,
+    format: &Option<String>,
+    current_song: &Option<SongInfo>,
+    config: &Config,
+    file_browser: &mut FileBrowser,
+    queued: &QueuedSet,
+    play_state: &Option<mpd_client::responses::PlayState>,
+    progress: Option<f64>,
+    elapsed: Option<std::time::Duration>,
+    duration: Option<std::time::Duration>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    menu_mode: &MenuMode,
+    bit_perfect_enabled: bool,
+    muted: bool,
+    skip_image_render: bool,
+) -> Rect {
+    // Same layout as queue mode, with the directory listing in place of the queue
+    let main_vertical_chunks = Layout::vertical([
+        Constraint::Length(1),       // Format info takes 1 line
+        Constraint::Length(3),       // New middle box takes 3 lines
+        Constraint::Percentage(100), // Remaining content takes rest
+    ])
+    .split(area);
+
+    let bottom_horizontal_chunks = Layout::horizontal([
+        Constraint::Percentage(50), // Directory listing takes 50% of width
+        Constraint::Percentage(50), // Right content takes 50% of width
+    ])
+    .split(main_vertical_chunks[2]);
+
+    let left_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Listing takes most of the space
+        Constraint::Length(3),       // Progress bar takes 3 lines
+    ])
+    .split(bottom_horizontal_chunks[0]);
+    let list_area = left_vertical_chunks[0];
+
+    // Render format info widget at top
+    let format_widget = create_format_widget(format, current_song, mpd_status.as_ref(), config);
+    frame.render_widget(format_widget, main_vertical_chunks[0]);
+
+    // Render middle box that spans both splits
+    let middle_box = create_top_box(
+        config,
+        mpd_status.as_ref(),
+        menu_mode,
+        bit_perfect_enabled,
+        config.pipewire.is_available(),
+        muted,
+    );
+    frame.render_widget(middle_box, main_vertical_chunks[1]);
+
+    // The box is titled with the directory, "/" being the root of the music directory
+    let title = format!("/{}", file_browser.path);
+    if file_browser.entries.is_empty() {
+        let files_box = create_message_box(&title, vec![Line::from("Empty directory")], config);
+        frame.render_widget(files_box, list_area);
+    } else {
+        let (window, mut window_state) = utils::visible_window(
+            &mut file_browser.list_state,
+            file_browser.entries.len(),
+            list_area,
+        );
+        let available_width =
+            (list_area.width.saturating_sub(4) as usize).saturating_sub(utils::QUEUED_MARKER_WIDTH);
+        let items: Vec<ListItem> = file_browser.entries[window]
+            .iter()
+            .map(|entry| match entry {
+                FileEntry::Directory(_) => {
+                    let name = WIDTH_CACHE.with(|cache| {
+                        let mut cache = cache.borrow_mut();
+                        utils::truncate_by_width_cached(
+                            &mut cache,
+                            entry.name(),
+                            available_width.saturating_sub(2),
+                        )
+                    });
+                    ListItem::new(Line::from(vec![
+                        Span::styled("󰉋 ", config.colors.queue_album_color()),
+                        Span::styled(name, config.colors.queue_album_color()),
+                    ]))
+                }
+                FileEntry::Song(song) => {
+                    let duration_str = RENDER_CACHE.with(|cache| match song.duration {
+                        Some(duration) => {
+                            let mut cache = cache.borrow_mut();
+                            format!(
+                                "  {}",
+                                cache.durations.format(duration, config.ui.time_format())
+                            )
+                        }
+                        None => "  --:--".to_owned(),
+                    });
+                    let max_name_width = available_width.saturating_sub(duration_str.width() + 2);
+                    let name = WIDTH_CACHE.with(|cache| {
+                        let mut cache = cache.borrow_mut();
+                        utils::truncate_by_width_cached(&mut cache, entry.name(), max_name_width)
+                    });
+                    let filler_width = max_name_width.saturating_sub(name.width());
+                    let filler = RENDER_CACHE
+                        .with(|cache| cache.borrow().fillers.spaces(filler_width).to_owned());
+
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("  {}{}", name, filler),
+                            config.colors.queue_song_title_color(),
+                        ),
+                        Span::styled(
+                            duration_str,
+                            Style::default().fg(config.colors.track_duration_color()),
+                        ),
+                        Span::styled(
+                            utils::queued_marker(queued.contains_file(&song.file_path)),
+                            config.colors.track_duration_color(),
+                        ),
+                    ]))
+                }
+            })
+            .collect();
+
+        let files_list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(
+                        Line::from(format!(" {} ", title)).fg(config.colors.border_title_color()),
+                    )
+                    .border_style(Style::default().fg(config.colors.border_color())),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(config.colors.queue_selected_text_color())
+                    .bg(config.colors.queue_selected_highlight_color()),
+            );
+        frame.render_stateful_widget(files_list, list_area, &mut window_state);
+        render_list_scrollbar(
+            frame,
+            list_area,
+            file_browser.entries.len(),
+            &file_browser.list_state,
+            config,
+        );
+    }
+
+    // Render progress bar under the listing
+    let progress_widget = create_left_box_bottom(
+        play_state,
+        progress,
+        elapsed,
+        duration,
+        mpd_status.as_ref(),
+        config,
+    );
+    frame.render_widget(progress_widget, left_vertical_chunks[1]);
+    let progress_area = left_vertical_chunks[1];
+
+    // Split the right area vertically: image on top, song info at bottom
+    let right_vertical_chunks = Layout::vertical([
+        Constraint::Percentage(100), // Image takes most space
+        Constraint::Length(4),       // Song info takes 4 lines
+    ])
+    .split(bottom_horizontal_chunks[1]);
+
+    // Render image or placeholder
+    render_image_widget(
+        frame,
+        protocol,
+        right_vertical_chunks[0],
+        &config.cover,
+        config.colors.cover_border_color(),
+        skip_image_render,
+    );
+
+    // Render the song information
+    let song_widget = create_song_widget(current_song, config);
+    frame.render_widget(song_widget, right_vertical_chunks[1]);
+
+    progress_area
+}
@@ -4,6 +4,8 @@ pub enum MenuMode {
     Queue,
     Artists,
     Albums,
+    /// MPD's music directory, browsed folder by folder
+    Files,
 }
 
 /// Panel focus for Tracks mode
@@ -1,6 +1,8 @@
 pub mod albums;
 pub mod artists;
+pub mod files;
 pub mod menu;
+pub mod now_playing;
 pub mod queue;
 
 pub use menu::{MenuMode, PanelFocus};
@@ -0,0 +1,94 @@
+use crate::app::{
+    config::Config,
+    song::SongInfo,
+    ui::{
+        Protocol,
+        rendering::utils,
+        widgets::{create_left_box_bottom, render_image_widget},
+    },
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::Paragraph,
+};
+
+/// Fullscreen view with just the cover, the song and the progress bar
+#[allow(clippy::too_many_arguments)]
+pub fn render_now_playing_mode(
+    frame: &mut Frame<'_>,
+    protocol: &mut Protocol,
+    area: Rect,
+    current_song: &Option<SongInfo>,
+    config: &Config,
+    play_state: &Option<mpd_client::responses::PlayState>,
+    progress: Option<f64>,
+    elapsed: Option<std::time::Duration>,
+    duration: Option<std::time::Duration>,
+    mpd_status: &Option<mpd_client::responses::Status>,
+    skip_image_render: bool,
+) -> Rect {
+    let vertical_chunks = Layout::vertical([
+        Constraint::Length(1),       // Breathing room above the cover
+        Constraint::Percentage(100), // Cover takes everything that is left
+        Constraint::Length(1),       // Gap between cover and text
+        Constraint::Length(3),       // Title, artist and album
+        Constraint::Length(1),       // Gap between text and progress bar
+        Constraint::Length(3),       // Progress bar takes 3 lines
+        Constraint::Length(1),       // Bottom margin
+    ])
+    .split(area);
+
+    // The cover is meant to fill the screen here, so the size limits and the
+    // configured position of the regular views don't apply
+    let cover_config = crate::app::config::cover::CoverConfig {
+        max_width: 0,
+        max_height: 0,
+        position: "center".to_string(),
+        ..config.cover.clone()
+    };
+    render_image_widget(
+        frame,
+        protocol,
+        vertical_chunks[1],
+        &cover_config,
+        config.colors.cover_border_color(),
+        skip_image_render,
+    );
+
+    let lines = match current_song {
+        Some(song) => vec![
+            Line::from(song.title.as_str())
+                .bold()
+                .fg(config.colors.song_title_color()),
+            Line::from(song.display_artists(&config.ui.artist_separator))
+                .fg(config.colors.artist_color()),
+            Line::from(song.album.as_str()).fg(config.colors.album_color()),
+        ],
+        None => vec![Line::default(), Line::from("No song playing").dark_gray()],
+    };
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default()).centered(),
+        vertical_chunks[3],
+    );
+
+    // Keep the progress bar readable on wide terminals instead of stretching it
+    let progress_area = utils::center_area(
+        vertical_chunks[5],
+        Constraint::Percentage(60),
+        Constraint::Length(3),
+    );
+    let progress_bar = create_left_box_bottom(
+        play_state,
+        progress,
+        elapsed,
+        duration,
+        mpd_status.as_ref(),
+        config,
+    );
+    frame.render_widget(progress_bar, progress_area);
+
+    progress_area
+}
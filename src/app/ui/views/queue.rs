@@ -1,12 +1,14 @@
 use crate::app::{
     ListState, MenuMode,
     config::Config,
+    navigation::queue_filter::QueueFilter,
     song::SongInfo,
     ui::{
         Protocol,
+        rendering::utils,
         widgets::{
             create_format_widget, create_left_box_bottom, create_left_box_top, create_song_widget,
-            create_top_box, render_image_widget,
+            create_top_box, render_image_widget, render_list_scrollbar,
         },
     },
 };
@@ -24,6 +26,7 @@ pub fn render_queue_mode(
     current_song: &Option<SongInfo>,
     queue: &[SongInfo],
     queue_list_state: &mut ListState,
+    queue_filter: Option<&mut QueueFilter>,
     config: &Config,
     play_state: &Option<mpd_client::responses::PlayState>,
     progress: Option<f64>,
@@ -32,8 +35,9 @@ pub fn render_queue_mode(
     mpd_status: &Option<mpd_client::responses::Status>,
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
+    muted: bool,
     skip_image_render: bool,
-) {
+) -> Rect {
     // Original layout - restore exactly as it was before changes
     // Split the area horizontally: left box, right content
     // Split area vertically: top section, middle section, bottom section
@@ -58,7 +62,7 @@ pub fn render_queue_mode(
     .split(bottom_horizontal_chunks[0]);
 
     // Render format info widget at top
-    let format_widget = create_format_widget(format, current_song, config);
+    let format_widget = create_format_widget(format, current_song, mpd_status.as_ref(), config);
     frame.render_widget(format_widget, main_vertical_chunks[0]);
 
     // Render middle box that spans both splits
@@ -68,22 +72,76 @@ pub fn render_queue_mode(
         menu_mode,
         bit_perfect_enabled,
         config.pipewire.is_available(),
+        muted,
     );
     frame.render_widget(middle_box, main_vertical_chunks[1]);
 
     // Render widgets in left vertical split
-    let left_box_top = create_left_box_top(
-        queue,
-        queue_list_state,
-        current_song,
-        config,
-        left_vertical_chunks[0],
-    );
-    frame.render_stateful_widget(left_box_top, left_vertical_chunks[0], queue_list_state);
+    // Only the visible rows are built, long queues would otherwise dominate the frame time
+    if let Some(filter) = queue_filter {
+        // The window and scrollbar are over the matches, which keep their queue indices
+        let mut filter_state =
+            ListState::default()
+                .with_offset(filter.offset)
+                .with_selected(queue_list_state.selected().and_then(|selected| {
+                    filter.matches.iter().position(|&index| index == selected)
+                }));
+        let (window, mut window_state) = utils::visible_window(
+            &mut filter_state,
+            filter.matches.len(),
+            left_vertical_chunks[0],
+        );
+        filter.offset = filter_state.offset();
+        let left_box_top = create_left_box_top(
+            queue,
+            Some(&*filter),
+            window,
+            queue_list_state,
+            mpd_status.as_ref(),
+            config,
+            left_vertical_chunks[0],
+        );
+        frame.render_stateful_widget(left_box_top, left_vertical_chunks[0], &mut window_state);
+        render_list_scrollbar(
+            frame,
+            left_vertical_chunks[0],
+            filter.matches.len(),
+            &filter_state,
+            config,
+        );
+    } else {
+        let (window, mut window_state) =
+            utils::visible_window(queue_list_state, queue.len(), left_vertical_chunks[0]);
+        let left_box_top = create_left_box_top(
+            queue,
+            None,
+            window,
+            queue_list_state,
+            mpd_status.as_ref(),
+            config,
+            left_vertical_chunks[0],
+        );
+        frame.render_stateful_widget(left_box_top, left_vertical_chunks[0], &mut window_state);
+        render_list_scrollbar(
+            frame,
+            left_vertical_chunks[0],
+            queue.len(),
+            queue_list_state,
+            config,
+        );
+    }
 
     // Render widgets in left vertical split
-    let left_box_bottom = create_left_box_bottom(play_state, progress, elapsed, duration, config);
+    let left_box_bottom = create_left_box_bottom(
+        play_state,
+        progress,
+        elapsed,
+        duration,
+        mpd_status.as_ref(),
+        config,
+    );
     frame.render_widget(left_box_bottom, left_vertical_chunks[1]);
+    let progress_area = left_vertical_chunks[1];
 
     // Split the right area vertically: image on top, song info at bottom
     let right_vertical_chunks = Layout::vertical([
@@ -95,9 +153,18 @@ pub fn render_queue_mode(
     let image_area = right_vertical_chunks[0];
 
     // Render image or placeholder
-    render_image_widget(frame, protocol, image_area, skip_image_render);
+    render_image_widget(
+        frame,
+        protocol,
+        image_area,
+        &config.cover,
+        config.colors.cover_border_color(),
+        skip_image_render,
+    );
 
     // Render the song information
     let song_widget = create_song_widget(current_song, config);
     frame.render_widget(song_widget, right_vertical_chunks[1]);
+
+    progress_area
 }
@@ -0,0 +1,72 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a small centered overlay of `fields` as aligned key/value rows
+pub fn render_fields_popup(
+    frame: &mut Frame,
+    title: &str,
+    fields: &[(String, String)],
+    config: &Config,
+) {
+    let area = frame.area();
+
+    let title = format!(" {} ", title);
+    let footer = " Esc: close ";
+
+    // Align values by padding keys to the widest one
+    let key_width = fields.iter().map(|(k, _)| k.width()).max().unwrap_or(0);
+    let value_width = fields.iter().map(|(_, v)| v.width()).max().unwrap_or(0);
+
+    // Popup width: padded key + gap + value + borders and margins
+    let content_width = (key_width + value_width + 6).max(title.width() + 4) as u16;
+    let popup_width = content_width.min(area.width.saturating_sub(4));
+    // Popup height: settings + borders (2)
+    let popup_height = (fields.len() as u16 + 2).min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = fields
+        .iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}  ", key, width = key_width),
+                    Style::default().fg(config.colors.top_accent_color()),
+                ),
+                Span::styled(
+                    value.as_str(),
+                    Style::default().fg(config.colors.song_title_color()),
+                ),
+            ])
+        })
+        .collect();
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(title.as_str()).fg(config.colors.border_title_color()))
+        .title_bottom(
+            Line::from(footer)
+                .fg(config.colors.top_accent_color())
+                .centered(),
+        )
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    frame.render_widget(Paragraph::new(lines).block(popup_block), popup_area);
+}
@@ -1,27 +1,71 @@
 use ratatui::{
-    style::Style,
-    text::Span,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
 };
 
 use crate::app::Config;
 
+/// Rounded, titled block shared by the placeholder boxes below
+fn titled_block<'a>(title: &'a str, config: &Config) -> Block<'a> {
+    Block::default()
+        .border_type(BorderType::Rounded)
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            format!(" {} ", title),
+            Style::default().fg(config.colors.border_title_color()),
+        ))
+        .border_style(Style::default().fg(config.colors.border_color()))
+}
+
 pub fn create_empty_box<'a>(title: &'a str, config: &Config) -> Paragraph<'a> {
-    let border_color = config.colors.border_color();
-    let border_title_color = config.colors.border_title_color();
     let text_color = config.colors.song_title_color();
 
     Paragraph::new("")
-        .block(
-            Block::default()
-                .border_type(BorderType::Rounded)
-                .borders(Borders::ALL)
-                .title(Span::styled(
-                    format!(" {} ", title),
-                    Style::default().fg(border_title_color),
-                ))
-                .border_style(Style::default().fg(border_color)),
-        )
+        .block(titled_block(title, config))
         .style(Style::default().fg(text_color))
         .centered()
 }
+
+/// Bordered box with a centered explanation instead of list contents
+pub fn create_message_box<'a>(
+    title: &'a str,
+    lines: Vec<Line<'a>>,
+    config: &Config,
+) -> Paragraph<'a> {
+    // Leave a blank line under the top border so the message doesn't hug it
+    let lines: Vec<Line> = std::iter::once(Line::default()).chain(lines).collect();
+
+    Paragraph::new(lines)
+        .block(titled_block(title, config))
+        .style(Style::default().fg(config.colors.song_title_color()))
+        .wrap(Wrap { trim: true })
+        .centered()
+}
+
+/// Explanation shown when MPD's database has no songs at all
+pub fn empty_library_lines(config: &Config) -> Vec<Line<'static>> {
+    let refresh_key = config
+        .binds
+        .refresh
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "u".to_string());
+    vec![
+        Line::from("No music found").bold(),
+        Line::default(),
+        Line::from("Check music_directory in your MPD config,")
+            .fg(config.colors.border_title_color()),
+        Line::from(format!("then press {} to update the database", refresh_key))
+            .fg(config.colors.border_title_color()),
+    ]
+}
+
+/// Explanation shown when the library could not be loaded from MPD at all
+pub fn library_unavailable_lines() -> Vec<Line<'static>> {
+    vec![
+        Line::from("Library unavailable").bold(),
+        Line::default(),
+        Line::from("Loading it from MPD failed, see the log for details"),
+    ]
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a centered, scrollable overlay listing every keybinding grouped by mode.
+/// `sections` are (mode, rows of (action, keys)), `scroll` is clamped to the content.
+pub fn render_help_popup(
+    frame: &mut Frame,
+    sections: &[(&str, Vec<(String, String)>)],
+    scroll: usize,
+    config: &Config,
+) {
+    let area = frame.area();
+
+    let title = " Keybindings ";
+    let footer = " j/k: scroll  any other key: close ";
+
+    // Align keys by padding actions to the widest one
+    let action_width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(action, _)| action.width()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (mode, rows) in sections {
+        lines.push(Line::from(Span::styled(
+            format!(" {}", mode),
+            Style::default()
+                .fg(config.colors.border_title_color())
+                .bold(),
+        )));
+        for (action, keys) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("   {:<width$}  ", action, width = action_width),
+                    Style::default().fg(config.colors.song_title_color()),
+                ),
+                Span::styled(
+                    keys.as_str(),
+                    Style::default().fg(config.colors.top_accent_color()),
+                ),
+            ]));
+        }
+        lines.push(Line::default());
+    }
+
+    let content_width = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+    let popup_width = (content_width as u16 + 4).max(footer.width() as u16 + 4);
+    let popup_width = popup_width.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let visible = popup_height.saturating_sub(2) as usize;
+    let scroll = scroll.min(lines.len().saturating_sub(visible)) as u16;
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(title).fg(config.colors.border_title_color()))
+        .title_bottom(
+            Line::from(footer)
+                .fg(config.colors.top_accent_color())
+                .centered(),
+        )
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    frame.render_widget(
+        Paragraph::new(lines).block(popup_block).scroll((scroll, 0)),
+        popup_area,
+    );
+}
@@ -1,9 +1,11 @@
 use ratatui::{
-    layout::{Constraint, Rect},
-    style::{Style, Stylize},
-    widgets::Paragraph,
+    layout::{Constraint, Margin, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Borders, Paragraph},
 };
 
+use crate::app::config::cover::{CoverConfig, CoverScaling};
+use crate::app::ui::ImageSupport;
 use crate::app::ui::rendering::utils::*;
 use ratatui_image::{Resize, StatefulImage};
 
@@ -11,6 +13,8 @@ pub fn render_image_widget(
     frame: &mut ratatui::Frame<'_>,
     protocol: &mut crate::app::ui::Protocol,
     image_area: Rect,
+    cover_config: &CoverConfig,
+    border_color: Color,
     skip_render: bool,
 ) {
     use image::imageops::FilterType;
@@ -24,16 +28,82 @@ pub fn render_image_widget(
         return;
     }
 
-    if let Some(ref mut img) = protocol.image {
-        // Get the image dimensions after resizing for the available area
+    if protocol.support == ImageSupport::Unsupported {
+        let placeholder_area =
+            center_area(image_area, Constraint::Length(21), Constraint::Length(1));
+        let placeholder =
+            Paragraph::new("Cover art unavailable").style(Style::default().dark_gray());
+        frame.render_widget(placeholder, placeholder_area);
+        return;
+    }
+
+    if !cover_config.enabled {
+        let placeholder_area =
+            center_area(image_area, Constraint::Length(18), Constraint::Length(1));
+        let placeholder = Paragraph::new("Cover art disabled").style(Style::default().dark_gray());
+        frame.render_widget(placeholder, placeholder_area);
+        return;
+    }
+
+    if protocol.image.is_some() {
         let resize = Resize::Scale(Some(FilterType::Lanczos3));
-        let img_rect = img.size_for(resize.clone(), image_area);
+        let border = cover_config.cover_border();
+
+        // With a frame the image is sized and drawn inside it, one cell in on every side
+        let available_area = match border {
+            Some(_) => image_area.inner(Margin::new(1, 1)),
+            None => image_area,
+        };
+        let max_area = limit_area(
+            available_area,
+            cover_config.max_width,
+            cover_config.max_height,
+        );
+        if cover_config.cover_scaling() == CoverScaling::Fill {
+            protocol.crop_to_fill(max_area);
+        }
+        let Some(ref mut img) = protocol.image else {
+            return;
+        };
+
+        // Get the image dimensions after resizing for the (size-limited) available area
+        let img_rect = img.size_for(resize.clone(), max_area);
+
+        // Place the image within the available area
+        let position = cover_config.cover_position();
+        let placed_area = position_image(img_rect, available_area, position);
+
+        // A letterbox color fills the whole cover area, and the frame goes around it
+        let background = cover_config.background_rgb();
+        let framed_area = match background {
+            Some((r, g, b)) => {
+                let letterbox = position_image(max_area, available_area, position);
+                let fill = Block::default().style(Style::default().bg(Color::Rgb(r, g, b)));
+                frame.render_widget(fill, letterbox);
+                letterbox
+            }
+            None => placed_area,
+        };
 
-        // Center the image within the available area
-        let centered_area = center_image(img_rect, image_area);
+        // The frame hugs the placed image rather than the whole panel
+        if let Some(border_type) = border
+            && !framed_area.is_empty()
+        {
+            let frame_area = Rect {
+                x: framed_area.x - 1,
+                y: framed_area.y - 1,
+                width: framed_area.width + 2,
+                height: framed_area.height + 2,
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(border_type)
+                .border_style(Style::default().fg(border_color));
+            frame.render_widget(block, frame_area);
+        }
 
         let image = StatefulImage::default().resize(resize);
-        frame.render_stateful_widget(image, centered_area, img);
+        frame.render_stateful_widget(image, placed_area, img);
     } else {
         let placeholder_area =
             center_area(image_area, Constraint::Length(12), Constraint::Length(1));
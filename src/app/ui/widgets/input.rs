@@ -0,0 +1,68 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a centered single-line text input overlay showing `input` after `label`
+pub fn render_input_popup(
+    frame: &mut Frame,
+    title: &str,
+    label: &str,
+    input: &str,
+    config: &Config,
+) {
+    let area = frame.area();
+
+    let footer = "Enter: set  Esc: cancel";
+
+    // Popup width: widest line + padding (2 on each side) + borders (1 on each side)
+    let content_width = (label.width() + input.width() + 1)
+        .max(footer.width())
+        .max(title.width()) as u16;
+    let popup_width = (content_width + 6).min(area.width.saturating_sub(4));
+
+    // Popup height: empty line + input + empty line + footer + borders (2)
+    let popup_height = 6.min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(label, Style::default().fg(config.colors.song_title_color())),
+            Span::styled(
+                format!("{}_", input),
+                Style::default().fg(config.colors.volume_color()),
+            ),
+        ])
+        .centered(),
+        Line::from(""),
+        Line::from(Span::styled(
+            footer,
+            Style::default().fg(config.colors.top_accent_color()),
+        ))
+        .centered(),
+    ];
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(format!(" {} ", title)).fg(config.colors.border_title_color()))
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    frame.render_widget(Paragraph::new(lines).block(popup_block), popup_area);
+}
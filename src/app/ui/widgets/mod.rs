@@ -1,15 +1,35 @@
+pub mod confirm;
+pub mod fields;
 pub mod generic;
+pub mod help;
 pub mod image;
+pub mod input;
 pub mod progress;
 pub mod queue;
+pub mod scrollbar;
+pub mod search;
+pub mod select;
 pub mod song;
+pub mod toast;
 pub mod top_box;
+pub mod track_info;
 
 // Re-export all widget functions from separate modules
-pub use self::generic::create_empty_box;
+pub use self::confirm::render_confirm_popup;
+pub use self::fields::render_fields_popup;
+pub use self::generic::{
+    create_empty_box, create_message_box, empty_library_lines, library_unavailable_lines,
+};
+pub use self::help::render_help_popup;
 pub use self::image::render_image_widget;
+pub use self::input::render_input_popup;
+pub use self::scrollbar::render_list_scrollbar;
+pub use self::search::render_search_popup;
+pub use self::select::render_select_popup;
 pub use self::song::create_format_widget;
+pub use self::toast::render_toast;
 pub use self::top_box::create_top_box;
+pub use self::track_info::render_track_info_popup;
 
 // Legacy function names for backward compatibility
 pub use self::progress::create_progress_bar as create_left_box_bottom;
@@ -5,19 +5,71 @@ use ratatui::{
 };
 
 use crate::app::Config;
+use crate::app::config::ui::{FmtOpts, ProgressStyle};
 use crate::app::ui::RENDER_CACHE;
 
+/// Partial cells for the braille style, indexed by filled sub-steps
+const BRAILLE_STEPS: [&str; 2] = ["", "⡇"];
+/// Partial cells for the fraction-block style, indexed by filled eighths
+const EIGHTH_BLOCKS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Build the filled and empty parts of a `width` cells wide bar at `ratio` (0.0..=1.0).
+/// Styles with partial cells advance by `steps.len()` sub-steps per cell.
+fn sub_cell_bar(
+    ratio: f64,
+    width: usize,
+    steps: &[&str],
+    full: &str,
+    empty: &str,
+) -> (String, String) {
+    let units = (ratio * (width * steps.len()) as f64) as usize;
+    let full_cells = (units / steps.len()).min(width);
+    let partial = if full_cells < width {
+        steps[units % steps.len()]
+    } else {
+        ""
+    };
+    let used = full_cells + usize::from(!partial.is_empty());
+
+    let mut filled = full.repeat(full_cells);
+    filled.push_str(partial);
+    (filled, empty.repeat(width - used))
+}
+
+/// Build the filled and empty parts of the progress bar for the configured style
+fn progress_bar_parts(style: ProgressStyle, ratio: f64, width: usize) -> (String, String) {
+    let ratio = ratio.clamp(0.0, 1.0);
+    match style {
+        ProgressStyle::Line => {
+            // Use cached progress bar strings
+            let filled = ((ratio * width as f64) as usize).min(width);
+            RENDER_CACHE.with(|cache| {
+                let cache = cache.borrow();
+                (
+                    cache.fillers.progress_chars(filled).to_owned(),
+                    cache.fillers.progress_chars(width - filled).to_owned(),
+                )
+            })
+        }
+        ProgressStyle::Block => sub_cell_bar(ratio, width, &[""], "█", "█"),
+        ProgressStyle::Braille => sub_cell_bar(ratio, width, &BRAILLE_STEPS, "⣿", "⣀"),
+        ProgressStyle::FractionBlock => sub_cell_bar(ratio, width, &EIGHTH_BLOCKS, "█", "░"),
+    }
+}
+
 pub fn create_progress_bar(
     play_state: &Option<mpd_client::responses::PlayState>,
     progress: Option<f64>,
     elapsed: Option<std::time::Duration>,
     duration: Option<std::time::Duration>,
+    mpd_status: Option<&mpd_client::responses::Status>,
     config: &Config,
 ) -> impl ratatui::widgets::Widget {
     let border_color = config.colors.border_color();
     let border_title_color = config.colors.border_title_color();
     let song_title_color = config.colors.song_title_color();
-    let progress_filled_color = config.colors.progress_filled_color();
+    // The filled part takes the accent of the play state
+    let progress_filled_color = config.colors.progress_state_color(*play_state);
     let progress_empty_color = config.colors.progress_empty_color();
 
     let state_text = match play_state {
@@ -34,12 +86,22 @@ pub fn create_progress_bar(
         None => config.colors.stopped(),
     };
 
-    let progress_value = progress.unwrap_or(0.0);
-    let progress_percentage = (progress_value * 100.0) as u16;
+    // Position of the playing song in the queue, e.g. "3 / 42" ("— / 42" when stopped)
+    let queue_position = mpd_status
+        .filter(|status| status.playlist_length > 0)
+        .map(|status| match status.current_song {
+            Some((position, _)) if status.state != mpd_client::responses::PlayState::Stopped => {
+                format!(" {} / {} ", position.0 + 1, status.playlist_length)
+            }
+            _ => format!(" — / {} ", status.playlist_length),
+        });
+
+    let progress_ratio = progress.unwrap_or(0.0);
 
     struct DynamicProgressBar {
         state_text: String,
-        progress_percentage: u16,
+        progress_ratio: f64,
+        progress_style: ProgressStyle,
         border_title_color: Style,
         border_color: Style,
         song_title_color: Style,
@@ -51,15 +113,22 @@ pub fn create_progress_bar(
         time_separator_color: Style,
         elapsed: Option<std::time::Duration>,
         duration: Option<std::time::Duration>,
+        queue_position: Option<String>,
+        time_format: FmtOpts,
     }
 
     impl ratatui::widgets::Widget for DynamicProgressBar {
         fn render(self, area: ratatui::layout::Rect, buf: &mut ratatui::buffer::Buffer) {
-            let block = Block::default()
+            let mut block = Block::default()
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
                 .title(Span::styled(" Progress ", self.border_title_color))
                 .border_style(self.border_color);
+            if let Some(position) = self.queue_position {
+                block = block.title_bottom(
+                    Line::from(Span::styled(position, self.border_title_color)).right_aligned(),
+                );
+            }
 
             let inner = block.inner(area);
             block.render(area, buf);
@@ -71,12 +140,15 @@ pub fn create_progress_bar(
                     (Some(elapsed), Some(duration)) => vec![
                         Span::raw(" "),
                         Span::styled(
-                            cache.durations.format_short(elapsed.as_secs()).to_owned(),
+                            cache.durations.format(elapsed, self.time_format).to_owned(),
                             self.time_elapsed_color,
                         ),
                         Span::styled("/", self.time_separator_color),
                         Span::styled(
-                            cache.durations.format_short(duration.as_secs()).to_owned(),
+                            cache
+                                .durations
+                                .format(duration, self.time_format)
+                                .to_owned(),
                             self.time_duration_color,
                         ),
                         Span::raw(" "),
@@ -84,7 +156,7 @@ pub fn create_progress_bar(
                     (Some(elapsed), None) => vec![
                         Span::raw(" "),
                         Span::styled(
-                            cache.durations.format_short(elapsed.as_secs()).to_owned(),
+                            cache.durations.format(elapsed, self.time_format).to_owned(),
                             self.time_elapsed_color,
                         ),
                         Span::styled("/--:--", self.song_title_color),
@@ -94,7 +166,10 @@ pub fn create_progress_bar(
                         Span::raw(" "),
                         Span::styled("--:--/", self.song_title_color),
                         Span::styled(
-                            cache.durations.format_short(duration.as_secs()).to_owned(),
+                            cache
+                                .durations
+                                .format(duration, self.time_format)
+                                .to_owned(),
                             self.time_duration_color,
                         ),
                         Span::raw(" "),
@@ -110,17 +185,8 @@ pub fn create_progress_bar(
             let total_text_width = state_width + spacing_width + time_width;
 
             let bar_width = inner.width.saturating_sub(total_text_width as u16) as usize;
-            let filled = (self.progress_percentage as usize * bar_width / 100).min(bar_width);
-            let empty = bar_width.saturating_sub(filled);
-
-            // Use cached progress bar strings
-            let (filled_str, empty_str) = RENDER_CACHE.with(|cache| {
-                let cache = cache.borrow();
-                (
-                    cache.fillers.progress_chars(filled).to_owned(),
-                    cache.fillers.progress_chars(empty).to_owned(),
-                )
-            });
+            let (filled_str, empty_str) =
+                progress_bar_parts(self.progress_style, self.progress_ratio, bar_width);
 
             let mut content_spans = vec![
                 Span::styled(&self.state_text, self.state_color),
@@ -138,7 +204,8 @@ pub fn create_progress_bar(
 
     DynamicProgressBar {
         state_text: state_text.to_string(),
-        progress_percentage,
+        progress_ratio,
+        progress_style: config.ui.progress_style(),
         border_title_color: Style::default().fg(border_title_color),
         border_color: Style::default().fg(border_color),
         song_title_color: Style::default().fg(song_title_color),
@@ -150,5 +217,70 @@ pub fn create_progress_bar(
         time_separator_color: Style::default().fg(config.colors.time_separator()),
         elapsed,
         duration,
+        queue_position,
+        time_format: config.ui.time_format(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: [ProgressStyle; 4] = [
+        ProgressStyle::Block,
+        ProgressStyle::Braille,
+        ProgressStyle::Line,
+        ProgressStyle::FractionBlock,
+    ];
+
+    #[test]
+    fn test_bar_ends() {
+        for style in STYLES {
+            let (filled, empty) = progress_bar_parts(style, 0.0, 10);
+            assert_eq!((filled.chars().count(), empty.chars().count()), (0, 10));
+            let (filled, empty) = progress_bar_parts(style, 1.0, 10);
+            assert_eq!((filled.chars().count(), empty.chars().count()), (10, 0));
+            // Out of range ratios are clamped
+            assert_eq!(progress_bar_parts(style, 1.5, 10), (filled, empty));
+        }
+        assert_eq!(
+            progress_bar_parts(ProgressStyle::FractionBlock, 0.0, 4),
+            (String::new(), "░░░░".to_string())
+        );
+        assert_eq!(
+            progress_bar_parts(ProgressStyle::Braille, 1.0, 4),
+            ("⣿⣿⣿⣿".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_partial_cells() {
+        // 5/16 of 10 cells is 25 eighths: 3 full cells and one eighth
+        assert_eq!(
+            progress_bar_parts(ProgressStyle::FractionBlock, 0.3125, 10),
+            ("███▏".to_string(), "░░░░░░".to_string())
+        );
+        // 3/8 of 4 cells is 3 half cells
+        assert_eq!(
+            progress_bar_parts(ProgressStyle::Braille, 0.375, 4),
+            ("⣿⡇".to_string(), "⣀⣀".to_string())
+        );
+        // Just below a full cell there is no partial step to show
+        assert_eq!(
+            progress_bar_parts(ProgressStyle::Block, 0.29, 10),
+            ("██".to_string(), "████████".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bar_always_fills_its_width() {
+        for style in STYLES {
+            for width in [0, 1, 7, 33] {
+                for step in 0..=40 {
+                    let (filled, empty) = progress_bar_parts(style, step as f64 / 40.0, width);
+                    assert_eq!(filled.chars().count() + empty.chars().count(), width);
+                }
+            }
+        }
     }
 }
@@ -5,24 +5,40 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, List, ListItem, ListState},
 };
 
+use std::borrow::Cow;
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
 use crate::app::Config;
 use crate::app::SongInfo;
+use crate::app::config::ui::QueueColumnKind;
+use crate::app::navigation::queue_filter::QueueFilter;
 use crate::app::ui::{RENDER_CACHE, WIDTH_CACHE, rendering::utils};
 
+/// Queue list showing only the `window` rows (see `utils::visible_window`),
+/// so it is rendered with a state relative to the window start.
+/// With a `filter` the rows are its matches and `window` indexes into them.
 pub fn create_queue_widget<'a>(
     queue: &[SongInfo],
+    filter: Option<&QueueFilter>,
+    window: Range<usize>,
     queue_list_state: &ListState,
-    current_song: &Option<SongInfo>,
+    mpd_status: Option<&mpd_client::responses::Status>,
     config: &Config,
     area: Rect,
 ) -> List<'a> {
     let border_color = config.colors.border_color();
     let border_title_color = config.colors.border_title_color();
-    let queue_album_color = config.colors.queue_album_color();
-    let queue_artist_color = config.colors.queue_artist_color();
-    let queue_song_title_color = config.colors.queue_song_title_color();
-    let queue_position_color = config.colors.queue_position_color();
-    let queue_duration_color = config.colors.queue_duration_color();
+
+    // Queue position of the playing (or paused) song. Uses the position rather than
+    // the file so only one row is marked when the same file is queued twice.
+    let playing_position = mpd_status
+        .filter(|status| status.state != mpd_client::responses::PlayState::Stopped)
+        .and_then(|status| status.current_song)
+        .map(|(position, _)| position.0);
+
+    // Width of the playing marker column ("▶ ")
+    let marker_width = 2;
 
     // Calculate available width inside the box (minus borders and padding)
     let inner_width = area.width.saturating_sub(4) as usize; // 2 for borders, 2 for padding
@@ -30,6 +46,9 @@ pub fn create_queue_widget<'a>(
     let queue_items: Vec<ListItem> = if queue.is_empty() {
         vec![]
     } else {
+        let columns = config.ui.queue_columns();
+        let time_format = config.ui.time_format();
+
         // First, determine the maximum number width needed for proper alignment
         // Use cached position strings for width calculation
         let max_num_width = RENDER_CACHE.with(|cache| {
@@ -43,125 +62,169 @@ pub fn create_queue_widget<'a>(
                 .unwrap_or(3) // fallback to 3 for single digit
         });
 
-        queue
-            .iter()
+        // The layout is the same for every row, so it is worked out once
+        let widths =
+            utils::queue_column_widths(columns, inner_width.saturating_sub(marker_width), |kind| {
+                match kind {
+                    QueueColumnKind::Position => max_num_width,
+                    QueueColumnKind::Track => 2,
+                    _ => 5, // "MM:SS"
+                }
+            });
+
+        // One row past the window, to see whether the album continues after the last row
+        let shown = window.len();
+        let rows: Vec<usize> = match filter {
+            Some(filter) => {
+                filter.matches[window.start..(window.end + 1).min(filter.matches.len())].to_vec()
+            }
+            None => (window.start..(window.end + 1).min(queue.len())).collect(),
+        };
+        let album_separators = config.ui.queue_album_separators;
+        // What the filter matched is highlighted in the columns it looked at
+        let highlight_term = |tag| filter.and_then(|filter| filter.highlight_term(tag));
+        let title_term = highlight_term("title");
+        let artist_term = highlight_term("artist");
+        let album_term = highlight_term("album");
+        rows.iter()
             .enumerate()
-            .map(|(i, song)| {
-                // Calculate available width for entire line using consistent max_num_width
-                let separator_width = 3; // " ║ "
-                let duration_display_width = 4; // "M:SS"
-                let remaining_width = inner_width
-                    .saturating_sub(max_num_width + separator_width * 2 + duration_display_width);
-
-                // Split remaining width into 3 equal parts for title, artist, album
-                let field_width = remaining_width / 3;
-
-                // Format duration if available using cache
-                let duration_str = RENDER_CACHE.with(|cache| match song.duration {
-                    Some(duration) => {
-                        let mut cache = cache.borrow_mut();
-                        cache.durations.format_short(duration.as_secs()).to_owned()
-                    }
-                    None => " (--:--)".to_owned(),
-                });
-
-                // Truncate each field to its allocated width using Unicode-aware width with caching
-                let field_width_max = field_width.max(8);
-                let (title, artist, album) = WIDTH_CACHE.with(|cache| {
-                    let mut cache = cache.borrow_mut();
-                    (
-                        utils::left_align_cached(&mut cache, &song.title, field_width_max),
-                        utils::left_align_cached(&mut cache, &song.artist, field_width_max),
-                        utils::left_align_cached(&mut cache, &song.album, field_width_max),
-                    )
-                });
+            .take(shown)
+            .map(|(row, &i)| {
+                let song = &queue[i];
+                let ends_album = album_separators
+                    && rows
+                        .get(row + 1)
+                        .is_some_and(|&next| album_ends_before(queue, i, next));
 
                 // Check if this is the currently playing song
-                let is_currently_playing = current_song
-                    .as_ref()
-                    .map(|current| current.file_path == song.file_path)
-                    .unwrap_or(false);
+                let is_currently_playing = playing_position == Some(i);
 
                 // Check if this is the selected song
                 let is_selected = queue_list_state.selected() == Some(i);
 
-                // Create base style
-                let mut queue_album_color = Style::default().fg(queue_album_color);
-                let mut queue_song_title_color = Style::default().fg(queue_song_title_color);
-                let mut queue_artist_color = Style::default().fg(queue_artist_color);
-                let mut border_color = Style::default().fg(border_color);
-                let mut duration_color = Style::default().fg(queue_duration_color);
-                let mut pos_color = Style::default().fg(queue_position_color);
+                // Selection replaces every color, playing adds bold-italics on top
+                let style_for = |color| {
+                    let mut style = Style::default().fg(color);
+                    if is_selected {
+                        style = style
+                            .bg(config.colors.queue_selected_highlight_color())
+                            .fg(config.colors.queue_selected_text_color());
+                    }
+                    if is_currently_playing {
+                        style = style.bold().italic();
+                    }
+                    style
+                };
+                let separator_style = style_for(border_color);
+                // The selection already recolors the row, so matches are underlined there
+                let match_style = |style: Style| {
+                    if is_selected {
+                        style.bold().underlined()
+                    } else {
+                        style.bold().fg(config.colors.top_accent_color())
+                    }
+                };
 
-                // Apply background highlight for selected song
+                let mut marker_color = Style::default().fg(config.colors.playing());
                 if is_selected {
-                    queue_album_color = queue_album_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    queue_song_title_color = queue_song_title_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    queue_artist_color = queue_artist_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    border_color = border_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    duration_color = duration_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
-                    pos_color = pos_color
-                        .bg(config.colors.queue_selected_highlight_color())
-                        .fg(config.colors.queue_selected_text_color());
+                    // The marker keeps its own color so a selected playing row shows both cues
+                    marker_color = marker_color.bg(config.colors.queue_selected_highlight_color());
                 }
-
-                // Apply bold-italics to currently playing song content
                 if is_currently_playing {
-                    queue_album_color = queue_album_color.bold().italic();
-                    queue_song_title_color = queue_song_title_color.bold().italic();
-                    queue_artist_color = queue_artist_color.bold().italic();
-                    border_color = border_color.bold().italic();
-                    duration_color = duration_color.bold().italic();
-                    pos_color = pos_color.bold().italic();
+                    marker_color = marker_color.bold();
                 }
+                let marker = if is_currently_playing { "▶ " } else { "  " };
 
-                // Create spans with appropriate styling - use cached position string
-                let num_str = RENDER_CACHE.with(|cache| {
-                    let cache = cache.borrow();
-                    cache.queue_positions.get(i).to_owned()
-                });
-                let padded_num_str = format!("{:<width$}", num_str, width = max_num_width);
-                let mut spans = vec![Span::styled(padded_num_str, pos_color)];
-
-                // Each field should have its own style, but when selected should be overwritten by the selection styling
-                spans.push(Span::styled(title.clone(), queue_song_title_color));
-                spans.push(Span::styled(" ║ ", border_color));
-                spans.push(Span::styled(artist.clone(), queue_artist_color));
-                spans.push(Span::styled(" ║ ", border_color));
-                spans.push(Span::styled(album.clone(), queue_album_color));
-                spans.push(Span::styled(duration_str.clone(), duration_color));
-
-                // If this row is selected, add padding to fill the entire width
-                if is_selected {
-                    // Calculate the current line width by reconstructing the line content
-                    let line_content = format!(
-                        "{} ║ {} ║ {}{}",
-                        title.clone(),
-                        artist.clone(),
-                        album.clone(),
-                        duration_str.clone()
-                    );
-                    let current_width =
-                        unicode_width::UnicodeWidthStr::width(&line_content as &str)
-                            + max_num_width;
-                    let remaining_width = area.width.saturating_sub(current_width as u16) as usize;
+                let mut spans = vec![Span::styled(marker, marker_color)];
+                let mut line_width = marker_width;
+
+                for (index, (column, &width)) in columns.iter().zip(&widths).enumerate() {
+                    if index > 0 {
+                        let separator =
+                            utils::column_separator(columns[index - 1].kind, column.kind);
+                        line_width += separator.width();
+                        spans.push(Span::styled(separator, separator_style));
+                    }
+
+                    // Only the configured columns are formatted
+                    let (text, style) = match column.kind {
+                        QueueColumnKind::Position => {
+                            let num_str = RENDER_CACHE
+                                .with(|cache| cache.borrow().queue_positions.get(i).to_owned());
+                            let mut style = style_for(config.colors.queue_position_color());
+                            if is_currently_playing {
+                                style = style.fg(config.colors.playing());
+                            }
+                            (Cow::Owned(num_str), style)
+                        }
+                        QueueColumnKind::Track => (
+                            Cow::Owned(format!("{:>width$}", song.track_number, width = width)),
+                            style_for(config.colors.queue_position_color()),
+                        ),
+                        QueueColumnKind::Title => (
+                            Cow::Borrowed(song.title.as_str()),
+                            style_for(config.colors.queue_song_title_color()),
+                        ),
+                        QueueColumnKind::Artist => (
+                            song.display_artists(&config.ui.artist_separator),
+                            style_for(config.colors.queue_artist_color()),
+                        ),
+                        QueueColumnKind::Album => (
+                            Cow::Borrowed(song.album.as_str()),
+                            style_for(config.colors.queue_album_color()),
+                        ),
+                        QueueColumnKind::Duration => {
+                            // Format duration if available using cache
+                            let duration_str = RENDER_CACHE.with(|cache| match song.duration {
+                                Some(duration) => {
+                                    let mut cache = cache.borrow_mut();
+                                    cache.durations.format(duration, time_format).to_owned()
+                                }
+                                None => "--:--".to_owned(),
+                            });
+                            (
+                                Cow::Owned(format!("{:>width$}", duration_str, width = width)),
+                                style_for(config.colors.queue_duration_color()),
+                            )
+                        }
+                    };
+
+                    // Truncate each field to its allocated width using Unicode-aware width with caching
+                    let text = WIDTH_CACHE.with(|cache| {
+                        utils::left_align_cached(&mut cache.borrow_mut(), &text, width)
+                    });
+                    line_width += width;
+                    let term = match column.kind {
+                        QueueColumnKind::Title => title_term.as_deref(),
+                        QueueColumnKind::Artist => artist_term.as_deref(),
+                        QueueColumnKind::Album => album_term.as_deref(),
+                        _ => None,
+                    };
+                    spans.extend(utils::highlight_match(
+                        text,
+                        term,
+                        style,
+                        match_style(style),
+                    ));
+                }
+
+                // If this row is selected or underlined, add padding to fill the entire width
+                if is_selected || ends_album {
+                    let remaining_width = area.width.saturating_sub(line_width as u16) as usize;
 
                     if remaining_width > 0 {
                         // Add spaces to fill the remaining width with the selected background color
                         let padding = RENDER_CACHE.with(|cache| {
                             cache.borrow().fillers.spaces(remaining_width).to_owned()
                         });
-                        spans.push(Span::styled(padding, border_color));
+                        spans.push(Span::styled(padding, separator_style));
+                    }
+                }
+
+                // The separator is an underline in the border color across the whole row
+                if ends_album {
+                    for span in &mut spans {
+                        span.style = span.style.underlined().underline_color(border_color);
                     }
                 }
 
@@ -176,7 +239,7 @@ pub fn create_queue_widget<'a>(
                 .border_type(BorderType::Rounded)
                 .borders(Borders::ALL)
                 .title(Span::styled(
-                    " Queue ",
+                    queue_title(filter),
                     Style::default().fg(border_title_color),
                 ))
                 .border_style(Style::default().fg(border_color)),
@@ -185,3 +248,125 @@ pub fn create_queue_widget<'a>(
         .highlight_style(Style::default())
         .repeat_highlight_symbol(true)
 }
+
+/// Whether the queue row of `index` is the last of its album, with `next` the index of
+/// the row shown below it
+fn album_ends_before(queue: &[SongInfo], index: usize, next: usize) -> bool {
+    queue[index].album != queue[next].album
+}
+
+/// Title of the queue box, with the filter and its match count when one is set
+fn queue_title(filter: Option<&QueueFilter>) -> String {
+    match filter {
+        Some(filter) if filter.editing => format!(" Queue  filter: {}_ ", filter.query),
+        Some(filter) => format!(
+            " Queue  filter: {} ({} matches) ",
+            filter.query,
+            filter.matches.len()
+        ),
+        None => " Queue ".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn synthetic_queue(len: usize) -> Vec<SongInfo> {
+        (0..len)
+            .map(|i| SongInfo {
+                title: format!("Track {}", i),
+                artist: format!("Artist {}", i % 50),
+                artists: vec![format!("Artist {}", i % 50)],
+                album: format!("Album {}", i / 12),
+                file_path: PathBuf::from(format!("music/{}.flac", i)),
+                duration: Some(std::time::Duration::from_secs(200)),
+                track_number: (i % 12) as u64 + 1,
+                ..SongInfo::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_album_separator_rows() {
+        use ratatui::{buffer::Buffer, style::Modifier, widgets::StatefulWidget};
+
+        let queue = synthetic_queue(30);
+        let mut config = Config::default();
+        config.ui.queue_album_separators = true;
+        let area = Rect::new(0, 0, 80, 16);
+        // Underlined rows of the rendered window (the first row inside the border is 1)
+        let underlined_rows = |filter: Option<&QueueFilter>, window: Range<usize>| {
+            let state = ListState::default();
+            let list = create_queue_widget(&queue, filter, window, &state, None, &config, area);
+            let mut buffer = Buffer::empty(area);
+            StatefulWidget::render(list, area, &mut buffer, &mut ListState::default());
+            (1..area.height - 1)
+                .filter(|&y| buffer[(2, y)].modifier.contains(Modifier::UNDERLINED))
+                .map(|y| y - 1)
+                .collect::<Vec<_>>()
+        };
+
+        // "Album 0" is queue rows 0-11; the last row of the window only gets a line
+        // when the row after the window starts another album
+        assert_eq!(underlined_rows(None, 0..12), vec![11]);
+        assert_eq!(underlined_rows(None, 0..11), Vec::<u16>::new());
+        assert_eq!(underlined_rows(None, 6..20), vec![5]);
+
+        // Filtered rows compare with the next match, not the next queue position
+        let filter = QueueFilter {
+            matches: vec![0, 1, 13, 14],
+            ..Default::default()
+        };
+        assert_eq!(underlined_rows(Some(&filter), 0..4), vec![1]);
+    }
+
+    #[test]
+    #[ignore] // Performance benchmarks are non-deterministic; run with --ignored
+    fn bench_full_vs_windowed_queue() {
+        use std::time::Instant;
+
+        let queue = synthetic_queue(5000);
+        let config = Config::default();
+        let area = Rect::new(0, 0, 120, 42);
+        let mut state = ListState::default().with_selected(Some(2500));
+        let (window, _) = utils::visible_window(&mut state, queue.len(), area);
+
+        let start = Instant::now();
+        let mut full_rows = 0;
+        for _ in 0..20 {
+            full_rows =
+                create_queue_widget(&queue, None, 0..queue.len(), &state, None, &config, area)
+                    .len();
+        }
+        let full_duration = start.elapsed();
+
+        let start = Instant::now();
+        let mut windowed_rows = 0;
+        for _ in 0..20 {
+            windowed_rows =
+                create_queue_widget(&queue, None, window.clone(), &state, None, &config, area)
+                    .len();
+        }
+        let windowed_duration = start.elapsed();
+
+        println!("\nQueue rendering, 5000 tracks, 20 frames:");
+        println!(
+            "  Full:     {:?} ({} rows per frame)",
+            full_duration, full_rows
+        );
+        println!(
+            "  Windowed: {:?} ({} rows per frame)",
+            windowed_duration, windowed_rows
+        );
+        println!(
+            "  Speedup:  {:.1}x",
+            full_duration.as_nanos() as f64 / windowed_duration.as_nanos() as f64
+        );
+
+        assert_eq!(full_rows, 5000);
+        assert_eq!(windowed_rows, 40);
+        assert!(windowed_duration < full_duration);
+    }
+}
@@ -0,0 +1,41 @@
+use ratatui::{
+    Frame,
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{ListState, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+
+use crate::app::Config;
+
+/// Render a scrollbar on the right border of a bordered list panel.
+/// Nothing is drawn when scrollbars are disabled or all `len` items fit.
+pub fn render_list_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    len: usize,
+    list_state: &ListState,
+    config: &Config,
+) {
+    // Inside the top and bottom borders
+    let track = area.inner(Margin {
+        vertical: 1,
+        horizontal: 0,
+    });
+    let visible = track.height as usize;
+    if !config.ui.scrollbars || len <= visible || visible == 0 {
+        return;
+    }
+
+    // Offset is the first visible row, so the thumb spans exactly the visible part
+    let mut state = ScrollbarState::new(len.saturating_sub(visible))
+        .position(list_state.offset())
+        .viewport_content_length(visible);
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(config.colors.border_color()))
+        .thumb_style(Style::default().fg(config.colors.queue_selected_highlight_color()));
+
+    frame.render_stateful_widget(scrollbar, track, &mut state);
+}
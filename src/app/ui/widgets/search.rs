@@ -0,0 +1,139 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::navigation::search::parse_search_query;
+use crate::app::ui::rendering::utils;
+use crate::app::{Config, SongInfo};
+
+/// Render the database search overlay: the query line above the list of matching songs.
+/// While `editing`, the query shows a cursor and the selection is hidden.
+pub fn render_search_popup(
+    frame: &mut Frame,
+    query: &str,
+    editing: bool,
+    results: &[SongInfo],
+    selected: usize,
+    config: &Config,
+) {
+    let area = frame.area();
+
+    let title = " Search ";
+    let footer = if editing {
+        " Enter: search  Esc: close "
+    } else {
+        " Enter/a: add  A: add all  /: edit  Esc: close "
+    };
+
+    // Popup takes most of the screen so long titles fit
+    let popup_width = (area.width * 4 / 5).min(area.width.saturating_sub(4));
+    let popup_height = (area.height * 4 / 5).min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(title).fg(config.colors.border_title_color()))
+        .title_bottom(
+            Line::from(footer)
+                .fg(config.colors.top_accent_color())
+                .centered(),
+        )
+        .style(Style::default().bg(ratatui::style::Color::Black));
+    let inner = popup_block.inner(popup_area);
+    frame.render_widget(popup_block, popup_area);
+
+    // Query line, empty line, results
+    let [query_area, _, results_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(inner);
+
+    let cursor = if editing { "_" } else { "" };
+    let query_line = Line::from(vec![
+        Span::styled(
+            " Search: ",
+            Style::default().fg(config.colors.song_title_color()),
+        ),
+        Span::styled(
+            format!("{}{}", query, cursor),
+            Style::default().fg(config.colors.volume_color()),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(query_line), query_area);
+
+    if results.is_empty() {
+        let hint = if query.is_empty() {
+            " Prefix with title:, artist: or album: to search a single tag"
+        } else {
+            " No matches"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(hint).fg(config.colors.top_accent_color())),
+            results_area,
+        );
+        return;
+    }
+
+    // What the query matched is highlighted in the tags it searched, once the results
+    // belong to it (a query being edited may not match them)
+    let (tag, term) = parse_search_query(query);
+    let term = (!editing && !term.is_empty()).then(|| term.to_lowercase());
+    let highlight_term = |column| term.as_deref().filter(|_| tag == column || tag == "any");
+    let highlight = |text: String, column, color| {
+        let style = Style::default().fg(color);
+        utils::highlight_match(
+            text,
+            highlight_term(column),
+            style,
+            style.bold().underlined(),
+        )
+    };
+
+    let separator = &config.ui.artist_separator;
+    let items: Vec<ListItem> = results
+        .iter()
+        .map(|song| {
+            let mut spans = highlight(
+                format!(" {}", song.display_artists(separator)),
+                "artist",
+                config.colors.artist_color(),
+            );
+            spans.push(Span::raw(" - "));
+            spans.extend(highlight(
+                song.title.clone(),
+                "title",
+                config.colors.song_title_color(),
+            ));
+            spans.extend(highlight(
+                format!("  ({})", song.album),
+                "album",
+                config.colors.album_color(),
+            ));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(config.colors.queue_selected_text_color())
+            .bg(config.colors.queue_selected_highlight_color()),
+    );
+    let mut state = ListState::default().with_selected((!editing).then_some(selected));
+    frame.render_stateful_widget(list, results_area, &mut state);
+}
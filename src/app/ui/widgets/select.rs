@@ -0,0 +1,72 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a centered overlay listing `entries` with the `selected` one highlighted
+pub fn render_select_popup(
+    frame: &mut Frame,
+    title: &str,
+    entries: &[String],
+    selected: usize,
+    footer: &str,
+    config: &Config,
+) {
+    let area = frame.area();
+
+    // Popup width: widest line + padding (2 on each side) + borders (1 on each side)
+    let content_width = entries
+        .iter()
+        .map(|entry| entry.width() + 2)
+        .chain([footer.width(), title.width()])
+        .max()
+        .unwrap_or(0) as u16;
+    let popup_width = (content_width + 6).min(area.width.saturating_sub(4));
+
+    // Popup height: entries + empty line after title + empty line before footer + footer + borders (2)
+    let popup_height = (entries.len() as u16 + 5).min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    for (i, entry) in entries.iter().enumerate() {
+        let style = if i == selected {
+            Style::default()
+                .fg(config.colors.queue_selected_text_color())
+                .bg(config.colors.queue_selected_highlight_color())
+        } else {
+            Style::default().fg(config.colors.song_title_color())
+        };
+        lines.push(Line::from(Span::styled(format!(" {} ", entry), style)).centered());
+    }
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from(Span::styled(
+            footer,
+            Style::default().fg(config.colors.top_accent_color()),
+        ))
+        .centered(),
+    );
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(title).fg(config.colors.border_title_color()))
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    frame.render_widget(Paragraph::new(lines).block(popup_block), popup_area);
+}
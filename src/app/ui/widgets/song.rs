@@ -27,7 +27,10 @@ pub fn create_now_playing_widget<'a>(
                     Style::default().fg(song_title_color),
                 )]),
                 Line::from(vec![
-                    Span::styled(&song.artist, Style::default().fg(artist_color)),
+                    Span::styled(
+                        song.display_artists(&config.ui.artist_separator),
+                        Style::default().fg(artist_color),
+                    ),
                     Span::styled(" - ", Style::default().fg(border_title_color)),
                     Span::styled(&song.album, Style::default().fg(album_color)),
                 ]),
@@ -53,6 +56,7 @@ pub fn create_now_playing_widget<'a>(
 pub fn create_format_widget<'a>(
     format: &'a Option<String>,
     current_song: &'a Option<SongInfo>,
+    mpd_status: Option<&mpd_client::responses::Status>,
     config: &'a Config,
 ) -> Paragraph<'a> {
     let format_color = config.colors.song_title_color();
@@ -92,7 +96,29 @@ pub fn create_format_widget<'a>(
                         Style::default().fg(format_color),
                     );
 
-                    Line::from(vec![file_type_span, sample_rate_span])
+                    let mut spans = vec![file_type_span, sample_rate_span];
+
+                    // Live bitrate, only while a song is actually playing or paused
+                    if let Some(status) = mpd_status
+                        && status.state != mpd_client::responses::PlayState::Stopped
+                        && let Some(bitrate) = status.bitrate.filter(|&b| b > 0)
+                    {
+                        spans.push(Span::styled(
+                            format!(" {}kbps", bitrate),
+                            Style::default().fg(format_color),
+                        ));
+                    }
+
+                    // ReplayGain as tagged, to see how loud the track is meant to play
+                    if let Some(gain) = current_song.as_ref().and_then(|song| song.replay_gain) {
+                        spans.push(Span::styled("  RG ", Style::default().fg(accent_color)));
+                        spans.push(Span::styled(
+                            gain.label(),
+                            Style::default().fg(format_color),
+                        ));
+                    }
+
+                    Line::from(spans)
                 } else {
                     Line::from(f.clone())
                 }
@@ -0,0 +1,54 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a toast with `text` in the bottom left, above the progress bar
+///
+/// The left half never holds cover art, so the toast can't be drawn over by the image.
+pub fn render_toast(frame: &mut Frame, text: &str, config: &Config) {
+    let area = frame.area();
+
+    // Text + padding (1 on each side) + borders, at most half the screen
+    let max_width = (area.width / 2).saturating_sub(2);
+    let toast_width = (text.width() as u16 + 4).min(max_width);
+    let inner_width = toast_width.saturating_sub(4).max(1);
+    let text_lines = (text.width() as u16).div_ceil(inner_width).clamp(1, 3);
+    let toast_height = text_lines + 2;
+
+    // Keep clear of the progress bar (3 lines) and the bottom border
+    let bottom = area.y + area.height.saturating_sub(4);
+    if toast_width < 10 || bottom < area.y + toast_height {
+        return;
+    }
+
+    let toast_area = Rect {
+        x: area.x + 2,
+        y: bottom - toast_height,
+        width: toast_width,
+        height: toast_height,
+    };
+
+    frame.render_widget(Clear, toast_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.top_accent_color()))
+        .title(Line::from(" Error ").fg(config.colors.border_title_color()))
+        .padding(Padding::horizontal(1))
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(config.colors.song_title_color()))
+        .wrap(Wrap { trim: true })
+        .block(block);
+
+    frame.render_widget(paragraph, toast_area);
+}
@@ -5,6 +5,7 @@ use ratatui::{
 };
 
 use crate::app::Config;
+use crate::app::mpd_handler::RepeatMode;
 use crate::app::ui::MenuMode;
 use crate::app::ui::RENDER_CACHE;
 
@@ -14,6 +15,7 @@ pub fn create_top_box<'a>(
     menu_mode: &MenuMode,
     bit_perfect_enabled: bool,
     bit_perfect_available: bool,
+    muted: bool,
 ) -> Paragraph<'a> {
     let border_color = config.colors.border_color();
     let text_color = config.colors.song_title_color();
@@ -38,11 +40,15 @@ pub fn create_top_box<'a>(
             }
             spans.push(Span::raw(" "));
         }
-        // Repeat (󰑖)
-        if status.repeat {
-            spans.push(Span::styled("󰑖", Style::default().fg(accent_color).bold()));
-        } else {
-            spans.push(Span::styled("󰑖", Style::default().fg(text_color)));
+        // Repeat: off (󰑗), all (󰑖) or one (󰑘)
+        match RepeatMode::from_status(status) {
+            RepeatMode::Off => spans.push(Span::styled("󰑗", Style::default().fg(text_color))),
+            RepeatMode::All => {
+                spans.push(Span::styled("󰑖", Style::default().fg(accent_color).bold()))
+            }
+            RepeatMode::One => {
+                spans.push(Span::styled("󰑘", Style::default().fg(accent_color).bold()))
+            }
         }
         spans.push(Span::raw(" "));
 
@@ -55,7 +61,7 @@ pub fn create_top_box<'a>(
         spans.push(Span::raw(" "));
 
         // Single (󰒞)
-        if status.single != mpd_client::commands::SingleMode::Disabled {
+        if status.single == mpd_client::commands::SingleMode::Enabled {
             spans.push(Span::styled("󰒞", Style::default().fg(accent_color).bold()));
         } else {
             spans.push(Span::styled("󰒞", Style::default().fg(text_color)));
@@ -70,6 +76,12 @@ pub fn create_top_box<'a>(
         }
         spans.push(Span::raw(" "));
 
+        // Stop after current song (󰓛) - only shown while armed
+        if status.single == mpd_client::commands::SingleMode::Oneshot {
+            spans.push(Span::styled("󰓛", Style::default().fg(accent_color).bold()));
+            spans.push(Span::raw(" "));
+        }
+
         // Playback state and song count
         spans.push(Span::raw(" │  "));
 
@@ -101,8 +113,10 @@ pub fn create_top_box<'a>(
         // Visual volume display with Nerd Font icons
         let volume = status.volume;
 
-        // Volume icon based on level
-        let volume_icon = if volume == 0 {
+        // Volume icon based on level, crossed out while muted
+        let volume_icon = if muted {
+            "󰸈"
+        } else if volume == 0 {
             "󰝟"
         } else if volume < 33 {
             "󰕿"
@@ -130,6 +144,9 @@ pub fn create_top_box<'a>(
             Style::default().fg(volume_empty_color),
         ));
         spans.push(Span::styled(percent_str, Style::default().fg(text_color)));
+        if muted {
+            spans.push(Span::styled(" muted", Style::default().fg(paused)));
+        }
 
         // Menu mode indicator
         spans.push(Span::raw("  │  "));
@@ -137,6 +154,7 @@ pub fn create_top_box<'a>(
             MenuMode::Queue => (" ", accent_color, "Queue", mode_color),
             MenuMode::Artists => ("󰠃 ", accent_color, "Artists", mode_color),
             MenuMode::Albums => ("󰀥 ", accent_color, "Albums", mode_color),
+            MenuMode::Files => ("󰉋 ", accent_color, "Files", mode_color),
         };
         spans.push(Span::styled(mode_text.0, Style::default().fg(mode_text.1)));
         spans.push(Span::styled(mode_text.2, Style::default().fg(mode_text.3)));
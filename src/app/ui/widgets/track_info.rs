@@ -0,0 +1,87 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::Config;
+
+/// Render a centered, scrollable overlay listing every tag of the current song,
+/// preceded by any missing-tag `warnings`.
+/// `scroll` is clamped so the last tag stays at the bottom of the popup.
+pub fn render_track_info_popup(
+    frame: &mut Frame,
+    warnings: &[String],
+    fields: &[(String, String)],
+    scroll: usize,
+    config: &Config,
+) {
+    let area = frame.area();
+
+    let title = " Track Info ";
+    let footer = " j/k: scroll  Esc: close ";
+
+    // Popup takes most of the screen, tag values can be long
+    let popup_width = (area.width * 4 / 5).max(footer.width() as u16 + 4);
+    let popup_width = popup_width.min(area.width.saturating_sub(4));
+    // Popup height: warnings + tags + borders (2)
+    let line_count = warnings.len() + fields.len();
+    let popup_height = (line_count as u16 + 2).min(area.height.saturating_sub(2));
+
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(popup_width) / 2,
+        y: area.y + area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    // Align values by padding keys to the widest one
+    let key_width = fields.iter().map(|(k, _)| k.width()).max().unwrap_or(0);
+    let warning_lines = warnings.iter().map(|warning| {
+        Line::from(Span::styled(
+            format!(" ! {}", warning),
+            Style::default().fg(config.colors.top_accent_color()).bold(),
+        ))
+    });
+    let tag_lines = fields.iter().map(|(key, value)| {
+        Line::from(vec![
+            Span::styled(
+                format!(" {:<width$}  ", key, width = key_width),
+                Style::default().fg(config.colors.top_accent_color()),
+            ),
+            Span::styled(
+                value.as_str(),
+                Style::default().fg(config.colors.song_title_color()),
+            ),
+        ])
+    });
+    let lines: Vec<Line> = warning_lines.chain(tag_lines).collect();
+
+    let visible = popup_height.saturating_sub(2) as usize;
+    let scroll = scroll.min(line_count.saturating_sub(visible)) as u16;
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(config.colors.queue_selected_highlight_color()))
+        .title(Line::from(title).fg(config.colors.border_title_color()))
+        .title_bottom(
+            Line::from(footer)
+                .fg(config.colors.top_accent_color())
+                .centered(),
+        )
+        .style(Style::default().bg(ratatui::style::Color::Black));
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(popup_block)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0)),
+        popup_area,
+    );
+}
@@ -6,6 +6,7 @@ use app::{
     cli::Args,
     config::Config,
     logging,
+    main_loop::{connect_with_timeout, connection_failure_message},
     terminal::{init_terminal, restore_terminal},
 };
 use clap::Parser;
@@ -43,11 +44,20 @@ async fn main() -> color_eyre::Result<()> {
         config.mpd.address = addr.clone();
     }
 
+    // -v/-q and --log-file take precedence over the [logging] section
+    if let Some(level) = args.log_level() {
+        config.logging.level = level.to_string().to_lowercase();
+    }
+    if let Some(ref path) = args.log_file {
+        config.logging.enabled = true;
+        config.logging.custom_log_path = Some(path.to_string_lossy().into_owned());
+    }
+
     // Initialize logger first
     if config.logging.enabled {
-        logging::ensure_log_directory()?;
+        logging::ensure_log_directory(&config.logging)?;
         logging::init_logger(&config.logging)?;
-        logging::log_startup_info();
+        logging::log_startup_info(&config.logging);
         // Log config loading now that logger is initialized
         logging::log_config_loading(&config_path, !config_existed);
 
@@ -57,6 +67,21 @@ async fn main() -> color_eyre::Result<()> {
         }
     }
 
+    // Connect before taking over the terminal, so a failure is a plain message on stderr
+    log::info!("Attempting to connect to MPD at: {}", config.mpd.address);
+    let connection =
+        match connect_with_timeout(&config.mpd.address, config.mpd.connect_timeout()).await {
+            Ok(connection) => {
+                logging::log_mpd_connection(&config.mpd.address, true, None);
+                connection
+            }
+            Err(e) => {
+                logging::log_mpd_connection(&config.mpd.address, false, Some(&e.to_string()));
+                eprintln!("{}", connection_failure_message(&config.mpd.address, &e));
+                std::process::exit(1);
+            }
+        };
+
     // Initialize terminal
     let terminal = init_terminal()?;
 
@@ -83,7 +108,7 @@ async fn main() -> color_eyre::Result<()> {
     }
 
     // Run application
-    let result = app.run(terminal).await;
+    let result = app.run(terminal, connection).await;
 
     // Log shutdown before restoring terminal
     if logging_enabled {